@@ -0,0 +1,1061 @@
+use crate::migrators::detect::SOURCE_NAMES;
+use crate::utils::build_system::BUILD_BACKENDS;
+use clap::parser::ValueSource;
+use clap::{Arg, ArgMatches, Command};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Format of the summary printed after a migration
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable log output
+    #[default]
+    Text,
+    /// A JSON migration summary on stdout
+    Json,
+}
+
+/// Command line arguments controlling a migration run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Args {
+    pub path: Option<PathBuf>,
+    pub merge_groups: bool,
+    pub import_global_pip_conf: bool,
+    pub import_index: Vec<String>,
+    pub strip_comments: bool,
+    pub no_reorder: bool,
+    pub verbose_subprocess: bool,
+    pub generate_gitignore: bool,
+    pub report_skipped: Option<PathBuf>,
+    pub dry_run: bool,
+    pub no_pin_python: bool,
+    pub output_format: OutputFormat,
+    pub merge_extras_into_main: bool,
+    pub prefer_poetry_table: bool,
+    /// Keep old.pyproject.toml after a successful migration
+    pub keep_old: bool,
+    pub validate_markers: bool,
+    pub strict: bool,
+    pub conda_include_system: bool,
+    pub copy_readme: bool,
+    /// Number of times `-v`/`--verbose` was passed
+    pub verbose: u8,
+    pub quiet: bool,
+    pub lock: bool,
+    /// Extra arguments passed through to `uv lock`
+    pub lock_args: Vec<String>,
+    /// Project type to migrate from when several are detected
+    pub from: Option<String>,
+    pub strict_detection: bool,
+    /// Python version passed to `uv init` instead of the detected one
+    pub python: Option<String>,
+    /// Conda environment file to migrate instead of environment.yml
+    pub environment_file: Option<PathBuf>,
+    pub merge_optional: bool,
+    pub stamp: bool,
+    /// Shell command run in the project directory after a successful migration
+    pub post_hook: Option<String>,
+    /// Extra Conda to PyPI package name mappings, as `conda_name=pypi_name`
+    pub conda_map: Vec<String>,
+    /// Group for requirements-dev.txt dependencies instead of the dev group
+    pub dev_group_name: Option<String>,
+    /// Version control system `uv init` sets up, passed as `--vcs`
+    pub vcs: String,
+    /// Migrate even when the project already looks migrated to uv
+    pub force: bool,
+    /// Maximum number of `uv add` commands to run at the same time
+    pub max_parallel_adds: usize,
+    /// `[tool.uv] index-strategy` to write, one of [`INDEX_STRATEGIES`]
+    pub index_strategy: Option<String>,
+    pub prune_empty_groups: bool,
+    /// Build backend for migrated packages, one of [`BUILD_BACKENDS`]
+    pub build_backend: String,
+    /// Copy `[tool.poetry]` to `[tool.poetry-legacy]` in the migrated pyproject.toml
+    pub keep_poetry_section: bool,
+    /// Remove `__pycache__` directories and `.pyc` files after a successful migration
+    pub clean_pycache: bool,
+    /// Keep the group entry rather than the main one for packages declared in both
+    pub prefer_group: bool,
+    /// Only migrate dependencies, leaving metadata and tool sections alone
+    pub dependencies_only: bool,
+    /// Only migrate metadata and tool sections, without adding dependencies
+    pub metadata_only: bool,
+    /// Import index URLs from `PIP_INDEX_URL` and similar environment variables
+    pub import_env_indexes: bool,
+    /// Report the environment variables uv reads index credentials from
+    pub index_auth_env: bool,
+    /// Produce byte-identical output for identical input
+    pub deterministic: bool,
+    /// Re-attach trailing comments of Poetry dependencies to the migrated entries
+    pub preserve_dep_comments: bool,
+    /// How often to retry a uv command that failed with a transient network error
+    pub uv_retries: u32,
+    /// Leave out dependencies with an invalid version instead of failing
+    pub skip_invalid: bool,
+    #[cfg(feature = "self_update")]
+    pub self_update: bool,
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Args {
+            path: None,
+            merge_groups: false,
+            import_global_pip_conf: false,
+            import_index: Vec::new(),
+            strip_comments: false,
+            no_reorder: false,
+            verbose_subprocess: false,
+            generate_gitignore: false,
+            report_skipped: None,
+            dry_run: false,
+            no_pin_python: false,
+            output_format: OutputFormat::default(),
+            merge_extras_into_main: false,
+            prefer_poetry_table: false,
+            keep_old: true,
+            validate_markers: false,
+            strict: false,
+            conda_include_system: false,
+            copy_readme: false,
+            verbose: 0,
+            quiet: false,
+            lock: false,
+            lock_args: Vec::new(),
+            from: None,
+            strict_detection: false,
+            python: None,
+            environment_file: None,
+            merge_optional: false,
+            stamp: false,
+            post_hook: None,
+            conda_map: Vec::new(),
+            dev_group_name: None,
+            vcs: "none".to_string(),
+            force: false,
+            max_parallel_adds: 1,
+            index_strategy: None,
+            prune_empty_groups: false,
+            build_backend: "hatchling".to_string(),
+            keep_poetry_section: false,
+            clean_pycache: false,
+            prefer_group: false,
+            dependencies_only: false,
+            metadata_only: false,
+            import_env_indexes: false,
+            index_auth_env: false,
+            deterministic: false,
+            preserve_dep_comments: false,
+            uv_retries: 0,
+            skip_invalid: false,
+            #[cfg(feature = "self_update")]
+            self_update: false,
+        }
+    }
+}
+
+impl Args {
+    /// The log level requested with `-v`/`--verbose` or `--quiet`, if any
+    pub fn log_level(&self) -> Option<&'static str> {
+        match (self.quiet, self.verbose) {
+            (true, _) => Some("warn"),
+            (false, 0) => None,
+            (false, 1) => Some("debug"),
+            (false, _) => Some("trace"),
+        }
+    }
+}
+
+/// Builds the clap command definition for uv-migrator
+pub fn build_command() -> Command {
+    let cmd = Command::new("uv-migrator")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("A tool for migrating Python projects to use the uv package manager")
+        .long_about(
+            "UV Migrator helps you convert Python projects from various dependency management systems \
+            (like Poetry or pip) to use the UV package manager. It preserves your dependencies, \
+            development configurations, and project structure while setting up a new UV-based environment."
+        )
+        .arg(
+            Arg::new("PATH")
+                .help("The path to the project directory to migrate")
+                .long_help(
+                    "Specifies the directory containing the Python project to migrate. \
+                    This should be the root directory of your project where pyproject.toml \
+                    or requirements.txt is located."
+                )
+                .value_parser(clap::value_parser!(String))
+        )
+        .arg(
+            Arg::new("merge-groups")
+                .long("merge-groups")
+                .help("Merge all dependency groups into the dev group")
+                .long_help(
+                    "When this flag is set, all dependency groups (including custom groups) \
+                    will be merged into the dev group. This is useful when you want to \
+                    simplify your dependency management by having only main and dev dependencies."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("import-global-pip-conf")
+                .long("import-global-pip-conf")
+                .help("Import extra index URLs from ~/.pip/pip.conf")
+                .long_help(
+                    "Reads and imports the index-url and extra-index-url settings of the [global] \
+                    and [install] sections of your pip configuration file (~/.pip/pip.conf, or the \
+                    file named by PIP_CONFIG_FILE). This is useful when your project requires \
+                    packages from private or alternative Python package indexes."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("import-index")
+                .long("import-index")
+                .help("Additional index URL to import")
+                .long_help(
+                    "Specifies additional Python package index URLs to use. You can provide this \
+                    option multiple times to add several index URLs. These URLs will be added to \
+                    your project's pyproject.toml as [[tool.uv.index]] entries."
+                )
+                .action(clap::ArgAction::Append)
+                .value_parser(clap::value_parser!(String))
+        )
+        .arg(
+            Arg::new("merge-extras-into-main")
+                .long("merge-extras-into-main")
+                .help("Merge all optional dependencies into the main dependencies")
+                .long_help(
+                    "When this flag is set, every dependency listed under \
+                    [project.optional-dependencies] is also added to [project.dependencies]. \
+                    This is the extras counterpart of --merge-groups and changes install \
+                    semantics: extras are installed by default."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("strip-comments")
+                .long("strip-comments")
+                .help("Remove comments from the migrated pyproject.toml")
+                .long_help(
+                    "By default comments carried over from the original tool sections are preserved. \
+                    When this flag is set, the comments copied from the original project are stripped \
+                    from the generated pyproject.toml. Comments written by the migration itself, such \
+                    as the Conda channels and --stamp, are kept. Cannot be combined with \
+                    --preserve-dep-comments or --index-auth-env, whose output is comments."
+                )
+                .conflicts_with_all(["preserve-dep-comments", "index-auth-env"])
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("no-reorder")
+                .long("no-reorder")
+                .help("Skip reordering the sections of the migrated pyproject.toml")
+                .long_help(
+                    "By default the generated pyproject.toml is reordered so that [project] and \
+                    [build-system] come first and tool sections come last. When this flag is set \
+                    the document is left in the order produced by the migration steps."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("verbose-subprocess")
+                .long("verbose-subprocess")
+                .help("Print the exact uv command lines before running them")
+                .long_help(
+                    "Logs every uv command executed during the migration, including the full \
+                    argument list and working directory, so it can be reproduced by hand."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("generate-gitignore")
+                .long("generate-gitignore")
+                .help("Add UV-related entries to .gitignore")
+                .long_help(
+                    "Appends entries such as .venv/ and __pycache__/ to the project's .gitignore, \
+                    creating the file if it does not exist. Entries that are already present are \
+                    not duplicated."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("report-skipped")
+                .long("report-skipped")
+                .value_name("PATH")
+                .help("Write skipped packages and unparseable lines to a file")
+                .long_help(
+                    "Writes every package, marker, line or dependency that was left out of the \
+                    migration to the given file together with the reason it was skipped. Paths \
+                    ending in .csv are written as CSV, anything else as plain text."
+                )
+                .value_parser(clap::value_parser!(PathBuf))
+        )
+        .arg(
+            Arg::new("dry-run")
+                .long("dry-run")
+                .help("Print the planned migration without changing any files")
+                .long_help(
+                    "Detects the project and extracts its dependencies, then logs the uv commands \
+                    and file changes the migration would make without running them. The project \
+                    directory is left untouched."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("no-pin-python")
+                .long("no-pin-python")
+                .help("Do not pin the detected Python version during uv init")
+                .long_help(
+                    "By default a Python version detected in the project is passed to uv init \
+                    with --python. When this flag is set no version is passed and uv chooses \
+                    the interpreter, which helps when the detected version is not installed."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("output-format")
+                .long("output-format")
+                .value_name("FORMAT")
+                .help("Output format for the migration summary")
+                .long_help(
+                    "Selects how the result of the migration is reported. 'text' (the default) \
+                    logs progress messages. 'json' prints a machine-readable summary to stdout \
+                    and keeps stdout free of anything else so it can be piped into tools like jq."
+                )
+                .value_parser(["text", "json"])
+                .default_value("text")
+        )
+        .arg(
+            Arg::new("prefer-poetry-table")
+                .long("prefer-poetry-table")
+                .help("Prefer [tool.poetry.dependencies] over [project].dependencies")
+                .long_help(
+                    "When a package is declared in both the PEP 621 [project].dependencies array \
+                    and the [tool.poetry.dependencies] table, the [project] version is used by \
+                    default. When this flag is set the [tool.poetry] version wins instead, which \
+                    suits partially migrated files where tool.poetry is still authoritative."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("keep-old")
+                .long("keep-old")
+                .value_name("BOOL")
+                .help("Keep old.pyproject.toml after a successful migration")
+                .long_help(
+                    "The original pyproject.toml is renamed to old.pyproject.toml during the \
+                    migration. It is kept by default; pass --keep-old=false to delete it once \
+                    every migration step has succeeded. It is restored if the migration fails."
+                )
+                .value_parser(clap::value_parser!(bool))
+                .num_args(0..=1)
+                .require_equals(true)
+                .default_value("true")
+                .default_missing_value("true")
+        )
+        .arg(
+            Arg::new("validate-markers")
+                .long("validate-markers")
+                .help("Check environment markers are valid PEP 508 before running uv add")
+                .long_help(
+                    "Parses the environment marker of every extracted dependency as a PEP 508 \
+                    marker expression and warns about invalid ones, which would otherwise make \
+                    uv add fail with a hard to read error. Combine with --strict to stop the \
+                    migration instead."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .help("Fail instead of warning when --validate-markers finds an invalid marker")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("conda-include-system")
+                .long("conda-include-system")
+                .help("Migrate every Conda package, including system packages that are normally skipped")
+                .long_help(
+                    "Disables the list of Conda system packages (compilers, BLAS, OpenSSL and the like) \
+                    that are skipped because they have no PyPI equivalent, so every package in \
+                    environment.yml is migrated. Useful when one of those names is actually a \
+                    package you publish yourself."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("copy-readme")
+                .long("copy-readme")
+                .help("Set [project].readme to the README the original project uses")
+                .long_help(
+                    "Points [project].readme at the README referenced by the original project \
+                    ([project].readme, [tool.poetry].readme or a setup.cfg long_description file), \
+                    falling back to a README in the project root. A warning is printed when the \
+                    referenced file does not exist."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Show debug output, or trace output when repeated (-vv)")
+                .long_help(
+                    "Raises the log level to debug, or to trace when passed twice. Takes \
+                    precedence over the RUST_LOG environment variable."
+                )
+                .action(clap::ArgAction::Count)
+        )
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .help("Only show warnings and errors")
+                .conflicts_with("verbose")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("lock")
+                .long("lock")
+                .help("Run uv lock after the migration")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("lock-arg")
+                .long("lock-arg")
+                .value_name("ARG")
+                .help("Extra argument to pass to uv lock, implies --lock")
+                .long_help(
+                    "Passes an argument through to the uv lock run after the migration, for example \
+                    --lock-arg=--upgrade or --lock-arg=--index-strategy=unsafe-best-match. Can be \
+                    given multiple times and implies --lock."
+                )
+                .allow_hyphen_values(true)
+                .action(clap::ArgAction::Append)
+                .value_parser(clap::value_parser!(String))
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .value_name("TYPE")
+                .help("Project type to migrate from when several are detected")
+                .value_parser(clap::builder::PossibleValuesParser::new(SOURCE_NAMES))
+        )
+        .arg(
+            Arg::new("strict-detection")
+                .long("strict-detection")
+                .help("Fail when more than one project type is detected instead of picking one")
+                .long_help(
+                    "By default the highest priority project type wins when a directory contains \
+                    several (for example a pyproject.toml with [tool.poetry] and a Pipfile). With \
+                    this flag the migration fails and lists the detected types, so the choice has \
+                    to be made explicitly with --from."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("python")
+                .long("python")
+                .value_name("VERSION")
+                .help("Python version to pin with uv init instead of the detected one")
+                .long_help(
+                    "Overrides the Python version detected from the project (for example the \
+                    Poetry python constraint or the Conda environment). Must be given as X.Y or \
+                    X.Y.Z."
+                )
+                .conflicts_with("no-pin-python")
+                .value_parser(parse_python_version)
+        )
+        .arg(
+            Arg::new("environment-file")
+                .long("environment-file")
+                .value_name("PATH")
+                .help("Conda environment file to migrate instead of environment.yml")
+                .long_help(
+                    "Migrates the project as a Conda project using the given environment file, \
+                    for example conda.yml or dev-environment.yml. Relative paths are resolved \
+                    against the project directory."
+                )
+                .conflicts_with("from")
+                .value_parser(clap::value_parser!(PathBuf))
+        )
+        .arg(
+            Arg::new("merge-optional")
+                .long("merge-optional")
+                .help("Also merge optional Poetry groups when using --merge-groups")
+                .long_help(
+                    "Poetry groups declared with optional = true keep their own group when \
+                    --merge-groups is used, so optional docs or benchmark dependencies do not \
+                    become unconditional dev dependencies. With this flag they are merged into \
+                    the dev group as well."
+                )
+                .requires("merge-groups")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("stamp")
+                .long("stamp")
+                .help("Add a comment to pyproject.toml recording the migration")
+                .long_help(
+                    "Writes a comment at the top of the migrated pyproject.toml noting the source \
+                    project type, the uv-migrator version and the date of the migration, so it \
+                    can be audited later."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("post-hook")
+                .long("post-hook")
+                .value_name("COMMAND")
+                .help("Command to run in the project directory after a successful migration")
+                .long_help(
+                    "Runs the given shell command in the project directory once the migration \
+                    has fully succeeded, for example 'ruff format' or 'pre-commit run'. The hook \
+                    never runs after a rollback, and a failing hook is reported as a warning \
+                    without undoing the migration."
+                )
+                .value_parser(clap::value_parser!(String))
+        )
+        .arg(
+            Arg::new("conda-map")
+                .long("conda-map")
+                .value_name("NAME=PYPI_NAME")
+                .help("Map a Conda package name to its PyPI name (can be repeated)")
+                .long_help(
+                    "Adds a Conda to PyPI package name mapping, for example \
+                    --conda-map pytorch-lightning=lightning. Mappings given here take precedence \
+                    over the built-in table. Can be used multiple times."
+                )
+                .action(clap::ArgAction::Append)
+                .value_parser(|value: &str| parse_conda_map(value).map(|_| value.to_string()))
+        )
+        .arg(
+            Arg::new("dev-group-name")
+                .long("dev-group-name")
+                .value_name("NAME")
+                .help("Add requirements-dev.txt dependencies to this group instead of dev")
+                .long_help(
+                    "By default the dependencies of requirements-dev.txt are added to the dev \
+                    group. With this option they are added to the named dependency group \
+                    instead, keeping them separate from the dev group."
+                )
+                .value_parser(clap::builder::NonEmptyStringValueParser::new())
+        )
+        .arg(
+            Arg::new("vcs")
+                .long("vcs")
+                .value_name("VCS")
+                .help("Version control system for uv init to set up")
+                .long_help(
+                    "Passed to uv init as --vcs. Defaults to none, so the migration never \
+                    creates or modifies a repository; use git to let uv initialize one."
+                )
+                .default_value("none")
+                .value_parser(["git", "none"])
+        )
+        .arg(
+            Arg::new("force")
+                .long("force")
+                .help("Migrate even if the project already has a uv.lock")
+                .long_help(
+                    "A uv.lock next to a pyproject.toml without Poetry, PDM or Pipenv \
+                    configuration means the project was already migrated, and uv-migrator \
+                    refuses to run again. Use this to migrate it anyway."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("max-parallel-adds")
+                .long("max-parallel-adds")
+                .value_name("N")
+                .help("Maximum number of uv add commands to run concurrently")
+                .long_help(
+                    "Dependencies are added with one uv add per dependency group. Concurrent uv \
+                    add calls update the same pyproject.toml and uv.lock and can race, so by \
+                    default they run one at a time. Raise this to add groups in parallel; a \
+                    warning is logged because the adds may overwrite each other's changes."
+                )
+                .default_value("1")
+                .value_parser(clap::value_parser!(u64).range(1..))
+        )
+        .arg(
+            Arg::new("index-strategy")
+                .long("index-strategy")
+                .value_name("STRATEGY")
+                .help("Set index-strategy under [tool.uv] in pyproject.toml")
+                .long_help(
+                    "Writes index-strategy to [tool.uv], controlling how uv resolves packages \
+                    available from several indexes. Often needed together with private \
+                    indexes, for example unsafe-best-match."
+                )
+                .value_parser(clap::builder::PossibleValuesParser::new(INDEX_STRATEGIES))
+        )
+        .arg(
+            Arg::new("prune-empty-groups")
+                .long("prune-empty-groups")
+                .help("Remove dependency groups and extras that have no dependencies")
+                .long_help(
+                    "Removes empty entries from [dependency-groups] and \
+                    [project.optional-dependencies] in the migrated pyproject.toml, and the \
+                    tables themselves once nothing is left in them."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("build-backend")
+                .long("build-backend")
+                .value_name("NAME")
+                .help("Build backend for migrated packages")
+                .long_help(
+                    "Build backend written to [build-system] when a Poetry package or a library \
+                    with scripts is migrated. Poetry include entries are only migrated for \
+                    hatchling."
+                )
+                .default_value("hatchling")
+                .value_parser(clap::builder::PossibleValuesParser::new(BUILD_BACKENDS))
+        )
+        .arg(
+            Arg::new("keep-poetry-section")
+                .long("keep-poetry-section")
+                .help("Keep a copy of [tool.poetry] as [tool.poetry-legacy]")
+                .long_help(
+                    "Copies the original [tool.poetry] table into the migrated pyproject.toml \
+                    as [tool.poetry-legacy], for reference during a gradual migration. The new \
+                    name keeps uv-migrator and other tools from treating the project as Poetry."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("clean-pycache")
+                .long("clean-pycache")
+                .help("Remove __pycache__ directories and .pyc files after migrating")
+                .long_help(
+                    "After a successful migration, removes __pycache__ directories and stray \
+                    .pyc files under the project directory. Symlinks are never followed, and \
+                    hidden directories, node_modules and virtual environments are left alone. \
+                    Nothing is removed when the migration is rolled back or with --dry-run."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("prefer-group")
+                .long("prefer-group")
+                .help("Keep group entries for packages that are also main dependencies")
+                .long_help(
+                    "A package declared both as a main dependency and in a dev or named group is \
+                    only added once. By default the main dependency wins and the group entry is \
+                    dropped; with this flag the group entries are kept and the main one is dropped."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("dependencies-only")
+                .long("dependencies-only")
+                .help("Only migrate dependencies, skipping metadata and tool sections")
+                .long_help(
+                    "Initializes the uv project and adds its dependencies, together with the \
+                    package indexes and sources they need, but does not migrate project metadata \
+                    such as the description, authors, URLs, scripts or build system, nor any \
+                    [tool.*] sections."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("metadata-only")
+                .long("metadata-only")
+                .help("Only migrate metadata and tool sections, without adding dependencies")
+                .long_help(
+                    "Initializes the uv project and migrates the project metadata, authors, URLs, \
+                    scripts and [tool.*] sections, but does not run uv add. Useful when the \
+                    dependencies are already managed with uv."
+                )
+                .conflicts_with("dependencies-only")
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("import-env-indexes")
+                .long("import-env-indexes")
+                .help("Import index URLs from PIP_INDEX_URL and related environment variables")
+                .long_help(
+                    "Reads index URLs from the PIP_INDEX_URL, PIP_EXTRA_INDEX_URL, UV_INDEX_URL \
+                    and UV_EXTRA_INDEX_URL environment variables and adds them to [[tool.uv.index]] \
+                    along with any --import-index URLs. The extra index variables may list several \
+                    URLs separated by whitespace. UV_INDEX_URL or PIP_INDEX_URL replaces PyPI and is \
+                    written with default = true. Credentials in the URLs are not written; uv reads \
+                    them from UV_INDEX_<NAME>_USERNAME and UV_INDEX_<NAME>_PASSWORD."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("index-auth-env")
+                .long("index-auth-env")
+                .help("Report the environment variables uv reads index credentials from")
+                .long_help(
+                    "For every index other than PyPI in [[tool.uv.index]], logs the \
+                    UV_INDEX_<NAME>_USERNAME and UV_INDEX_<NAME>_PASSWORD variables uv reads its \
+                    credentials from, and records them in a comment above [tool.uv], so CI \
+                    secrets can be wired up."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("deterministic")
+                .long("deterministic")
+                .help("Produce identical output for identical input, for CI")
+                .long_help(
+                    "Guarantees that migrating the same project twice produces byte-identical \
+                    pyproject.toml and --report-skipped files, on any machine. Dependencies are \
+                    added in a stable order (main, dev, then groups and extras by name, packages \
+                    by name), skipped items are sorted, and --stamp leaves out the date."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("preserve-dep-comments")
+                .long("preserve-dep-comments")
+                .help("Keep trailing comments of Poetry dependencies")
+                .long_help(
+                    "Best effort: comments written after a Poetry dependency, e.g. \
+                    `torch = \"^2.0\"  # CUDA build`, are copied from old.pyproject.toml onto a \
+                    comment line above the matching entry in the migrated dependency lists."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+        .arg(
+            Arg::new("uv-retries")
+                .long("uv-retries")
+                .value_name("N")
+                .help("Retry uv commands that fail with transient network errors")
+                .long_help(
+                    "Retries a failed uv command up to N times, waiting 1s, 2s, 4s, ... in \
+                    between, when its error output points at a transient network problem: a \
+                    timeout, a reset connection, or an HTTP 429 or 503 response. Other failures, \
+                    such as resolution errors, fail the migration right away."
+                )
+                .default_value("0")
+                .value_parser(clap::value_parser!(u32))
+        )
+        .arg(
+            Arg::new("skip-invalid")
+                .long("skip-invalid")
+                .help("Leave out dependencies with an invalid version instead of failing")
+                .long_help(
+                    "Every version constraint is checked before the project is changed, and a \
+                    malformed one such as ^^0.1 or >=,<2 stops the migration with a list of all \
+                    offending dependencies. With this flag they are left out with a warning and \
+                    listed as skipped instead."
+                )
+                .action(clap::ArgAction::SetTrue)
+        );
+
+    #[cfg(feature = "self_update")]
+    let cmd = {
+        cmd.arg(
+            Arg::new("self_update")
+                .long("self-update")
+                .help("Update uv-migrator to the latest version")
+                .long_help(
+                    "Checks for and downloads the latest version of uv-migrator from GitHub releases. \
+                    The tool will automatically update itself if a newer version is available."
+                )
+                .action(clap::ArgAction::SetTrue)
+        )
+    };
+
+    // Options apply to the plan subcommand as well as to a direct migration
+    let cmd = cmd
+        .mut_args(|arg| {
+            if arg.is_positional() {
+                arg
+            } else {
+                arg.global(true)
+            }
+        })
+        .args_conflicts_with_subcommands(true)
+        .subcommand(
+            Command::new("plan")
+                .about("Compute a migration plan without applying it")
+                .long_about(
+                    "Detects the project, extracts its dependencies and computes the exact uv \
+                    commands of the migration, then writes them to a plan file that can be \
+                    reviewed and later executed with 'uv-migrator apply'.",
+                )
+                .arg(
+                    Arg::new("PATH")
+                        .help("The path to the project directory to plan a migration for")
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    Arg::new("output")
+                        .short('o')
+                        .long("output")
+                        .value_name("FILE")
+                        .help("Write the plan to this file instead of stdout")
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        )
+        .subcommand(
+            Command::new("apply")
+                .about("Apply a migration plan written by 'uv-migrator plan'")
+                .long_about(
+                    "Runs the uv commands recorded in a plan file with the options the plan was \
+                    made with. Only --dry-run, --verbose, --quiet and --output-format may be \
+                    given; --dry-run turns the plan into a dry run.",
+                )
+                .arg(
+                    Arg::new("PLAN")
+                        .help("The plan file to apply")
+                        .required(true)
+                        .value_parser(clap::value_parser!(PathBuf)),
+                ),
+        );
+
+    let after_help = {
+        let mut help = String::from(
+            "EXAMPLES:\n\
+            # Migrate a project in the current directory\n\
+            uv-migrator .\n\
+            \n\
+            # Merge all dependency groups into dev dependencies\n\
+            uv-migrator . --merge-groups\n\
+            \n\
+            # Migrate a project with a private package index\n\
+            uv-migrator . --import-index https://private.pypi.org/simple/\n\
+            \n\
+            # Migrate using global pip configuration\n\
+            uv-migrator . --import-global-pip-conf\n\
+            \n\
+            # Review a migration plan before applying it\n\
+            uv-migrator plan . -o plan.json\n\
+            uv-migrator apply plan.json\n",
+        );
+
+        #[cfg(feature = "self_update")]
+        {
+            help.push_str(
+                "\n\
+                # Update uv-migrator to the latest version\n\
+                uv-migrator --self-update\n",
+            );
+        }
+
+        help.push_str(
+            "\n\
+            EXIT CODES:\n\
+            0  Migration succeeded\n\
+            1  Migration failed\n\
+            2  No supported project was detected\n\
+            \n\
+            For more information and documentation, visit:\n\
+            https://github.com/stvnksslr/uv-migrator",
+        );
+
+        help
+    };
+
+    cmd.after_help(after_help)
+}
+
+/// Splits a `--conda-map` value of the form `conda_name=pypi_name`
+pub fn parse_conda_map(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((conda, pypi)) if !conda.trim().is_empty() && !pypi.trim().is_empty() => {
+            Ok((conda.trim().to_string(), pypi.trim().to_string()))
+        }
+        _ => Err(format!(
+            "invalid Conda mapping '{}': expected NAME=PYPI_NAME",
+            value
+        )),
+    }
+}
+
+/// Values accepted by `--index-strategy`, matching uv's `index-strategy` setting
+pub const INDEX_STRATEGIES: &[&str] = &["first-index", "unsafe-first-match", "unsafe-best-match"];
+
+/// Validates an `--index-strategy` value against [`INDEX_STRATEGIES`]
+pub fn parse_index_strategy(value: &str) -> Result<String, String> {
+    if INDEX_STRATEGIES.contains(&value) {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "invalid index strategy '{}': expected one of {}",
+            value,
+            INDEX_STRATEGIES.join(", ")
+        ))
+    }
+}
+
+/// Validates a `--python` value of the form `X.Y` or `X.Y.Z`
+pub fn parse_python_version(value: &str) -> Result<String, String> {
+    let parts: Vec<&str> = value.split('.').collect();
+    let valid = (2..=3).contains(&parts.len())
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    if valid {
+        Ok(value.to_string())
+    } else {
+        Err(format!(
+            "'{}' is not a Python version, expected X.Y or X.Y.Z (e.g. 3.12)",
+            value
+        ))
+    }
+}
+
+/// Converts parsed clap matches into [`Args`]
+pub fn args_from_matches(matches: &ArgMatches) -> Args {
+    Args {
+        path: matches
+            .try_get_one::<String>("PATH")
+            .ok()
+            .flatten()
+            .map(PathBuf::from),
+        merge_groups: matches.get_flag("merge-groups"),
+        import_global_pip_conf: matches.get_flag("import-global-pip-conf"),
+        import_index: matches
+            .get_many::<String>("import-index")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        strip_comments: matches.get_flag("strip-comments"),
+        no_reorder: matches.get_flag("no-reorder"),
+        verbose_subprocess: matches.get_flag("verbose-subprocess"),
+        generate_gitignore: matches.get_flag("generate-gitignore"),
+        report_skipped: matches.get_one::<PathBuf>("report-skipped").cloned(),
+        dry_run: matches.get_flag("dry-run"),
+        no_pin_python: matches.get_flag("no-pin-python"),
+        output_format: match matches
+            .get_one::<String>("output-format")
+            .map(String::as_str)
+        {
+            Some("json") => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        },
+        merge_extras_into_main: matches.get_flag("merge-extras-into-main"),
+        prefer_poetry_table: matches.get_flag("prefer-poetry-table"),
+        keep_old: matches.get_one::<bool>("keep-old").copied().unwrap_or(true),
+        validate_markers: matches.get_flag("validate-markers"),
+        strict: matches.get_flag("strict"),
+        conda_include_system: matches.get_flag("conda-include-system"),
+        copy_readme: matches.get_flag("copy-readme"),
+        verbose: matches.get_count("verbose"),
+        quiet: matches.get_flag("quiet"),
+        lock: matches.get_flag("lock"),
+        from: matches.get_one::<String>("from").cloned(),
+        strict_detection: matches.get_flag("strict-detection"),
+        python: matches.get_one::<String>("python").cloned(),
+        environment_file: matches.get_one::<PathBuf>("environment-file").cloned(),
+        merge_optional: matches.get_flag("merge-optional"),
+        stamp: matches.get_flag("stamp"),
+        post_hook: matches.get_one::<String>("post-hook").cloned(),
+        conda_map: matches
+            .get_many::<String>("conda-map")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        dev_group_name: matches.get_one::<String>("dev-group-name").cloned(),
+        vcs: matches
+            .get_one::<String>("vcs")
+            .cloned()
+            .unwrap_or_else(|| "none".to_string()),
+        force: matches.get_flag("force"),
+        max_parallel_adds: matches
+            .get_one::<u64>("max-parallel-adds")
+            .map_or(1, |&n| n as usize),
+        index_strategy: matches.get_one::<String>("index-strategy").cloned(),
+        prune_empty_groups: matches.get_flag("prune-empty-groups"),
+        build_backend: matches
+            .get_one::<String>("build-backend")
+            .cloned()
+            .unwrap_or_else(|| "hatchling".to_string()),
+        keep_poetry_section: matches.get_flag("keep-poetry-section"),
+        clean_pycache: matches.get_flag("clean-pycache"),
+        prefer_group: matches.get_flag("prefer-group"),
+        dependencies_only: matches.get_flag("dependencies-only"),
+        metadata_only: matches.get_flag("metadata-only"),
+        import_env_indexes: matches.get_flag("import-env-indexes"),
+        index_auth_env: matches.get_flag("index-auth-env"),
+        deterministic: matches.get_flag("deterministic"),
+        preserve_dep_comments: matches.get_flag("preserve-dep-comments"),
+        uv_retries: matches.get_one::<u32>("uv-retries").copied().unwrap_or(0),
+        skip_invalid: matches.get_flag("skip-invalid"),
+        lock_args: matches
+            .get_many::<String>("lock-arg")
+            .map(|values| values.cloned().collect())
+            .unwrap_or_default(),
+        #[cfg(feature = "self_update")]
+        self_update: matches.get_flag("self_update"),
+    }
+}
+
+/// What uv-migrator was asked to do
+#[derive(Debug, Clone)]
+pub enum CliCommand {
+    /// Migrate the project at `args.path` directly
+    Migrate(Args),
+    /// Compute a migration plan and write it to `output`, or stdout
+    Plan { args: Args, output: Option<PathBuf> },
+    /// Apply a previously written plan file
+    Apply { args: Args, plan: PathBuf },
+}
+
+/// Converts parsed clap matches into a [`CliCommand`]
+pub fn command_from_matches(matches: &ArgMatches) -> CliCommand {
+    match matches.subcommand() {
+        Some(("plan", sub_matches)) => CliCommand::Plan {
+            args: args_from_matches(sub_matches),
+            output: sub_matches.get_one::<PathBuf>("output").cloned(),
+        },
+        Some(("apply", sub_matches)) => CliCommand::Apply {
+            args: args_from_matches(sub_matches),
+            plan: sub_matches
+                .get_one::<PathBuf>("PLAN")
+                .cloned()
+                .unwrap_or_default(),
+        },
+        _ => CliCommand::Migrate(args_from_matches(matches)),
+    }
+}
+
+/// Options `apply` honors on the command line; every other option comes from the plan
+pub const APPLY_OPTIONS: &[&str] = &["dry-run", "verbose", "quiet", "output-format"];
+
+/// Rejects migration options given to `apply`, which would otherwise be ignored in
+/// favour of the options recorded in the plan
+pub fn check_apply_options(matches: &ArgMatches) -> Result<(), String> {
+    let ignored: Vec<String> = matches
+        .ids()
+        .map(|id| id.as_str())
+        .filter(|id| *id != "PLAN" && !APPLY_OPTIONS.contains(id))
+        .filter(|id| matches.value_source(id) == Some(ValueSource::CommandLine))
+        .map(|id| format!("--{}", id))
+        .collect();
+    if ignored.is_empty() {
+        return Ok(());
+    }
+    Err(format!(
+        "{} cannot be used with apply: the options recorded in the plan are used. \
+        Only --{} may be given.",
+        ignored.join(", "),
+        APPLY_OPTIONS.join(", --")
+    ))
+}
+
+/// Parses the process arguments into a [`CliCommand`]
+pub fn parse_command() -> CliCommand {
+    let mut cmd = build_command();
+    let matches = cmd.get_matches_mut();
+    if let Some(("apply", sub_matches)) = matches.subcommand() {
+        if let Err(message) = check_apply_options(sub_matches) {
+            cmd.error(clap::error::ErrorKind::ArgumentConflict, message)
+                .exit();
+        }
+    }
+    command_from_matches(&matches)
+}
+
+/// Parses the process arguments into [`Args`]
+pub fn parse_args() -> Args {
+    args_from_matches(&build_command().get_matches())
+}
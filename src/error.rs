@@ -0,0 +1,43 @@
+use crate::migrators::detect::NO_PROJECT_ERROR;
+use std::fmt;
+
+/// Failure categories that callers of the library and the binary can tell apart.
+///
+/// Detection and the already-migrated check return their category directly. Other
+/// steps report errors as messages, which convert into [`Error::Migration`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// No supported project files were found in the directory
+    NoProjectDetected,
+    /// The project already has a uv.lock and nothing left to migrate
+    AlreadyMigrated(String),
+    /// Any other failure, with its message
+    Migration(String),
+}
+
+impl Error {
+    /// The process exit code for this error: 2 when no project was detected, 1 otherwise
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::NoProjectDetected => 2,
+            Error::AlreadyMigrated(_) | Error::Migration(_) => 1,
+        }
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Migration(message)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoProjectDetected => f.write_str(NO_PROJECT_ERROR),
+            Error::AlreadyMigrated(message) | Error::Migration(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
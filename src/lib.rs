@@ -1,3 +1,33 @@
+pub mod cli;
+pub mod error;
 pub mod migrators;
+pub mod models;
 pub mod types;
 pub mod utils;
+
+pub use error::Error;
+pub use models::{MigrationConfig, MigrationReport};
+
+/// Migrates the project described by `config` to uv and reports what was done.
+///
+/// This is the library counterpart of running `uv-migrator` on the command line:
+/// failures are rolled back and returned as an [`Error`], so callers can match on
+/// [`Error::NoProjectDetected`] or [`Error::AlreadyMigrated`].
+///
+/// ```
+/// use uv_migrator::{migrate, MigrationConfig};
+///
+/// let project = tempfile::TempDir::new().unwrap();
+/// std::fs::write(project.path().join("requirements.txt"), "requests==2.31.0\n").unwrap();
+///
+/// // A dry run only plans the uv commands, so uv does not need to be installed
+/// let report = migrate(&MigrationConfig::new(project.path()).dry_run(true)).unwrap();
+///
+/// assert_eq!(report.project_type, "Requirements");
+/// assert_eq!(report.dependencies.main, 1);
+/// assert!(report.skipped.is_empty());
+/// assert!(report.dry_run);
+/// ```
+pub fn migrate(config: &MigrationConfig) -> Result<MigrationReport, Error> {
+    migrators::run_migration(config.project_dir(), config.args()).map(MigrationReport::from)
+}
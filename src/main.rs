@@ -1,171 +1,129 @@
-use crate::utils::check_uv_requirements;
-use clap::{Arg, Command};
 use log::{error, info};
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::exit;
-mod migrators;
-mod types;
-mod utils;
+use uv_migrator::cli::{Args, CliCommand, OutputFormat};
+use uv_migrator::error::Error;
+use uv_migrator::models::{MigrationPlan, MigrationSummary};
+#[cfg(feature = "self_update")]
+use uv_migrator::utils;
+use uv_migrator::utils::check_uv_requirements;
+use uv_migrator::{cli, migrators};
 
 fn main() {
-    if env::var_os("RUST_LOG").is_none() {
-        env::set_var("RUST_LOG", "info");
+    let command = cli::parse_command();
+    let args = match &command {
+        CliCommand::Migrate(args)
+        | CliCommand::Plan { args, .. }
+        | CliCommand::Apply { args, .. } => args,
+    };
+
+    if let Some(level) = args.log_level() {
+        env::set_var("RUST_LOG", level);
+    } else if env::var_os("RUST_LOG").is_none() {
+        // Keep the JSON summary readable by only logging problems in json mode
+        let level = match args.output_format {
+            OutputFormat::Json => "warn",
+            OutputFormat::Text => "info",
+        };
+        env::set_var("RUST_LOG", level);
     }
     env_logger::init();
 
-    if let Err(e) = run() {
+    let result = match &command {
+        CliCommand::Migrate(args) => run(args),
+        CliCommand::Plan { args, output } => plan(args, output.as_deref()),
+        CliCommand::Apply { args, plan } => apply(args, plan),
+    };
+
+    if let Err(e) = result {
         error!("{}", e);
-        exit(1);
+        exit(e.exit_code());
     }
 }
 
-fn run() -> Result<(), String> {
-    let mut cmd = Command::new("uv-migrator")
-        .version(env!("CARGO_PKG_VERSION"))
-        .about("A tool for migrating Python projects to use the uv package manager")
-        .long_about(
-            "UV Migrator helps you convert Python projects from various dependency management systems \
-            (like Poetry or pip) to use the UV package manager. It preserves your dependencies, \
-            development configurations, and project structure while setting up a new UV-based environment."
-        )
-        .arg(
-            Arg::new("PATH")
-                .help("The path to the project directory to migrate")
-                .long_help(
-                    "Specifies the directory containing the Python project to migrate. \
-                    This should be the root directory of your project where pyproject.toml \
-                    or requirements.txt is located."
-                )
-                .value_parser(clap::value_parser!(String))
-        )
-        .arg(
-            Arg::new("merge-groups")
-                .long("merge-groups")
-                .help("Merge all dependency groups into the dev group")
-                .long_help(
-                    "When this flag is set, all dependency groups (including custom groups) \
-                    will be merged into the dev group. This is useful when you want to \
-                    simplify your dependency management by having only main and dev dependencies."
-                )
-                .action(clap::ArgAction::SetTrue)
-        )
-        .arg(
-            Arg::new("import-global-pip-conf")
-                .long("import-global-pip-conf")
-                .help("Import extra index URLs from ~/.pip/pip.conf")
-                .long_help(
-                    "Reads and imports any extra package index URLs defined in your global pip \
-                    configuration file (~/.pip/pip.conf). This is useful when your project requires \
-                    packages from private or alternative Python package indexes."
-                )
-                .action(clap::ArgAction::SetTrue)
-        )
-        .arg(
-            Arg::new("import-index")
-                .long("import-index")
-                .help("Additional index URL to import")
-                .long_help(
-                    "Specifies additional Python package index URLs to use. You can provide this \
-                    option multiple times to add several index URLs. These URLs will be added to \
-                    your project's pyproject.toml in the [tool.uv] section."
-                )
-                .action(clap::ArgAction::Append)
-                .value_parser(clap::value_parser!(String))
-        );
+fn project_dir(args: &Args) -> Result<PathBuf, String> {
+    let input_path = match &args.path {
+        Some(path) => path.clone(),
+        None => {
+            return Err("No path provided. Use --help for usage information.".to_string());
+        }
+    };
 
+    Ok(if input_path.is_dir() {
+        input_path
+    } else {
+        input_path.parent().unwrap_or(Path::new(".")).to_path_buf()
+    })
+}
+
+fn run(args: &Args) -> Result<(), Error> {
     #[cfg(feature = "self_update")]
     {
-        cmd = cmd.arg(
-            Arg::new("self_update")
-                .long("self-update")
-                .help("Update uv-migrator to the latest version")
-                .long_help(
-                    "Checks for and downloads the latest version of uv-migrator from GitHub releases. \
-                    The tool will automatically update itself if a newer version is available."
-                )
-                .action(clap::ArgAction::SetTrue)
-        );
+        if args.self_update {
+            return match utils::update() {
+                Ok(_) => Ok(()),
+                Err(e) => Err(format!("Failed to update: {}", e).into()),
+            };
+        }
     }
 
-    let after_help = {
-        let mut help = String::from(
-            "EXAMPLES:\n\
-            # Migrate a project in the current directory\n\
-            uv-migrator .\n\
-            \n\
-            # Merge all dependency groups into dev dependencies\n\
-            uv-migrator . --merge-groups\n\
-            \n\
-            # Migrate a project with a private package index\n\
-            uv-migrator . --import-index https://private.pypi.org/simple/\n\
-            \n\
-            # Migrate using global pip configuration\n\
-            uv-migrator . --import-global-pip-conf\n",
-        );
-
-        #[cfg(feature = "self_update")]
-        {
-            help.push_str(
-                "\n\
-                # Update uv-migrator to the latest version\n\
-                uv-migrator --self-update\n",
-            );
-        }
+    let project_dir = project_dir(args)?;
 
-        help.push_str(
-            "\n\
-            For more information and documentation, visit:\n\
-            https://github.com/stvnksslr/uv-migrator",
-        );
+    if !args.dry_run {
+        check_uv_requirements()?;
+    }
 
-        help
-    };
+    let summary = migrators::run_migration(&project_dir, args).map_err(migration_failed)?;
 
-    cmd = cmd.after_help(after_help);
+    Ok(report(args, &summary)?)
+}
 
-    let matches = cmd.get_matches();
+/// Prefixes failures of the migration steps, keeping the error category
+fn migration_failed(error: Error) -> Error {
+    match error {
+        Error::Migration(message) => Error::Migration(format!("Migration failed: {}", message)),
+        other => other,
+    }
+}
 
-    #[cfg(feature = "self_update")]
-    {
-        if matches.get_flag("self_update") {
-            return match utils::update() {
-                Ok(_) => Ok(()),
-                Err(e) => Err(format!("Failed to update: {}", e)),
-            };
+fn plan(args: &Args, output: Option<&Path>) -> Result<(), Error> {
+    let project_dir = project_dir(args)?;
+    let plan = migrators::plan_migration(&project_dir, args)?;
+
+    match output {
+        Some(path) => {
+            plan.save(path)?;
+            info!("Wrote migration plan to {}", path.display());
         }
+        None => println!("{}", plan.to_json()?),
     }
+    Ok(())
+}
 
-    if !matches.contains_id("PATH") {
-        return Err("No path provided. Use --help for usage information.".to_string());
+fn apply(args: &Args, plan_path: &Path) -> Result<(), Error> {
+    let mut plan = MigrationPlan::load(plan_path)?;
+    plan.apply_cli_overrides(args);
+
+    if !plan.options.dry_run {
+        check_uv_requirements()?;
     }
 
-    check_uv_requirements()?;
+    let summary = migrators::apply_plan(&plan).map_err(migration_failed)?;
 
-    let input_path = Path::new(matches.get_one::<String>("PATH").unwrap());
-    let project_dir = if input_path.is_dir() {
-        input_path.to_path_buf()
-    } else {
-        input_path.parent().unwrap_or(Path::new(".")).to_path_buf()
-    };
+    Ok(report(args, &summary)?)
+}
 
-    let import_global_pip_conf = matches.get_flag("import-global-pip-conf");
-    let merge_groups = matches.get_flag("merge-groups");
-    let additional_index_urls: Vec<String> = matches
-        .get_many::<String>("import-index")
-        .map(|values| values.cloned().collect())
-        .unwrap_or_default();
-
-    match migrators::run_migration(
-        &project_dir,
-        import_global_pip_conf,
-        &additional_index_urls,
-        merge_groups,
-    ) {
-        Ok(_) => {
-            info!("Migration completed successfully");
-            Ok(())
+fn report(args: &Args, summary: &MigrationSummary) -> Result<(), String> {
+    match args.output_format {
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(summary)
+                .map_err(|e| format!("Failed to serialize migration summary: {}", e))?;
+            println!("{}", json);
         }
-        Err(e) => Err(format!("Migration failed: {}", e)),
+        OutputFormat::Text if summary.dry_run => info!("Dry run completed; no changes were made"),
+        OutputFormat::Text => info!("Migration completed successfully"),
     }
+
+    Ok(())
 }
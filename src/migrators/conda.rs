@@ -0,0 +1,409 @@
+use crate::migrators::requirements::{parse_include, RequirementsMigrationSource};
+use crate::migrators::{Dependency, MigrationSource};
+use crate::models::{SkippedItem, SkippedKind};
+use log::{debug, info, warn};
+use serde_yaml_ng::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File names Conda uses for environment definitions
+const ENVIRONMENT_FILES: &[&str] = &["environment.yml", "environment.yaml"];
+
+/// Conda packages that ship system libraries or toolchains and have no PyPI equivalent
+const SYSTEM_PACKAGES: &[&str] = &[
+    "_libgcc_mutex",
+    "_openmp_mutex",
+    "blas",
+    "bzip2",
+    "ca-certificates",
+    "cudatoolkit",
+    "cudnn",
+    "intel-openmp",
+    "ld_impl_linux-64",
+    "libblas",
+    "libcblas",
+    "libffi",
+    "libgcc-ng",
+    "libgfortran-ng",
+    "libgomp",
+    "liblapack",
+    "libstdcxx-ng",
+    "libuuid",
+    "libzlib",
+    "mkl",
+    "mkl-service",
+    "ncurses",
+    "openssl",
+    "readline",
+    "sqlite",
+    "tk",
+    "tzdata",
+    "xz",
+    "zlib",
+];
+
+/// Conda package names that are published under a different name on PyPI
+const PACKAGE_MAPPINGS: &[(&str, &str)] = &[
+    ("faiss", "faiss-cpu"),
+    ("matplotlib-base", "matplotlib"),
+    ("msgpack-python", "msgpack"),
+    ("opencv", "opencv-python"),
+    ("py-opencv", "opencv-python"),
+    ("pyqt", "PyQt5"),
+    ("pytables", "tables"),
+    ("python-graphviz", "graphviz"),
+    ("pytorch", "torch"),
+    ("pytorch-cpu", "torch"),
+    ("pytorch-gpu", "torch"),
+    ("ruamel_yaml", "ruamel.yaml"),
+];
+
+#[derive(Default)]
+pub struct CondaMigrationSource {
+    /// Environment file to read instead of discovering `environment.yml`
+    environment_file: Option<PathBuf>,
+    /// Conda to PyPI name mappings that override or extend `PACKAGE_MAPPINGS`
+    name_mappings: HashMap<String, String>,
+    /// Whether system packages such as `openssl` are migrated instead of skipped
+    include_system: bool,
+}
+
+impl MigrationSource for CondaMigrationSource {
+    fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String> {
+        let (dependencies, _) = self.parse_environment(project_dir)?;
+        debug!("Extracted {} Conda dependencies", dependencies.len());
+        Ok(dependencies)
+    }
+
+    fn skipped_items(&self, project_dir: &Path) -> Result<Vec<SkippedItem>, String> {
+        let (_, skipped) = self.parse_environment(project_dir)?;
+        Ok(skipped)
+    }
+
+    fn source_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let environment_path = self.environment_path(project_dir)?;
+        let mut files = vec![project_dir.join("pyproject.toml"), environment_path.clone()];
+        let environment = self.read_environment(project_dir)?;
+        let base_dir = environment_path.parent().unwrap_or(project_dir);
+        for entry in Self::dependency_entries(&environment) {
+            let pip_entries = entry.get("pip").and_then(|p| p.as_sequence());
+            for requirement in pip_entries.into_iter().flatten().filter_map(|p| p.as_str()) {
+                if let Some(file) = parse_include(requirement.trim()) {
+                    RequirementsMigrationSource.referenced_files(&base_dir.join(file), &mut files);
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl CondaMigrationSource {
+    /// Creates a source whose Conda to PyPI name mappings take precedence over the
+    /// built-in table. Keys are Conda package names and are matched case-insensitively.
+    pub fn new(name_mappings: HashMap<String, String>) -> Self {
+        Self {
+            environment_file: None,
+            include_system: false,
+            name_mappings: name_mappings
+                .into_iter()
+                .map(|(conda, pypi)| (conda.to_lowercase(), pypi))
+                .collect(),
+        }
+    }
+
+    /// Reads the given environment file, relative to the project directory, instead of
+    /// discovering one
+    pub fn environment_file(mut self, environment_file: impl Into<PathBuf>) -> Self {
+        self.environment_file = Some(environment_file.into());
+        self
+    }
+
+    /// Migrates Conda system packages such as `openssl` instead of skipping them
+    pub fn include_system(mut self, include_system: bool) -> Self {
+        self.include_system = include_system;
+        self
+    }
+
+    pub fn detect_project_type(project_dir: &Path) -> bool {
+        Self::find_environment_file(project_dir).is_some()
+    }
+
+    fn find_environment_file(project_dir: &Path) -> Option<PathBuf> {
+        ENVIRONMENT_FILES
+            .iter()
+            .map(|name| project_dir.join(name))
+            .find(|path| path.exists())
+    }
+
+    /// Extracts the Python version requested by the environment, e.g. `python=3.11`.
+    ///
+    /// Ranges such as `python>=3.9,<3.12` are reduced to their lower bound, `3.9`, as
+    /// `uv init --python` takes a single version. Returns `None` when the spec has no
+    /// lower bound.
+    pub fn extract_python_version(&self, project_dir: &Path) -> Result<Option<String>, String> {
+        let environment = self.read_environment(project_dir)?;
+        let version = Self::dependency_entries(&environment)
+            .into_iter()
+            .filter_map(|entry| entry.as_str())
+            .map(Self::parse_conda_spec)
+            .find(|(name, _)| name == "python")
+            .and_then(|(_, version)| version)
+            .and_then(|version| {
+                let requested = python_request(&version);
+                if requested.is_none() {
+                    warn!(
+                        "Not pinning Python: '{}' has no lower bound to pass to uv init",
+                        version
+                    );
+                }
+                requested
+            });
+
+        Ok(version)
+    }
+
+    /// The channels listed in the environment file, in order
+    pub fn channels(&self, project_dir: &Path) -> Result<Vec<String>, String> {
+        let environment = self.read_environment(project_dir)?;
+        Ok(environment
+            .get("channels")
+            .and_then(|c| c.as_sequence())
+            .map(|channels| {
+                channels
+                    .iter()
+                    .filter_map(|c| c.as_str())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    fn environment_path(&self, project_dir: &Path) -> Result<PathBuf, String> {
+        match &self.environment_file {
+            Some(environment_file) => Ok(project_dir.join(environment_file)),
+            None => Self::find_environment_file(project_dir)
+                .ok_or_else(|| "No Conda environment file found.".to_string()),
+        }
+    }
+
+    fn read_environment(&self, project_dir: &Path) -> Result<Value, String> {
+        let path = self.environment_path(project_dir)?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Error reading file '{}': {}", path.display(), e))?;
+        serde_yaml_ng::from_str(&content)
+            .map_err(|e| format!("Error parsing '{}': {}", path.display(), e))
+    }
+
+    fn dependency_entries(environment: &Value) -> Vec<&Value> {
+        environment
+            .get("dependencies")
+            .and_then(|d| d.as_sequence())
+            .map(|entries| entries.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Parses the environment file into dependencies and the entries left out of the migration.
+    ///
+    /// System packages are skipped unless [`CondaMigrationSource::include_system`] is set.
+    pub fn parse_environment(
+        &self,
+        project_dir: &Path,
+    ) -> Result<(Vec<Dependency>, Vec<SkippedItem>), String> {
+        let environment = self.read_environment(project_dir)?;
+        let mut dependencies = Vec::new();
+        let mut skipped = Vec::new();
+
+        for entry in Self::dependency_entries(&environment) {
+            if let Some(spec) = entry.as_str() {
+                let (name, version) = Self::parse_conda_spec(spec);
+
+                if name == "python" {
+                    continue;
+                }
+                if name == "pip" {
+                    skipped.push(SkippedItem::new(
+                        SkippedKind::Dependency,
+                        name,
+                        "pip is not needed in a uv-managed environment",
+                    ));
+                    continue;
+                }
+                if !self.include_system && SYSTEM_PACKAGES.contains(&name.as_str()) {
+                    info!("Skipping Conda system package: {}", name);
+                    skipped.push(SkippedItem::new(
+                        SkippedKind::Package,
+                        name,
+                        "Conda system package with no PyPI equivalent",
+                    ));
+                    continue;
+                }
+
+                let pypi_name = self.map_package_name(&name);
+                if pypi_name != name {
+                    debug!("Mapped Conda package '{}' to PyPI '{}'", name, pypi_name);
+                }
+                match Dependency::new(pypi_name).version(version).build() {
+                    Ok(dep) => dependencies.push(dep),
+                    Err(e) => skipped.push(SkippedItem::new(SkippedKind::Line, spec, e)),
+                }
+            } else if let Some(pip_entries) = entry.get("pip").and_then(|p| p.as_sequence()) {
+                let requirements = RequirementsMigrationSource;
+                for requirement in pip_entries.iter().filter_map(|p| p.as_str()) {
+                    let requirement = requirement.trim();
+                    if let Some(file) = parse_include(requirement) {
+                        // Included files are relative to the environment file
+                        let environment_path = self.environment_path(project_dir)?;
+                        let base_dir = environment_path.parent().unwrap_or(project_dir);
+                        let file_path = base_dir.join(file);
+                        info!("Including pip requirements file: {}", file_path.display());
+                        dependencies.extend(requirements.parse_requirements_file(&file_path)?);
+                        continue;
+                    }
+                    if is_editable_self_install(requirement) {
+                        warn!(
+                            "Skipping editable install of the project itself: {}",
+                            requirement
+                        );
+                        skipped.push(SkippedItem::new(
+                            SkippedKind::Line,
+                            requirement,
+                            "the project itself is installed by uv",
+                        ));
+                        continue;
+                    }
+                    match requirements.parse_requirement(requirement) {
+                        Ok(Some(dep)) => dependencies.push(dep),
+                        Ok(None) => {}
+                        Err(e) => skipped.push(SkippedItem::new(SkippedKind::Line, requirement, e)),
+                    }
+                }
+            }
+        }
+
+        Ok((dependencies, skipped))
+    }
+
+    /// Splits a Conda match spec such as `numpy=1.21` or `conda-forge::scipy>=1.7`
+    /// into the package name and a PEP 440 version specifier.
+    fn parse_conda_spec(spec: &str) -> (String, Option<String>) {
+        let spec = spec.trim();
+        let spec = spec.rsplit("::").next().unwrap_or(spec);
+
+        let split_at = spec
+            .find(|c: char| "=<>!~".contains(c))
+            .unwrap_or(spec.len());
+        let name = spec[..split_at].trim().to_lowercase();
+        // Hand-edited files may put spaces around operators, e.g. `numpy = 1.21.5`
+        let constraint: String = spec[split_at..].split_whitespace().collect();
+
+        if constraint.is_empty() {
+            return (name, None);
+        }
+
+        let version = match constraint.strip_prefix('=') {
+            // A single `=` is Conda's fuzzy match; anything after a second `=` is a build string
+            Some(rest) if !rest.starts_with('=') => {
+                let version = rest.split('=').next().unwrap_or(rest).trim();
+                if version.ends_with(".*") || version.split('.').count() >= 3 {
+                    format!("=={}", version)
+                } else {
+                    format!("=={}.*", version)
+                }
+            }
+            _ => constraint,
+        };
+
+        (name, Some(version))
+    }
+
+    fn map_package_name(&self, name: &str) -> String {
+        if let Some(pypi) = self.name_mappings.get(name) {
+            return pypi.clone();
+        }
+        PACKAGE_MAPPINGS
+            .iter()
+            .find(|(conda, _)| *conda == name)
+            .map(|(_, pypi)| pypi.to_string())
+            .unwrap_or_else(|| name.to_string())
+    }
+}
+
+/// Whether a pip entry is an editable install of the project itself, e.g. `-e .`
+fn is_editable_self_install(requirement: &str) -> bool {
+    let target = requirement
+        .strip_prefix("--editable")
+        .or_else(|| requirement.strip_prefix("-e"))
+        .map(|rest| rest.trim_start_matches('=').trim());
+    target.is_some_and(|t| t == "." || t == "./" || t.starts_with(".["))
+}
+
+/// Reduces a PEP 440 Python specifier to a version for `uv init --python`: an exact
+/// pin such as `==3.11.4` is kept, while `==3.11.*`, `>=3.9,<3.12` and `~=3.10.2`
+/// become their major and minor version.
+fn python_request(specifier: &str) -> Option<String> {
+    let alternative = specifier.split('|').next().unwrap_or(specifier);
+    alternative.split(',').map(str::trim).find_map(|clause| {
+        if let Some(version) = clause.strip_prefix("==") {
+            if !version.ends_with(".*") {
+                return Some(version.to_string());
+            }
+        }
+        let version = ["==", ">=", "~="]
+            .iter()
+            .find_map(|op| clause.strip_prefix(op))?
+            .trim_end_matches(".*");
+        let parts: Vec<&str> = version.split('.').collect();
+        match parts.as_slice() {
+            [major] if !major.is_empty() => Some(format!("{}.0", major)),
+            [major, minor, ..] => Some(format!("{}.{}", major, minor)),
+            _ => None,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_conda_spec() {
+        assert_eq!(
+            CondaMigrationSource::parse_conda_spec("numpy=1.21"),
+            ("numpy".to_string(), Some("==1.21.*".to_string()))
+        );
+        assert_eq!(
+            CondaMigrationSource::parse_conda_spec("numpy=1.21.5=py39h5d0ccc0_0"),
+            ("numpy".to_string(), Some("==1.21.5".to_string()))
+        );
+        assert_eq!(
+            CondaMigrationSource::parse_conda_spec("conda-forge::scipy>=1.7"),
+            ("scipy".to_string(), Some(">=1.7".to_string()))
+        );
+        assert_eq!(
+            CondaMigrationSource::parse_conda_spec("pandas"),
+            ("pandas".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn test_parse_conda_spec_with_spaces() {
+        for spec in [
+            "numpy = 1.21.5",
+            "numpy ==1.21.5",
+            "numpy == 1.21.5",
+            " numpy=1.21.5 ",
+        ] {
+            assert_eq!(
+                CondaMigrationSource::parse_conda_spec(spec),
+                ("numpy".to_string(), Some("==1.21.5".to_string())),
+                "{}",
+                spec
+            );
+        }
+        assert_eq!(
+            CondaMigrationSource::parse_conda_spec("scipy >= 1.7, < 2"),
+            ("scipy".to_string(), Some(">=1.7,<2".to_string()))
+        );
+    }
+}
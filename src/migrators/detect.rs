@@ -1,65 +1,236 @@
+use crate::error::Error;
 use log::info;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use crate::migrators::conda::CondaMigrationSource;
+use crate::migrators::pdm::PdmMigrationSource;
+use crate::migrators::pep621::Pep621MigrationSource;
 use crate::migrators::pipenv::PipenvMigrationSource;
 use crate::migrators::poetry::PoetryMigrationSource;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ProjectType {
     Poetry(PoetryProjectType),
     Pipenv,
     Requirements,
     SetupPy,
+    Conda,
+    Pdm,
+    /// A standard PEP 621 pyproject.toml without tool-specific dependency tables
+    Pep621,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PoetryProjectType {
     Package,
     Application,
+    /// A library that also ships console scripts
+    Mixed,
 }
 
+/// The error reported when no supported project files are found
+pub const NO_PROJECT_ERROR: &str = "Unable to detect project type. Ensure you have either a pyproject.toml with a [tool.poetry] section or a [project] section, a Pipfile, a Conda environment.yml, a setup.py or setup.cfg file, or requirements.txt file(s).";
+
+/// The start of the error reported when the project was already migrated to uv
+pub const ALREADY_MIGRATED_ERROR: &str = "Project already appears to be migrated to uv";
+
+impl ProjectType {
+    /// The name used for this project type by `--from`
+    pub fn source_name(&self) -> &'static str {
+        match self {
+            ProjectType::Poetry(_) => "poetry",
+            ProjectType::Pipenv => "pipenv",
+            ProjectType::Requirements => "requirements",
+            ProjectType::SetupPy => "setup-py",
+            ProjectType::Conda => "conda",
+            ProjectType::Pdm => "pdm",
+            ProjectType::Pep621 => "pep621",
+        }
+    }
+
+    /// A human readable name for this project type, e.g. "Poetry" or "setup.py"
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ProjectType::Poetry(_) => "Poetry",
+            ProjectType::SetupPy => "setup.py",
+            ProjectType::Pipenv => "Pipfile",
+            ProjectType::Requirements => "requirements",
+            ProjectType::Conda => "Conda",
+            ProjectType::Pdm => "PDM",
+            ProjectType::Pep621 => "PEP 621",
+        }
+    }
+}
+
+/// Names accepted by `--from`, in detection priority order
+pub const SOURCE_NAMES: &[&str] = &[
+    "pdm",
+    "poetry",
+    "pep621",
+    "pipenv",
+    "conda",
+    "setup-py",
+    "requirements",
+];
+
 pub fn detect_project_type(project_dir: &Path) -> Result<ProjectType, String> {
+    let (project_type, description) = detect_all(project_dir)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NO_PROJECT_ERROR.to_string())?;
+    info!("Detected {}", description);
+    Ok(project_type)
+}
+
+/// Returns every project type found in the directory, highest priority first
+pub fn detect_project_types(project_dir: &Path) -> Result<Vec<ProjectType>, String> {
+    Ok(detect_all(project_dir)?
+        .into_iter()
+        .map(|(project_type, _)| project_type)
+        .collect())
+}
+
+/// Chooses the project type to migrate.
+///
+/// `from` picks one of the detected types by its `--from` name. Without it the
+/// highest priority type is used, unless `strict` is set and several types match.
+pub fn select_project_type(
+    project_dir: &Path,
+    from: Option<&str>,
+    strict: bool,
+) -> Result<ProjectType, Error> {
+    let detected = detect_all(project_dir)?;
+
+    if let Some(from) = from {
+        let (project_type, description) = detected
+            .into_iter()
+            .find(|(project_type, _)| project_type.source_name() == from)
+            .ok_or_else(|| {
+                format!(
+                    "--from {} was given but no {} project was detected",
+                    from, from
+                )
+            })?;
+        info!("Detected {} (selected with --from)", description);
+        return Ok(project_type);
+    }
+
+    if strict && detected.len() > 1 {
+        let names: Vec<&str> = detected
+            .iter()
+            .map(|(project_type, _)| project_type.source_name())
+            .collect();
+        return Err(Error::Migration(format!(
+            "Ambiguous project type: found {} projects. Use --from <{}> to choose one.",
+            names.join(", "),
+            names.join("|")
+        )));
+    }
+
+    let (project_type, description) = detected
+        .into_iter()
+        .next()
+        .ok_or(Error::NoProjectDetected)?;
+    info!("Detected {}", description);
+    Ok(project_type)
+}
+
+/// Collects each detectable project type with a description for logging
+fn detect_all(project_dir: &Path) -> Result<Vec<(ProjectType, &'static str)>, String> {
+    let mut detected = Vec::new();
+
     let pyproject_path = project_dir.join("pyproject.toml");
     if pyproject_path.exists() {
-        // First, check the project section (Poetry 2.0 style)
-        if let Ok(content) = std::fs::read_to_string(&pyproject_path) {
-            if let Ok(pyproject) = toml::from_str::<toml::Value>(&content) {
-                // Check for Poetry 2.0 project section
-                if let Some(project) = pyproject.get("project") {
-                    if project.get("dependencies").is_some() {
-                        info!("Detected Poetry 2.0 project");
-                        return Ok(ProjectType::Poetry(PoetryProjectType::Package));
-                    }
-                }
-            }
-        }
-
-        // Then check for traditional Poetry section
-        if has_poetry_section(&pyproject_path)? {
-            info!("Detected Poetry project");
+        // PDM projects also use a PEP 621 project section, so check for them first
+        if PdmMigrationSource::detect_project_type(project_dir) {
+            detected.push((ProjectType::Pdm, "PDM project"));
+        } else if has_poetry_2_project_section(&pyproject_path) {
+            let poetry_type = PoetryMigrationSource::detect_project_type(project_dir)?;
+            detected.push((ProjectType::Poetry(poetry_type), "Poetry 2.0 project"));
+        } else if has_poetry_section(&pyproject_path)? {
+            // Then check for traditional Poetry section
             let poetry_type = PoetryMigrationSource::detect_project_type(project_dir)?;
-            return Ok(ProjectType::Poetry(poetry_type));
+            detected.push((ProjectType::Poetry(poetry_type), "Poetry project"));
+        } else if Pep621MigrationSource::detect_project_type(project_dir) {
+            // Any other backend with standard [project] dependencies
+            let description = if Pep621MigrationSource::is_hatch_project(project_dir) {
+                "Hatch project"
+            } else {
+                "PEP 621 project"
+            };
+            detected.push((ProjectType::Pep621, description));
         }
     }
 
     if PipenvMigrationSource::detect_project_type(project_dir) {
-        info!("Detected Pipenv project");
-        return Ok(ProjectType::Pipenv);
+        detected.push((ProjectType::Pipenv, "Pipenv project"));
+    }
+
+    if CondaMigrationSource::detect_project_type(project_dir) {
+        detected.push((ProjectType::Conda, "Conda project"));
     }
 
     let setup_py_path = project_dir.join("setup.py");
-    if setup_py_path.exists() {
-        info!("Detected setuptools project");
-        return Ok(ProjectType::SetupPy);
+    let is_setuptools = setup_py_path.exists() || project_dir.join("setup.cfg").exists();
+    if is_setuptools {
+        detected.push((ProjectType::SetupPy, "setuptools project"));
     }
 
+    // setuptools projects read their requirements files themselves
     let requirements_files = find_requirements_files(project_dir);
-    if !requirements_files.is_empty() {
-        info!("Detected project with requirements files");
-        return Ok(ProjectType::Requirements);
+    if !requirements_files.is_empty() && !is_setuptools {
+        detected.push((ProjectType::Requirements, "project with requirements files"));
     }
 
-    Err("Unable to detect project type. Ensure you have either a pyproject.toml with a [tool.poetry] section or a [project] section, a Pipfile, a setup.py file, or requirements.txt file(s).".to_string())
+    Ok(detected)
+}
+
+/// Whether the directory holds the output of a previous migration.
+///
+/// That is a `uv.lock` next to a pyproject.toml with a `[project]` or `[tool.uv]`
+/// table and no Poetry, PDM or Pipenv configuration left to migrate.
+pub fn is_uv_project(project_dir: &Path) -> bool {
+    if !project_dir.join("uv.lock").exists() || project_dir.join("Pipfile").exists() {
+        return false;
+    }
+    std::fs::read_to_string(project_dir.join("pyproject.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        .map(|pyproject| {
+            let tool = pyproject.get("tool");
+            let has_tool = |name: &str| tool.and_then(|t| t.get(name)).is_some();
+            (pyproject.get("project").is_some() || has_tool("uv"))
+                && !has_tool("poetry")
+                && !has_tool("pdm")
+        })
+        .unwrap_or(false)
+}
+
+/// Whether pyproject.toml has a Poetry 2.0 style `[project]` section with dependencies.
+///
+/// The project must also use Poetry, through a `[tool.poetry]` table or the
+/// poetry-core build backend, to tell it apart from other PEP 621 projects.
+fn has_poetry_2_project_section(pyproject_path: &Path) -> bool {
+    std::fs::read_to_string(pyproject_path)
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        .map(|pyproject| {
+            let has_dependencies = pyproject
+                .get("project")
+                .is_some_and(|p| p.get("dependencies").is_some());
+            let uses_poetry = pyproject
+                .get("tool")
+                .and_then(|t| t.get("poetry"))
+                .is_some()
+                || pyproject
+                    .get("build-system")
+                    .and_then(|b| b.get("build-backend"))
+                    .and_then(|b| b.as_str())
+                    .is_some_and(|backend| backend.starts_with("poetry"));
+            has_dependencies && uses_poetry
+        })
+        .unwrap_or(false)
 }
 
 /// Parses the contents of a TOML file to check for Poetry configuration.
@@ -87,26 +258,19 @@ fn has_poetry_section(pyproject_path: &Path) -> Result<bool, String> {
         )
     })?;
 
-    // Check for traditional Poetry section
-    let has_tool_poetry = pyproject
+    // A bare [project] section is a PEP 621 project, not necessarily Poetry
+    Ok(pyproject
         .get("tool")
         .and_then(|t| t.get("poetry"))
-        .is_some();
-
-    // Check for Poetry 2.0 project section
-    let has_project_section = pyproject
-        .get("project")
-        .and_then(|p| p.get("dependencies"))
-        .is_some();
-
-    Ok(has_tool_poetry || has_project_section)
+        .is_some())
 }
 
 /// Finds all requirements files in a directory.
 ///
 /// Searches the specified directory for files that start with "requirements"
 /// (e.g., requirements.txt, requirements-dev.txt). This includes any file
-/// with a "requirements" prefix, regardless of its suffix.
+/// with a "requirements" prefix, regardless of its suffix, as well as the
+/// `.txt` files of a `requirements/` directory.
 ///
 /// # Arguments
 ///
@@ -116,7 +280,7 @@ fn has_poetry_section(pyproject_path: &Path) -> Result<bool, String> {
 ///
 /// A Vec<PathBuf> containing paths to all found requirements files.
 fn find_requirements_files(dir: &Path) -> Vec<std::path::PathBuf> {
-    std::fs::read_dir(dir)
+    let mut files: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
         .unwrap()
         .filter_map(|entry| {
             let entry = entry.unwrap();
@@ -134,5 +298,16 @@ fn find_requirements_files(dir: &Path) -> Vec<std::path::PathBuf> {
                 None
             }
         })
-        .collect()
+        .collect();
+
+    // A requirements/ directory holding base.txt, dev.txt, ...
+    if let Ok(entries) = std::fs::read_dir(dir.join("requirements")) {
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt")),
+        );
+    }
+    files
 }
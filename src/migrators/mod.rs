@@ -1,32 +1,57 @@
+use crate::cli::Args;
+use crate::error::Error;
 use crate::migrators::detect::{PoetryProjectType, ProjectType};
-use crate::utils::build_system::update_build_system;
+use crate::models::{
+    hash_source_files, DependencyCounts, MigrationPlan, MigrationSummary, PythonSelection,
+    PythonSource, SkippedItem, SkippedKind, PLAN_FORMAT_VERSION,
+};
+use crate::utils::build_system::{set_build_system, update_build_system, update_hatch_includes};
 use crate::utils::{
     author::extract_authors_from_poetry,
     author::extract_authors_from_setup_py,
-    parse_pip_conf, pyproject,
+    markers::validate_marker,
+    package_source::PackageSourceSnapshot,
+    parse_pip_conf, pyproject, report,
     toml::{read_toml, update_section, write_toml},
-    update_pyproject_toml, update_url, FileTrackerGuard,
+    update_pyproject_toml, update_url, FileTrackerGuard, UvCommandBuilder,
 };
-use log::info;
+use conda::CondaMigrationSource;
+use log::{info, warn};
+use pdm::PdmMigrationSource;
+use pep621::Pep621MigrationSource;
 use poetry::PoetryMigrationSource;
 use setup_py::SetupPyMigrationSource;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use toml_edit::{Array, Formatted, Item, Value};
 
-mod dependency;
+pub mod conda;
 pub mod detect;
+pub mod pdm;
+pub mod pep621;
 pub mod pipenv;
 pub mod poetry;
 pub mod requirements;
+pub mod setup_cfg;
 pub mod setup_py;
+pub mod tox;
 
-pub use dependency::{Dependency, DependencyType};
+pub use crate::models::{Dependency, DependencyType};
 pub use detect::detect_project_type;
 
 pub trait MigrationSource {
     fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String>;
+
+    /// Lists the packages, markers and lines this source leaves out of the migration
+    fn skipped_items(&self, _project_dir: &Path) -> Result<Vec<SkippedItem>, String> {
+        Ok(Vec::new())
+    }
+
+    /// Lists the files this source reads, including ones that may not exist yet
+    fn source_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        Ok(vec![project_dir.join("pyproject.toml")])
+    }
 }
 
 pub trait MigrationTool {
@@ -44,20 +69,158 @@ pub trait MigrationTool {
     ) -> Result<(), String>;
 }
 
-pub struct UvTool;
+/// Migration tool that drives the `uv` command line
+pub struct UvTool {
+    args: Args,
+}
 
-impl MigrationTool for UvTool {
-    fn prepare_project(
+impl UvTool {
+    pub fn new(args: &Args) -> Self {
+        UvTool { args: args.clone() }
+    }
+
+    fn command(&self, project_dir: &Path) -> Result<UvCommandBuilder, String> {
+        // A dry run only renders command lines, so it does not require uv to be installed
+        let builder = match UvCommandBuilder::new() {
+            Ok(builder) => builder,
+            Err(_) if self.args.dry_run => UvCommandBuilder::with_path("uv"),
+            Err(e) => return Err(e),
+        };
+        Ok(builder
+            .working_dir(project_dir)
+            .verbose(self.args.verbose_subprocess)
+            .retries(self.args.uv_retries))
+    }
+
+    /// Runs the command, or only logs it under `--dry-run`
+    fn run(&self, command: &UvCommandBuilder, description: &str) -> Result<(), String> {
+        if self.args.dry_run {
+            info!("[dry-run] Would run: {}", command.command_line());
+            return Ok(());
+        }
+        command.execute_success(description)?;
+        Ok(())
+    }
+}
+
+impl UvTool {
+    /// Computes the `uv init` arguments for the project
+    pub fn init_args(
+        &self,
+        project_dir: &Path,
+        project_type: &ProjectType,
+    ) -> Result<Vec<String>, String> {
+        let python = self.python_selection(project_dir, project_type)?;
+        Ok(build_init_args(
+            is_package(project_type),
+            python.version.as_deref(),
+            &self.args,
+        ))
+    }
+
+    /// Chooses the Python version for `uv init`: `--python` wins over the version
+    /// the project declares, and `--no-pin-python` leaves it unpinned
+    pub fn python_selection(
         &self,
         project_dir: &Path,
-        file_tracker: &mut FileTrackerGuard,
         project_type: &ProjectType,
+    ) -> Result<PythonSelection, String> {
+        let selection = match &self.args.python {
+            Some(version) => PythonSelection {
+                version: Some(version.clone()),
+                source: PythonSource::Override,
+            },
+            None => {
+                // Extract Python version for Poetry, PDM, PEP 621 and Conda projects
+                let (version, source) = match project_type {
+                    ProjectType::Poetry(_) => (
+                        PoetryMigrationSource::extract_python_version(project_dir)?,
+                        PythonSource::PoetryConstraint,
+                    ),
+                    ProjectType::Pdm => (
+                        PdmMigrationSource::extract_python_version(project_dir)?,
+                        PythonSource::RequiresPython,
+                    ),
+                    ProjectType::Pep621 => (
+                        Pep621MigrationSource::extract_python_version(project_dir)?,
+                        PythonSource::RequiresPython,
+                    ),
+                    ProjectType::Conda => (
+                        conda_source(&self.args).extract_python_version(project_dir)?,
+                        PythonSource::CondaEnvironment,
+                    ),
+                    _ => (None, PythonSource::NotFound),
+                };
+                match version {
+                    Some(version) if self.args.no_pin_python => {
+                        info!(
+                            "Found Python version constraint {}, not pinning it because of --no-pin-python",
+                            version
+                        );
+                        PythonSelection {
+                            version: None,
+                            source: PythonSource::NotPinned,
+                        }
+                    }
+                    Some(version) => PythonSelection {
+                        version: Some(version),
+                        source,
+                    },
+                    None => PythonSelection::default(),
+                }
+            }
+        };
+
+        match &selection.version {
+            Some(version) => info!(
+                "Using Python {} for uv init (source: {})",
+                version, selection.source
+            ),
+            None => info!(
+                "Not pinning a Python version for uv init (source: {})",
+                selection.source
+            ),
+        }
+        Ok(selection)
+    }
+
+    /// Backs up pyproject.toml and runs `uv init` with precomputed arguments
+    pub fn init_project(
+        &self,
+        project_dir: &Path,
+        file_tracker: &mut FileTrackerGuard,
+        is_package: bool,
+        init_args: &[String],
+    ) -> Result<(), String> {
+        // uv init may scaffold files we never write ourselves, such as .python-version
+        let existing_entries = if self.args.dry_run {
+            None
+        } else {
+            Some(directory_entries(project_dir)?)
+        };
+        let result = self.run_init(project_dir, file_tracker, is_package, init_args);
+        if let Some(existing_entries) = existing_entries {
+            for path in directory_entries(project_dir)?.difference(&existing_entries) {
+                file_tracker.track_created_file(path)?;
+            }
+        }
+        result
+    }
+
+    fn run_init(
+        &self,
+        project_dir: &Path,
+        file_tracker: &mut FileTrackerGuard,
+        is_package: bool,
+        init_args: &[String],
     ) -> Result<(), String> {
         let pyproject_path = project_dir.join("pyproject.toml");
         let backup_path = project_dir.join("old.pyproject.toml");
 
         // Backup existing pyproject.toml if it exists
-        if pyproject_path.exists() {
+        if pyproject_path.exists() && self.args.dry_run {
+            info!("[dry-run] Would rename pyproject.toml to old.pyproject.toml");
+        } else if pyproject_path.exists() {
             file_tracker.track_rename(&pyproject_path, &backup_path)?;
             fs::rename(&pyproject_path, &backup_path)
                 .map_err(|e| format!("Failed to rename existing pyproject.toml: {}", e))?;
@@ -65,69 +228,160 @@ impl MigrationTool for UvTool {
         }
         file_tracker.track_file(&pyproject_path)?;
 
-        // Determine if this is a package project
-        let is_package = matches!(
-            project_type,
-            &ProjectType::Poetry(PoetryProjectType::Package) | &ProjectType::SetupPy
-        );
+        let command = self.command(project_dir)?.args(init_args.iter().cloned());
 
-        // Extract Python version for Poetry projects
-        let python_version = match project_type {
-            ProjectType::Poetry(_) => {
-                match PoetryMigrationSource::extract_python_version(project_dir)? {
-                    Some(version) => {
-                        info!("Found Python version constraint: {}", version);
-                        Some(version)
-                    }
-                    None => {
-                        info!("No Python version constraint found, using --no-pin-python");
-                        None
-                    }
-                }
-            }
-            _ => None,
+        // Remember any package sources the scaffold could clobber
+        let package_sources = if is_package && !self.args.dry_run {
+            PackageSourceSnapshot::capture(project_dir)?
+        } else {
+            PackageSourceSnapshot::default()
         };
 
-        // Find uv executable
-        let uv_path =
-            which::which("uv").map_err(|e| format!("Failed to find uv command: {}", e))?;
-
-        // Build uv init command
-        let mut command = std::process::Command::new(&uv_path);
-        command.arg("init");
+        info!("Executing uv init command: {:?}", command.get_args());
+        self.run(&command, "uv init")?;
+        if self.args.dry_run {
+            return Ok(());
+        }
+        if !pyproject_path.exists() {
+            return Err(format!(
+                "uv init reported success but no pyproject.toml was created in '{}'",
+                project_dir.display()
+            ));
+        }
 
-        // Add appropriate flags based on project configuration
-        if python_version.is_none() {
-            command.arg("--no-pin-python");
+        info!("Successfully initialized new project with uv init");
+        if package_sources.has_existing_sources() {
+            info!("Preserving existing package sources under src/");
+            package_sources.restore(project_dir)?;
         }
+        Ok(())
+    }
 
-        if is_package {
-            command.arg("--package");
+    /// Computes one set of `uv add` arguments per dependency type.
+    ///
+    /// Dependency types are ordered by their first appearance and dependencies keep
+    /// their extraction order, so the same input always produces the same commands.
+    pub fn add_args(&self, dependencies: &[Dependency]) -> Vec<Vec<String>> {
+        let mut grouped_deps: Vec<(&DependencyType, Vec<&Dependency>)> = Vec::new();
+        for dep in dependencies {
+            match grouped_deps
+                .iter_mut()
+                .find(|(dep_type, _)| *dep_type == &dep.dep_type)
+            {
+                Some((_, deps)) => deps.push(dep),
+                None => grouped_deps.push((&dep.dep_type, vec![dep])),
+            }
         }
 
-        if let Some(version) = python_version {
-            command.arg("--python").arg(version);
+        grouped_deps
+            .into_iter()
+            .map(|(dep_type, deps)| build_add_args(dep_type, &deps))
+            .collect()
+    }
+
+    /// Logs how each versioned dependency's constraint is rewritten for uv,
+    /// e.g. `fastapi: ^0.111.0 -> >=0.111.0,<0.112.0`
+    pub fn log_constraint_summary(&self, dependencies: &[Dependency]) {
+        let rewritten: Vec<(&str, RenderedDependency)> = dependencies
+            .iter()
+            .map(|dep| (dep.name.as_str(), render_dependency(dep)))
+            .filter(|(_, rendered)| rendered.original.is_some())
+            .collect();
+        if rewritten.is_empty() {
+            return;
         }
 
-        // Set working directory and execute command
-        command.current_dir(project_dir);
+        info!("Dependency constraints:");
+        for (name, rendered) in rewritten {
+            info!(
+                "  {}: {} -> {}",
+                name,
+                rendered.original.unwrap_or_default().trim(),
+                rendered.constraint.unwrap_or_default()
+            );
+        }
+    }
 
+    /// Runs `uv add` with precomputed arguments
+    pub fn run_add(&self, project_dir: &Path, add_args: &[String]) -> Result<(), String> {
+        let command = self.command(project_dir)?.args(add_args.iter().cloned());
         info!(
-            "Executing uv init command: {:?}",
-            command.get_args().collect::<Vec<_>>()
+            "Adding dependencies: {}",
+            add_args
+                .iter()
+                .skip(1)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(" ")
         );
+        self.run(&command, "uv add")
+    }
 
-        let output = command
-            .output()
-            .map_err(|e| format!("Failed to execute uv init: {}", e))?;
+    /// Runs `uv add` for each set of arguments, at most `--max-parallel-adds` at a time.
+    ///
+    /// With the default of one the commands run in order, one after another.
+    pub fn run_adds(&self, project_dir: &Path, add_args: &[Vec<String>]) -> Result<(), String> {
+        let max_parallel = self.args.max_parallel_adds.max(1);
+        if max_parallel == 1 || self.args.dry_run {
+            for args in add_args {
+                self.run_add(project_dir, args)?;
+            }
+            return Ok(());
+        }
 
-        if output.status.success() {
-            info!("Successfully initialized new project with uv init");
-            Ok(())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(format!("uv init failed: {}", stderr))
+        warn!(
+            "Running up to {} uv add commands at once; they all update pyproject.toml and \
+            uv.lock, so concurrent adds can overwrite each other's changes",
+            max_parallel
+        );
+        for batch in add_args.chunks(max_parallel) {
+            std::thread::scope(|scope| {
+                let handles: Vec<_> = batch
+                    .iter()
+                    .map(|args| scope.spawn(move || self.run_add(project_dir, args)))
+                    .collect();
+                handles.into_iter().try_for_each(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|_| Err("uv add panicked".to_string()))
+                })
+            })?;
         }
+        Ok(())
+    }
+
+    /// Computes the `uv lock` arguments, or `None` when no lock was requested
+    pub fn lock_args(&self) -> Option<Vec<String>> {
+        if !self.args.lock && self.args.lock_args.is_empty() {
+            return None;
+        }
+        let mut lock_args = vec!["lock".to_string()];
+        lock_args.extend(self.args.lock_args.iter().cloned());
+        Some(lock_args)
+    }
+
+    /// Runs `uv lock` with precomputed arguments
+    pub fn run_lock(&self, project_dir: &Path, lock_args: &[String]) -> Result<(), String> {
+        let command = self.command(project_dir)?.args(lock_args.iter().cloned());
+        info!("Locking dependencies");
+        self.run(&command, "uv lock")
+    }
+}
+
+impl MigrationTool for UvTool {
+    fn prepare_project(
+        &self,
+        project_dir: &Path,
+        file_tracker: &mut FileTrackerGuard,
+        project_type: &ProjectType,
+    ) -> Result<(), String> {
+        let init_args = self.init_args(project_dir, project_type)?;
+        self.init_project(
+            project_dir,
+            file_tracker,
+            is_package(project_type),
+            &init_args,
+        )
     }
 
     fn add_dependencies(
@@ -135,86 +389,305 @@ impl MigrationTool for UvTool {
         project_dir: &Path,
         dependencies: &[Dependency],
     ) -> Result<(), String> {
-        let uv_path =
-            which::which("uv").map_err(|e| format!("Failed to find uv command: {}", e))?;
+        self.log_constraint_summary(dependencies);
+        self.run_adds(project_dir, &self.add_args(dependencies))?;
 
-        let mut grouped_deps: HashMap<&DependencyType, Vec<&Dependency>> = HashMap::new();
-        for dep in dependencies {
-            grouped_deps.entry(&dep.dep_type).or_default().push(dep);
+        info!("All dependencies added successfully!");
+        Ok(())
+    }
+}
+
+/// The top-level entries of a directory
+fn directory_entries(dir: &Path) -> Result<BTreeSet<PathBuf>, String> {
+    fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory '{}': {}", dir.display(), e))?
+        .map(|entry| {
+            entry
+                .map(|entry| entry.path())
+                .map_err(|e| format!("Failed to read directory '{}': {}", dir.display(), e))
+        })
+        .collect()
+}
+
+/// Whether `uv init` should scaffold a package for this project type
+fn is_package(project_type: &ProjectType) -> bool {
+    matches!(
+        project_type,
+        ProjectType::Poetry(PoetryProjectType::Package | PoetryProjectType::Mixed)
+            | ProjectType::SetupPy
+    )
+}
+
+/// Builds the `uv init` arguments.
+///
+/// The detected Python version is passed as `--python` unless `--no-pin-python` was requested,
+/// and `--vcs` defaults to `none` so uv never touches version control unless asked to.
+pub fn build_init_args(is_package: bool, python_version: Option<&str>, args: &Args) -> Vec<String> {
+    let mut init_args = vec!["init".to_string()];
+    let python_version = python_version.filter(|_| !args.no_pin_python);
+
+    if python_version.is_none() {
+        init_args.push("--no-pin-python".to_string());
+    }
+
+    if is_package {
+        init_args.push("--package".to_string());
+    }
+
+    if let Some(version) = python_version {
+        init_args.push("--python".to_string());
+        init_args.push(version.to_string());
+    }
+
+    init_args.push("--vcs".to_string());
+    init_args.push(args.vcs.clone());
+
+    init_args
+}
+
+/// Builds the `uv add` arguments for one dependency type.
+pub fn build_add_args(dep_type: &DependencyType, deps: &[&Dependency]) -> Vec<String> {
+    let mut add_args = vec!["add".to_string()];
+
+    match dep_type {
+        DependencyType::Dev => add_args.push("--dev".to_string()),
+        DependencyType::Group(group_name) => {
+            add_args.push("--group".to_string());
+            add_args.push(group_name.clone());
         }
+        DependencyType::Optional(extra) => {
+            add_args.push("--optional".to_string());
+            add_args.push(extra.clone());
+        }
+        DependencyType::Main => {}
+    }
 
-        for (dep_type, deps) in grouped_deps {
-            if deps.is_empty() {
-                continue;
-            }
+    add_args.extend(deps.iter().map(|dep| format_dependency(dep)));
+    add_args
+}
 
-            let mut command = std::process::Command::new(&uv_path);
-            command.arg("add");
+/// Renders a dependency as a PEP 508 requirement string for `uv add`.
+///
+/// Poetry caret and tilde requirements are expanded to their exact bounds, e.g. `^0.5.2`
+/// becomes `>=0.5.2,<0.6.0` and `~1.2` becomes `>=1.2,<1.3.0`. Each constraint of a
+/// comma-separated list is expanded the same way, so `^1.2,!=1.2.5` stays valid PEP 440.
+pub fn format_dependency(dep: &Dependency) -> String {
+    render_dependency(dep).requirement
+}
 
-            match dep_type {
-                DependencyType::Dev => {
-                    command.arg("--dev");
-                }
-                DependencyType::Group(group_name) => {
-                    command.arg("--group").arg(group_name);
-                }
-                DependencyType::Main => {}
-            }
+/// A dependency rendered for `uv add`, along with the constraint it was rendered from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedDependency {
+    /// The version constraint as written in the source project
+    pub original: Option<String>,
+    /// The version constraint passed to uv, e.g. `>=0.111.0,<0.112.0`
+    pub constraint: Option<String>,
+    /// The full PEP 508 requirement string
+    pub requirement: String,
+}
 
-            command.current_dir(project_dir);
-
-            for dep in deps {
-                let mut dep_str = if let Some(version) = &dep.version {
-                    let version = version.trim();
-                    if version.contains(',') || version.starts_with("~=") {
-                        format!("{}{}", dep.name, version)
-                    } else if let Some(stripped) = version.strip_prefix('~') {
-                        format!("{}~={}", dep.name, stripped)
-                    } else if let Some(stripped) = version.strip_prefix('^') {
-                        format!("{}>={}", dep.name, stripped)
-                    } else if version.starts_with(['>', '<', '=']) {
-                        format!("{}{}", dep.name, version)
-                    } else {
-                        format!("{}=={}", dep.name, version)
-                    }
-                } else {
-                    dep.name.clone()
-                };
+/// Renders a dependency for `uv add`; see [`format_dependency`].
+pub fn render_dependency(dep: &Dependency) -> RenderedDependency {
+    let name = match &dep.extras {
+        Some(extras) => format!("{}[{}]", dep.name, extras.join(",")),
+        None => dep.name.clone(),
+    };
+
+    let constraint = dep.version.as_deref().map(|version| {
+        let version = version.trim();
+        if version.starts_with("git+") {
+            format!(" @ {}", version)
+        } else {
+            exact_constraints(version, 3)
+        }
+    });
 
-                if let Some(markers) = &dep.environment_markers {
-                    dep_str.push_str(&format!("; {}", markers));
-                }
-                command.arg(dep_str);
+    let mut requirement = format!("{}{}", name, constraint.as_deref().unwrap_or_default());
+    if let Some(markers) = &dep.environment_markers {
+        requirement.push_str(&format!("; {}", markers));
+    }
+
+    RenderedDependency {
+        original: dep.version.clone(),
+        constraint: constraint.map(|c| c.trim().to_string()),
+        requirement,
+    }
+}
+
+/// Converts each comma-separated Poetry constraint into its PEP 440 equivalent without
+/// changing the range it allows.
+///
+/// Poetry carets allow changes up to the left-most non-zero segment, so `^0.5.2` must not
+/// admit the breaking 0.6 release, and Poetry's `~1.2.3` stops before 1.3, unlike pip's
+/// `~=1.2.3`, so both are spelled out as explicit ranges. Upper bounds are written with
+/// `segments` release segments, e.g. `<0.6.0` for 3 and `<0.6` for 2. Empty constraints
+/// are dropped.
+fn exact_constraints(version: &str, segments: usize) -> String {
+    version
+        .split(',')
+        .map(str::trim)
+        .filter(|constraint| !constraint.is_empty())
+        .map(|constraint| {
+            if let Some(base) = constraint.strip_prefix('^') {
+                bounded_range(base.trim(), caret_upper_bound(base.trim(), segments))
+            } else if constraint.starts_with("~=") {
+                constraint.to_string()
+            } else if let Some(base) = constraint.strip_prefix('~') {
+                bounded_range(base.trim(), tilde_upper_bound(base.trim(), segments))
+            } else if constraint.starts_with(['>', '<', '=', '!']) {
+                constraint.to_string()
+            } else {
+                format!("=={}", constraint)
             }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
 
-            info!(
-                "Running uv add command for {:?} dependencies: {:?}",
-                dep_type, command
-            );
+/// Converts a Poetry `python` constraint such as `^3.11` into a `requires-python`
+/// specifier such as `>=3.11,<4.0`.
+///
+/// Returns `None` for `*` and for `||` alternatives, which PEP 440 cannot express.
+pub fn requires_python_from_poetry(constraint: &str) -> Option<String> {
+    let constraint = constraint.trim();
+    if constraint.is_empty() || constraint == "*" || constraint.contains("||") {
+        return None;
+    }
 
-            let output = command
-                .output()
-                .map_err(|e| format!("Failed to execute uv command: {}", e))?;
+    Some(exact_constraints(constraint, 2))
+}
 
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(format!(
-                    "Failed to add {:?} dependencies: {}",
-                    dep_type, stderr
-                ));
-            }
-        }
+fn bounded_range(base: &str, upper: Option<String>) -> String {
+    match upper {
+        Some(upper) => format!(">={},<{}", base, upper),
+        None => format!(">={}", base),
+    }
+}
 
-        info!("All dependencies added successfully!");
-        Ok(())
+/// Parses the leading numeric release segments of a version such as `1.2.3`
+fn release_segments(version: &str) -> Option<Vec<u64>> {
+    let segments: Vec<u64> = version
+        .split('.')
+        .map_while(|part| part.parse().ok())
+        .collect();
+    (!segments.is_empty()).then_some(segments)
+}
+
+/// Renders bumped release segments padded to at least `width` components
+fn render_upper_bound(mut segments: Vec<u64>, width: usize) -> String {
+    while segments.len() < width {
+        segments.push(0);
     }
+    segments
+        .iter()
+        .map(|s| s.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Upper bound of a Poetry caret requirement: the left-most non-zero segment is bumped
+fn caret_upper_bound(version: &str, width: usize) -> Option<String> {
+    let mut segments = release_segments(version)?;
+    let index = segments
+        .iter()
+        .position(|&s| s != 0)
+        .unwrap_or(segments.len() - 1);
+    segments.truncate(index + 1);
+    segments[index] += 1;
+    Some(render_upper_bound(segments, width))
+}
+
+/// Upper bound of a Poetry tilde requirement: the minor segment is bumped when present
+fn tilde_upper_bound(version: &str, width: usize) -> Option<String> {
+    let mut segments = release_segments(version)?;
+    let index = if segments.len() >= 2 { 1 } else { 0 };
+    segments.truncate(index + 1);
+    segments[index] += 1;
+    Some(render_upper_bound(segments, width))
 }
 
-pub fn merge_dependency_groups(dependencies: Vec<Dependency>) -> Vec<Dependency> {
+/// Resolves packages declared both as main dependencies and in a dev or named group.
+///
+/// The main entry is kept and the group entries are dropped, unless `prefer_group`
+/// is set, in which case the main entry is dropped instead.
+pub fn dedupe_main_and_groups(
+    dependencies: Vec<Dependency>,
+    prefer_group: bool,
+) -> Vec<Dependency> {
+    let in_group =
+        |dep: &Dependency| matches!(dep.dep_type, DependencyType::Dev | DependencyType::Group(_));
+    let main_names: HashSet<String> = dependencies
+        .iter()
+        .filter(|d| d.dep_type == DependencyType::Main)
+        .map(|d| d.name.to_lowercase())
+        .collect();
+    let group_names: HashSet<String> = dependencies
+        .iter()
+        .filter(|d| in_group(d))
+        .map(|d| d.name.to_lowercase())
+        .collect();
+
+    dependencies
+        .into_iter()
+        .filter(|dep| {
+            let name = dep.name.to_lowercase();
+            let duplicate = if prefer_group {
+                dep.dep_type == DependencyType::Main && group_names.contains(&name)
+            } else {
+                in_group(dep) && main_names.contains(&name)
+            };
+            if duplicate {
+                info!(
+                    "'{}' is declared in main and group dependencies; keeping the {} entry",
+                    dep.name,
+                    if prefer_group { "group" } else { "main" }
+                );
+            }
+            !duplicate
+        })
+        .collect()
+}
+
+/// Sorts dependencies into a stable order: main, dev, groups and then extras, with
+/// groups and extras by name and the packages of each by name
+pub fn sort_dependencies(dependencies: &mut [Dependency]) {
+    dependencies.sort_by_cached_key(|dep| {
+        let (rank, label) = match &dep.dep_type {
+            DependencyType::Main => (0, String::new()),
+            DependencyType::Dev => (1, String::new()),
+            DependencyType::Group(group) => (2, group.clone()),
+            DependencyType::Optional(extra) => (3, extra.clone()),
+        };
+        (rank, label, dep.name.to_lowercase())
+    });
+}
+
+/// Moves dev dependencies into the named dependency group
+pub fn rename_dev_group(dependencies: Vec<Dependency>, group: &str) -> Vec<Dependency> {
     dependencies
         .into_iter()
         .map(|mut dep| {
-            if matches!(dep.dep_type, DependencyType::Group(_)) {
+            if dep.dep_type == DependencyType::Dev {
+                dep.dep_type = DependencyType::Group(group.to_string());
+            }
+            dep
+        })
+        .collect()
+}
+
+/// Moves group dependencies into dev dependencies.
+///
+/// Dependencies of optional groups keep their group unless `merge_optional` is set.
+pub fn merge_dependency_groups(
+    dependencies: Vec<Dependency>,
+    merge_optional: bool,
+) -> Vec<Dependency> {
+    dependencies
+        .into_iter()
+        .map(|mut dep| {
+            if matches!(dep.dep_type, DependencyType::Group(_))
+                && (merge_optional || !dep.optional_group)
+            {
                 dep.dep_type = DependencyType::Dev;
             }
             dep
@@ -222,13 +695,275 @@ pub fn merge_dependency_groups(dependencies: Vec<Dependency>) -> Vec<Dependency>
         .collect()
 }
 
-pub fn run_migration(
+/// Describes every dependency whose environment marker is not valid PEP 508
+pub fn invalid_markers(dependencies: &[Dependency]) -> Vec<String> {
+    dependencies
+        .iter()
+        .filter_map(|dep| {
+            let marker = dep.environment_markers.as_ref()?;
+            validate_marker(marker)
+                .err()
+                .map(|e| format!("{} ('{}'): {}", dep.name, marker, e))
+        })
+        .collect()
+}
+
+/// Describes every dependency whose version constraint would not render as a
+/// valid PEP 440 specifier set for `uv add`, along with its index in `dependencies`
+pub fn invalid_versions(dependencies: &[Dependency]) -> Vec<(usize, String)> {
+    dependencies
+        .iter()
+        .enumerate()
+        .filter_map(|(index, dep)| {
+            let constraint = render_dependency(dep).constraint?;
+            if constraint.starts_with('@') {
+                return None;
+            }
+            crate::utils::specifiers::validate_specifiers(&constraint)
+                .err()
+                .map(|e| {
+                    let original = dep.version.as_deref().unwrap_or_default();
+                    (index, format!("{} ('{}'): {}", dep.name, original, e))
+                })
+        })
+        .collect()
+}
+
+/// Runs a full migration of the project in `project_dir` and summarizes what it did.
+///
+/// Refuses to run on a project that was already migrated unless `--force` is given.
+pub fn run_migration(project_dir: &Path, args: &Args) -> Result<MigrationSummary, Error> {
+    let plan = plan_migration(project_dir, args)?;
+    apply_plan(&plan)
+}
+
+/// Refuses to migrate a project that was already migrated, unless `--force` is given
+fn ensure_not_migrated(project_dir: &Path, args: &Args) -> Result<(), Error> {
+    if !args.force && detect::is_uv_project(project_dir) {
+        return Err(Error::AlreadyMigrated(format!(
+            "{}: found uv.lock and a pyproject.toml without Poetry, PDM or Pipenv configuration. \
+            Use --force to migrate it again.",
+            detect::ALREADY_MIGRATED_ERROR
+        )));
+    }
+    Ok(())
+}
+
+/// The Conda source configured by `--environment-file`, `--conda-map` and
+/// `--conda-include-system`
+fn conda_source(args: &Args) -> CondaMigrationSource {
+    let name_mappings = args
+        .conda_map
+        .iter()
+        .filter_map(|mapping| crate::cli::parse_conda_map(mapping).ok())
+        .collect();
+    let source = CondaMigrationSource::new(name_mappings).include_system(args.conda_include_system);
+    match &args.environment_file {
+        Some(environment_file) => source.environment_file(environment_file),
+        None => source,
+    }
+}
+
+/// The source that reads dependencies for `project_type`
+fn migration_source(project_type: &ProjectType, args: &Args) -> Box<dyn MigrationSource> {
+    match project_type {
+        ProjectType::Poetry(_) => Box::new(poetry::PoetryMigrationSource),
+        ProjectType::Pipenv => Box::new(pipenv::PipenvMigrationSource),
+        ProjectType::Requirements => Box::new(requirements::RequirementsMigrationSource),
+        ProjectType::SetupPy => Box::new(SetupPyMigrationSource),
+        ProjectType::Conda => Box::new(conda_source(args)),
+        ProjectType::Pdm => Box::new(PdmMigrationSource),
+        ProjectType::Pep621 => Box::new(Pep621MigrationSource),
+    }
+}
+
+/// Detects the project and computes every `uv` command of the migration without
+/// changing any files.
+pub fn plan_migration(project_dir: &Path, args: &Args) -> Result<MigrationPlan, Error> {
+    ensure_not_migrated(project_dir, args)?;
+    if let Some(version) = &args.python {
+        crate::cli::parse_python_version(version)?;
+    }
+    for mapping in &args.conda_map {
+        crate::cli::parse_conda_map(mapping)?;
+    }
+    if let Some(strategy) = &args.index_strategy {
+        crate::cli::parse_index_strategy(strategy)?;
+    }
+    crate::utils::build_system::build_backend_spec(&args.build_backend)?;
+    if args.dependencies_only && args.metadata_only {
+        return Err(
+            "--dependencies-only and --metadata-only cannot be used together"
+                .to_string()
+                .into(),
+        );
+    }
+    if args.strip_comments && (args.preserve_dep_comments || args.index_auth_env) {
+        return Err(
+            "--strip-comments cannot be used with --preserve-dep-comments or --index-auth-env"
+                .to_string()
+                .into(),
+        );
+    }
+
+    let project_type: ProjectType = match &args.environment_file {
+        Some(environment_file) => {
+            if !project_dir.join(environment_file).exists() {
+                return Err(format!(
+                    "Conda environment file '{}' not found",
+                    project_dir.join(environment_file).display()
+                )
+                .into());
+            }
+            info!(
+                "Using Conda environment file {} from --environment-file",
+                environment_file.display()
+            );
+            ProjectType::Conda
+        }
+        None => {
+            detect::select_project_type(project_dir, args.from.as_deref(), args.strict_detection)?
+        }
+    };
+    info!("Detected project type: {:?}", project_type);
+
+    // Extract dependencies based on project type
+    let migration_source = migration_source(&project_type, args);
+    let source_files = project_source_files(project_dir, &*migration_source)?;
+
+    let mut dependencies = match project_type {
+        ProjectType::Poetry(_) if args.prefer_poetry_table => {
+            poetry::PoetryMigrationSource.extract_dependencies_with_precedence(project_dir, true)?
+        }
+        _ => migration_source.extract_dependencies(project_dir)?,
+    };
+
+    // tox.ini test environments complement whichever source declares the project
+    for dep in tox::ToxMigrationSource.extract_dependencies(project_dir)? {
+        let declared = dependencies
+            .iter()
+            .any(|d| d.dep_type == dep.dep_type && d.name.eq_ignore_ascii_case(&dep.name));
+        if !declared {
+            dependencies.push(dep);
+        }
+    }
+    dependencies = dedupe_main_and_groups(dependencies, args.prefer_group);
+    info!("Extracted {} dependencies", dependencies.len());
+
+    if let (ProjectType::Requirements, Some(group)) = (&project_type, &args.dev_group_name) {
+        dependencies = rename_dev_group(dependencies, group);
+        info!(
+            "Moved requirements-dev.txt dependencies into group '{}'",
+            group
+        );
+    }
+
+    if args.merge_groups {
+        dependencies = merge_dependency_groups(dependencies, args.merge_optional);
+        info!("Merged all dependency groups into dev dependencies");
+    }
+
+    if args.validate_markers {
+        let problems = invalid_markers(&dependencies);
+        if args.strict && !problems.is_empty() {
+            return Err(format!("Invalid environment markers:\n{}", problems.join("\n")).into());
+        }
+        for problem in &problems {
+            warn!("Invalid environment marker: {}", problem);
+        }
+    }
+
+    // Collected even without --report-skipped, as the migration summary lists them too
+    let mut skipped = migration_source.skipped_items(project_dir)?;
+
+    let invalid = invalid_versions(&dependencies);
+    if !invalid.is_empty() {
+        if !args.skip_invalid {
+            let problems: Vec<&str> = invalid.iter().map(|(_, p)| p.as_str()).collect();
+            return Err(format!(
+                "Invalid version specifiers:\n{}\nFix them or pass --skip-invalid to leave these dependencies out",
+                problems.join("\n")
+            ).into());
+        }
+        for (index, problem) in &invalid {
+            warn!("Skipping dependency with an invalid version: {}", problem);
+            skipped.push(SkippedItem::new(
+                SkippedKind::Dependency,
+                &dependencies[*index].name,
+                format!("invalid version specifier: {}", problem),
+            ));
+        }
+        // Only the offending entries go; the same package in other groups or
+        // with other markers is kept
+        let mut index = 0;
+        dependencies.retain(|_| {
+            let keep = !invalid.iter().any(|(i, _)| *i == index);
+            index += 1;
+            keep
+        });
+    }
+
+    if args.deterministic {
+        sort_dependencies(&mut dependencies);
+        skipped.sort_by(|a, b| (a.kind, &a.name, &a.reason).cmp(&(b.kind, &b.name, &b.reason)));
+    }
+
+    let migration_tool = UvTool::new(args);
+    let add_args = if args.metadata_only {
+        info!("Not adding dependencies (--metadata-only)");
+        Vec::new()
+    } else {
+        migration_tool.log_constraint_summary(&dependencies);
+        migration_tool.add_args(&dependencies)
+    };
+    let python = migration_tool.python_selection(project_dir, &project_type)?;
+    Ok(MigrationPlan {
+        project_dir: project_dir.to_path_buf(),
+        is_package: is_package(&project_type),
+        init_args: build_init_args(is_package(&project_type), python.version.as_deref(), args),
+        python,
+        add_args,
+        lock_args: migration_tool.lock_args(),
+        dependencies: DependencyCounts::from_dependencies(&dependencies),
+        project_type,
+        skipped,
+        options: args.clone(),
+        source_hash: hash_source_files(project_dir, &source_files)?,
+        format_version: PLAN_FORMAT_VERSION,
+    })
+}
+
+/// The files `source` and tox.ini read the project from
+fn project_source_files(
     project_dir: &Path,
-    import_global_pip_conf: bool,
-    additional_index_urls: &[String],
-    merge_groups: bool,
-) -> Result<(), String> {
-    let mut file_tracker = FileTrackerGuard::new();
+    source: &dyn MigrationSource,
+) -> Result<Vec<PathBuf>, String> {
+    let mut files = source.source_files(project_dir)?;
+    files.extend(tox::ToxMigrationSource.source_files(project_dir)?);
+    Ok(files)
+}
+
+/// Executes a migration plan, rolling back file changes if any step fails.
+pub fn apply_plan(plan: &MigrationPlan) -> Result<MigrationSummary, Error> {
+    let args = &plan.options;
+    let project_dir = plan.project_dir.as_path();
+    let project_type = &plan.project_type;
+    // The project may have been migrated or edited since the plan was made
+    ensure_not_migrated(project_dir, args)?;
+    // Resolved again, so files added since planning, like a new requirements file, count too
+    let source_files = project_source_files(project_dir, &*migration_source(project_type, args))?;
+    if hash_source_files(project_dir, &source_files)? != plan.source_hash {
+        return Err(
+            "The project's source files changed since the plan was made; create a new plan"
+                .to_string()
+                .into(),
+        );
+    }
+    let mut file_tracker = if args.dry_run {
+        FileTrackerGuard::new_dry_run()
+    } else {
+        FileTrackerGuard::new()
+    };
     let hello_py_path = project_dir.join("hello.py");
     let pyproject_path = project_dir.join("pyproject.toml");
     let old_pyproject_path = project_dir.join("old.pyproject.toml");
@@ -237,56 +972,111 @@ pub fn run_migration(
         file_tracker.track_file(&hello_py_path)?;
     }
 
-    let result = (|| {
-        let project_type: ProjectType = detect_project_type(project_dir)?;
-        info!("Detected project type: {:?}", project_type);
-
-        // Extract dependencies based on project type
-        let migration_source: Box<dyn MigrationSource> = match project_type {
-            ProjectType::Poetry(_) => Box::new(poetry::PoetryMigrationSource),
-            ProjectType::Pipenv => Box::new(pipenv::PipenvMigrationSource),
-            ProjectType::Requirements => Box::new(requirements::RequirementsMigrationSource),
-            ProjectType::SetupPy => Box::new(SetupPyMigrationSource),
-        };
-
-        let mut dependencies = migration_source.extract_dependencies(project_dir)?;
-        info!("Extracted {} dependencies", dependencies.len());
-
-        if merge_groups {
-            dependencies = merge_dependency_groups(dependencies);
-            info!("Merged all dependency groups into dev dependencies");
-        }
+    let mut summary = MigrationSummary {
+        project_type: format!("{:?}", project_type),
+        dependencies: plan.dependencies.clone(),
+        python: plan.python.clone(),
+        skipped: plan.skipped.clone(),
+        dry_run: args.dry_run,
+        ..MigrationSummary::default()
+    };
 
+    let result: Result<(), String> = (|| {
         // Initialize UV project
-        let migration_tool = UvTool;
-        migration_tool.prepare_project(project_dir, &mut file_tracker, &project_type)?;
+        let migration_tool = UvTool::new(args);
+        migration_tool.init_project(
+            project_dir,
+            &mut file_tracker,
+            plan.is_package,
+            &plan.init_args,
+        )?;
         info!("Project initialized with UV");
 
         // Add dependencies
-        migration_tool.add_dependencies(project_dir, &dependencies)?;
+        migration_tool.run_adds(project_dir, &plan.add_args)?;
         info!("Dependencies added successfully");
 
+        if args.dry_run {
+            if let Some(lock_args) = &plan.lock_args {
+                migration_tool.run_lock(project_dir, lock_args)?;
+            }
+            log_planned_steps(
+                project_type,
+                args,
+                old_pyproject_path.exists() || pyproject_path.exists(),
+            );
+            return Ok(());
+        }
+
         // Track pyproject.toml for potential updates
         file_tracker.track_file(&pyproject_path)?;
 
         if old_pyproject_path.exists() {
             match project_type {
-                ProjectType::Poetry(_) => perform_poetry_migration(project_dir, &mut file_tracker)?,
-                ProjectType::SetupPy => perform_setup_py_migration(project_dir, &mut file_tracker)?,
-                ProjectType::Pipenv => perform_pipenv_migration(project_dir, &mut file_tracker)?,
+                ProjectType::Poetry(poetry_type) => {
+                    perform_poetry_migration(project_dir, &mut file_tracker, poetry_type, args)?
+                }
                 ProjectType::Requirements => {
                     perform_requirements_migration(project_dir, &mut file_tracker)?
                 }
+                // These sources never read the old pyproject.toml, so keep its extras
+                ProjectType::SetupPy | ProjectType::Pipenv | ProjectType::Conda => {
+                    preserve_optional_dependencies(project_dir, &mut file_tracker)?
+                }
+                // Their extras are added back with `uv add --optional`
+                ProjectType::Pdm | ProjectType::Pep621 => {}
             }
         }
 
+        // Pipenv, setuptools and requirements projects rarely have a pyproject.toml to back up
+        match project_type {
+            ProjectType::Pipenv => perform_pipenv_migration(project_dir, &mut file_tracker)?,
+            ProjectType::SetupPy if !args.dependencies_only => {
+                perform_setup_py_migration(project_dir, &mut file_tracker)?
+            }
+            ProjectType::Requirements => {
+                let links = requirements::RequirementsMigrationSource.find_links(project_dir)?;
+                if !links.is_empty() {
+                    info!("Migrating find-links sources to [tool.uv]");
+                    file_tracker.track_file(&pyproject_path)?;
+                    pyproject::update_find_links(project_dir, &links)?;
+                }
+            }
+            _ => {}
+        }
+
         // Perform common migrations
-        perform_common_migrations(
-            project_dir,
-            &mut file_tracker,
-            import_global_pip_conf,
-            additional_index_urls,
-        )?;
+        perform_common_migrations(project_dir, &mut file_tracker, args, &mut summary)?;
+
+        // Recorded after --strip-comments has run, so the channels survive it
+        if *project_type == ProjectType::Conda {
+            perform_conda_migration(project_dir, &mut file_tracker, args)?;
+        }
+
+        if args.stamp {
+            info!("Adding migration stamp to pyproject.toml");
+            file_tracker.track_file(&pyproject_path)?;
+            pyproject::add_migration_stamp(
+                project_dir,
+                project_type.display_name(),
+                !args.deterministic,
+            )?;
+        }
+
+        if let Some(lock_args) = &plan.lock_args {
+            file_tracker.track_file(&project_dir.join("uv.lock"))?;
+            migration_tool.run_lock(project_dir, lock_args)?;
+        }
+
+        if old_pyproject_path.exists() {
+            summary.files_backed_up.push(old_pyproject_path.clone());
+        }
+        summary.sources_pinned = read_toml(&pyproject_path)?
+            .get("tool")
+            .and_then(|t| t.get("uv"))
+            .and_then(|u| u.get("sources"))
+            .and_then(|s| s.as_table_like())
+            .map_or(0, |sources| sources.len());
 
         // Cleanup
         if hello_py_path.exists() {
@@ -295,12 +1085,26 @@ pub fn run_migration(
             info!("Deleted hello.py");
         }
 
+        if let Some(report_path) = &args.report_skipped {
+            report::write_skipped_report(report_path, &plan.skipped)?;
+        }
+
+        // Every step that reads the backup has run by now
+        if !args.keep_old && old_pyproject_path.exists() {
+            file_tracker.track_delete(&old_pyproject_path)?;
+            fs::remove_file(&old_pyproject_path)
+                .map_err(|e| format!("Failed to delete old.pyproject.toml: {}", e))?;
+            summary
+                .files_backed_up
+                .retain(|path| path != &old_pyproject_path);
+            info!("Deleted old.pyproject.toml");
+        }
+
         Ok(())
     })();
 
-    if result.is_err() {
+    if let Err(migration_error) = result {
         info!("An error occurred during migration. Rolling back changes...");
-        let migration_error = result.unwrap_err();
         file_tracker.force_rollback();
         drop(file_tracker);
 
@@ -308,21 +1112,115 @@ pub fn run_migration(
             return Err(format!(
                 "{}\nError: Rollback failed - pyproject.toml was not restored.",
                 migration_error
-            ));
+            )
+            .into());
         }
 
         return Err(format!(
             "{}\nNote: File changes have been rolled back to their original state.",
             migration_error
-        ));
+        )
+        .into());
+    }
+    drop(file_tracker);
+
+    if args.clean_pycache {
+        if args.dry_run {
+            info!("[dry-run] Would remove __pycache__ directories and .pyc files");
+        } else {
+            crate::utils::pycache::clean_pycache(project_dir)?;
+        }
     }
 
-    result
+    if let Some(hook) = &args.post_hook {
+        if args.dry_run {
+            info!("[dry-run] Would run post-migration hook: {}", hook);
+        } else {
+            run_post_hook(project_dir, hook);
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Runs the `--post-hook` command through the shell in the project directory.
+///
+/// The migration has already succeeded at this point, so a failing hook is only
+/// reported as a warning.
+fn run_post_hook(project_dir: &Path, hook: &str) {
+    info!("Running post-migration hook: {}", hook);
+    let mut command = if cfg!(windows) {
+        let mut command = std::process::Command::new("cmd");
+        command.arg("/C");
+        command
+    } else {
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c");
+        command
+    };
+
+    match command.arg(hook).current_dir(project_dir).output() {
+        Ok(output) => {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                info!("[post-hook] {}", line);
+            }
+            for line in String::from_utf8_lossy(&output.stderr).lines() {
+                info!("[post-hook] {}", line);
+            }
+            if output.status.success() {
+                info!("Post-migration hook completed successfully");
+            } else {
+                warn!(
+                    "Post-migration hook '{}' failed with {}; the migration itself succeeded",
+                    hook, output.status
+                );
+            }
+        }
+        Err(e) => warn!("Failed to run post-migration hook '{}': {}", hook, e),
+    }
+}
+
+/// Logs the file changes a real run would make after `uv init` and `uv add`
+fn log_planned_steps(project_type: &ProjectType, args: &Args, has_pyproject: bool) {
+    if has_pyproject {
+        info!(
+            "[dry-run] Would migrate {} metadata from old.pyproject.toml into pyproject.toml",
+            project_type.display_name()
+        );
+    }
+    info!("[dry-run] Would update pyproject.toml with project version, indexes and tool sections");
+    if args.merge_extras_into_main {
+        info!("[dry-run] Would merge optional dependencies into the main dependencies");
+    }
+    if args.generate_gitignore {
+        info!("[dry-run] Would add uv entries to .gitignore");
+    }
+    if !args.no_reorder {
+        info!("[dry-run] Would reorder pyproject.toml sections");
+    }
+    if args.strip_comments {
+        info!("[dry-run] Would strip comments from pyproject.toml");
+    }
+    if args.stamp {
+        info!("[dry-run] Would add a migration stamp comment to pyproject.toml");
+    }
+    if let Some(report_path) = &args.report_skipped {
+        info!(
+            "[dry-run] Would write skipped items to {}",
+            report_path.display()
+        );
+    }
+    if !args.keep_old && has_pyproject {
+        info!("[dry-run] Would delete old.pyproject.toml");
+    }
+    info!("[dry-run] No files were changed");
 }
 
 fn perform_poetry_migration(
     project_dir: &Path,
     file_tracker: &mut FileTrackerGuard,
+    poetry_type: &PoetryProjectType,
+    args: &Args,
 ) -> Result<(), String> {
     let pyproject_path = project_dir.join("pyproject.toml");
 
@@ -333,6 +1231,32 @@ fn perform_poetry_migration(
         pyproject::update_uv_indices(project_dir, &sources)?;
     }
 
+    info!("Checking for source-pinned Poetry dependencies");
+    let source_pins = pyproject::extract_poetry_source_pins(project_dir)?;
+    if !source_pins.is_empty() {
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_source_pins(project_dir, &source_pins)?;
+    }
+
+    info!("Checking for Poetry git dependencies");
+    let git_deps = pyproject::extract_poetry_git_dependencies(project_dir)?;
+    if !git_deps.is_empty() {
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_git_dependencies(project_dir, &git_deps)?;
+    }
+
+    info!("Checking for Poetry path dependencies");
+    let path_deps = pyproject::extract_poetry_path_dependencies(project_dir)?;
+    if !path_deps.is_empty() {
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_path_dependencies(project_dir, &path_deps)?;
+    }
+
+    if args.dependencies_only {
+        info!("Skipping Poetry metadata, scripts and build system (--dependencies-only)");
+        return Ok(());
+    }
+
     info!("Migrating Poetry authors");
     let poetry_authors = extract_authors_from_poetry(project_dir)?;
     if !poetry_authors.is_empty() {
@@ -361,8 +1285,42 @@ fn perform_poetry_migration(
 
     info!("Checking Poetry build system");
     let mut doc = read_toml(&pyproject_path)?;
-    if update_build_system(&mut doc, project_dir)? {
-        info!("Migrated build system from Poetry to Hatchling");
+    if update_build_system(&mut doc, project_dir, &args.build_backend)? {
+        info!(
+            "Migrated build system from Poetry to {}",
+            args.build_backend
+        );
+        file_tracker.track_file(&pyproject_path)?;
+        write_toml(&pyproject_path, &mut doc)?;
+    } else if *poetry_type == PoetryProjectType::Mixed {
+        // Console scripts of a library are only installed when the package is built
+        info!(
+            "Using {} to build the library and its scripts",
+            args.build_backend
+        );
+        set_build_system(&mut doc, &args.build_backend)?;
+        file_tracker.track_file(&pyproject_path)?;
+        write_toml(&pyproject_path, &mut doc)?;
+    }
+
+    if args.build_backend != "hatchling" {
+        if read_toml(&project_dir.join("old.pyproject.toml"))?
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .is_some_and(|p| p.get("include").is_some() || p.get("exclude").is_some())
+        {
+            warn!(
+                "Poetry include and exclude entries are only migrated for hatchling, configure them for {} by hand",
+                args.build_backend
+            );
+        }
+        return Ok(());
+    }
+
+    info!("Migrating Poetry include and exclude entries");
+    let mut doc = read_toml(&pyproject_path)?;
+    if update_hatch_includes(&mut doc, project_dir)? {
+        info!("Migrated Poetry include and exclude entries to Hatchling build targets");
         file_tracker.track_file(&pyproject_path)?;
         write_toml(&pyproject_path, &mut doc)?;
     }
@@ -377,6 +1335,17 @@ fn perform_setup_py_migration(
     let pyproject_path = project_dir.join("pyproject.toml");
 
     info!("Migrating metadata from setup.py");
+    if let Some(name) = SetupPyMigrationSource::extract_name(project_dir)? {
+        file_tracker.track_file(&pyproject_path)?;
+        let mut doc = read_toml(&pyproject_path)?;
+        update_section(
+            &mut doc,
+            &["project", "name"],
+            Item::Value(Value::String(Formatted::new(name))),
+        );
+        write_toml(&pyproject_path, &mut doc)?;
+    }
+
     if let Some(description) = SetupPyMigrationSource::extract_description(project_dir)? {
         file_tracker.track_file(&pyproject_path)?;
         pyproject::update_description(project_dir, &description)?;
@@ -413,12 +1382,35 @@ fn perform_setup_py_migration(
     Ok(())
 }
 
+fn perform_conda_migration(
+    project_dir: &Path,
+    file_tracker: &mut FileTrackerGuard,
+    args: &Args,
+) -> Result<(), String> {
+    let channels = conda_source(args).channels(project_dir)?;
+    // Packages from the defaults channel alone need no explanation
+    if channels.is_empty() || channels.iter().all(|channel| channel == "defaults") {
+        return Ok(());
+    }
+
+    info!("Recording Conda channels: {}", channels.join(", "));
+    file_tracker.track_file(&project_dir.join("pyproject.toml"))?;
+    pyproject::add_conda_channels_comment(project_dir, &channels)
+}
+
 fn perform_pipenv_migration(
     project_dir: &Path,
     file_tracker: &mut FileTrackerGuard,
 ) -> Result<(), String> {
     let pyproject_path = project_dir.join("pyproject.toml");
 
+    info!("Checking for Pipfile package sources to migrate");
+    let sources = pipenv::PipenvMigrationSource::extract_sources(project_dir)?;
+    if !sources.is_empty() {
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_uv_indices(project_dir, &sources)?;
+    }
+
     if let Ok(content) = std::fs::read_to_string(project_dir.join("Pipfile")) {
         if content.contains("[scripts]") {
             info!("Migrating Pipfile scripts");
@@ -429,34 +1421,34 @@ fn perform_pipenv_migration(
     Ok(())
 }
 
+/// Keeps the `[project.optional-dependencies]` of an existing pyproject.toml
+fn preserve_optional_dependencies(
+    project_dir: &Path,
+    file_tracker: &mut FileTrackerGuard,
+) -> Result<(), String> {
+    file_tracker.track_file(&project_dir.join("pyproject.toml"))?;
+    pyproject::merge_optional_dependencies(project_dir)?;
+    Ok(())
+}
+
 fn perform_requirements_migration(
     project_dir: &Path,
     file_tracker: &mut FileTrackerGuard,
 ) -> Result<(), String> {
     let pyproject_path = project_dir.join("pyproject.toml");
 
+    preserve_optional_dependencies(project_dir, file_tracker)?;
+
     let requirements_source = requirements::RequirementsMigrationSource;
     let req_files = requirements_source.find_requirements_files(project_dir);
 
-    for (file_path, _dep_type) in req_files {
-        if let Some(file_name) = file_path.file_name().and_then(|n| n.to_str()) {
-            match file_name {
-                "requirements.txt" => {
-                    continue;
-                }
-                "requirements-dev.txt" => {
-                    continue;
-                }
-                _ => {
-                    if let Some(_group_name) = file_name
-                        .strip_prefix("requirements-")
-                        .and_then(|n| n.strip_suffix(".txt"))
-                    {
-                        info!("Configuring group from requirements file: {}", file_name);
-                        file_tracker.track_file(&pyproject_path)?;
-                    }
-                }
-            }
+    for (file_path, dep_type) in req_files {
+        if let DependencyType::Group(_) = dep_type {
+            info!(
+                "Configuring group from requirements file: {}",
+                file_path.display()
+            );
+            file_tracker.track_file(&pyproject_path)?;
         }
     }
 
@@ -466,8 +1458,103 @@ fn perform_requirements_migration(
 fn perform_common_migrations(
     project_dir: &Path,
     file_tracker: &mut FileTrackerGuard,
-    import_global_pip_conf: bool,
-    additional_index_urls: &[String],
+    args: &Args,
+    summary: &mut MigrationSummary,
+) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+
+    if args.dependencies_only {
+        info!("Skipping project metadata and tool sections (--dependencies-only)");
+    } else {
+        migrate_metadata(project_dir, file_tracker, args)?;
+    }
+
+    let mut extra_urls = Vec::new();
+    let mut default_url = None;
+    if args.import_global_pip_conf {
+        extra_urls.extend(parse_pip_conf()?);
+    }
+    if args.import_env_indexes {
+        extra_urls.extend(crate::utils::pip::index_urls_from_env());
+        default_url = crate::utils::pip::default_index_url_from_env();
+    }
+    extra_urls.extend(args.import_index.iter().cloned());
+
+    if !extra_urls.is_empty() {
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_uv_indices_from_urls(project_dir, &extra_urls, default_url.as_deref())?;
+    }
+
+    if args.index_auth_env {
+        file_tracker.track_file(&pyproject_path)?;
+        for hint in pyproject::add_index_auth_hints(project_dir)? {
+            info!(
+                "Index '{}' reads credentials from {} and {}",
+                hint.index, hint.username, hint.password
+            );
+        }
+    }
+
+    if let Some(strategy) = &args.index_strategy {
+        info!("Setting index-strategy to {}", strategy);
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_index_strategy(project_dir, strategy)?;
+    }
+
+    if !args.dependencies_only {
+        info!("Migrating Tool sections");
+        file_tracker.track_file(&pyproject_path)?;
+        summary.tool_sections_copied = pyproject::append_tool_sections(project_dir)?;
+
+        if args.keep_poetry_section {
+            file_tracker.track_file(&pyproject_path)?;
+            if pyproject::copy_poetry_section(project_dir)? {
+                info!("Kept the original [tool.poetry] as [tool.poetry-legacy]");
+                summary
+                    .tool_sections_copied
+                    .push(pyproject::POETRY_LEGACY_SECTION.to_string());
+            }
+        }
+    }
+
+    if args.merge_extras_into_main {
+        info!("Merging optional dependencies into main dependencies");
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::merge_extras_into_main(project_dir)?;
+    }
+
+    if args.prune_empty_groups {
+        file_tracker.track_file(&pyproject_path)?;
+        let pruned = pyproject::prune_empty_groups(project_dir)?;
+        if !pruned.is_empty() {
+            info!("Removed empty dependency groups: {}", pruned.join(", "));
+        }
+    }
+
+    if args.preserve_dep_comments {
+        let comments = pyproject::extract_poetry_dependency_comments(project_dir)?;
+        if !comments.is_empty() {
+            file_tracker.track_file(&pyproject_path)?;
+            let attached = pyproject::reattach_dependency_comments(project_dir, &comments)?;
+            info!("Preserved {} dependency comments", attached);
+        }
+    }
+
+    if args.generate_gitignore {
+        info!("Updating .gitignore with UV entries");
+        file_tracker.track_file(&project_dir.join(".gitignore"))?;
+        crate::utils::gitignore::update_gitignore(project_dir)?;
+    }
+
+    finalize_pyproject(project_dir, file_tracker, args)
+}
+
+/// Migrates project metadata such as the description, URLs, version,
+/// requires-python and readme
+fn migrate_metadata(
+    project_dir: &Path,
+    file_tracker: &mut FileTrackerGuard,
+    args: &Args,
 ) -> Result<(), String> {
     let pyproject_path = project_dir.join("pyproject.toml");
 
@@ -480,24 +1567,55 @@ fn perform_common_migrations(
         pyproject::update_project_version(project_dir, &version)?;
     }
 
-    let mut extra_urls = Vec::new();
-    if import_global_pip_conf {
-        extra_urls.extend(parse_pip_conf()?);
+    if let Some(requires_python) = pyproject::extract_requires_python(project_dir)? {
+        info!("Migrating requires-python: {}", requires_python);
+        file_tracker.track_file(&pyproject_path)?;
+        pyproject::update_requires_python(project_dir, &requires_python)?;
     }
-    extra_urls.extend(additional_index_urls.iter().cloned());
 
-    if !extra_urls.is_empty() {
-        file_tracker.track_file(&pyproject_path)?;
-        update_pyproject_toml(project_dir, &extra_urls)?;
+    if args.copy_readme {
+        match pyproject::extract_readme(project_dir)? {
+            Some(readme) if project_dir.join(&readme).exists() => {
+                info!("Setting project readme to {}", readme);
+                file_tracker.track_file(&pyproject_path)?;
+                pyproject::update_readme(project_dir, &readme)?;
+            }
+            Some(readme) => warn!(
+                "README '{}' referenced by the project does not exist, not setting [project].readme",
+                readme
+            ),
+            None => info!("No README found to reference from [project].readme"),
+        }
     }
 
-    info!("Migrating Tool sections");
-    file_tracker.track_file(&pyproject_path)?;
-    pyproject::append_tool_sections(project_dir)?;
+    Ok(())
+}
 
-    info!("Reordering pyproject.toml sections");
-    file_tracker.track_file(&pyproject_path)?;
-    crate::utils::toml::reorder_toml_sections(project_dir)?;
+/// Applies the final formatting passes to the migrated pyproject.toml.
+///
+/// Sections are reordered unless `--no-reorder` is set, and comments are
+/// stripped when `--strip-comments` is set. Comments generated by the migration,
+/// such as the Conda channels and the `--stamp` line, are written after this runs.
+pub fn finalize_pyproject(
+    project_dir: &Path,
+    file_tracker: &mut FileTrackerGuard,
+    args: &Args,
+) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+
+    if args.no_reorder {
+        info!("Skipping pyproject.toml section reordering");
+    } else {
+        info!("Reordering pyproject.toml sections");
+        file_tracker.track_file(&pyproject_path)?;
+        crate::utils::toml::reorder_toml_sections(project_dir)?;
+    }
+
+    if args.strip_comments {
+        info!("Stripping comments from pyproject.toml");
+        file_tracker.track_file(&pyproject_path)?;
+        crate::utils::toml::strip_toml_comments(project_dir)?;
+    }
 
     Ok(())
 }
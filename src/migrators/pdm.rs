@@ -0,0 +1,152 @@
+use crate::migrators::pep621::Pep621MigrationSource;
+use crate::migrators::requirements::RequirementsMigrationSource;
+use crate::migrators::{Dependency, DependencyType, MigrationSource};
+use crate::models::{SkippedItem, SkippedKind};
+use crate::utils::toml::read_toml;
+use log::{debug, info};
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+pub struct PdmMigrationSource;
+
+impl MigrationSource for PdmMigrationSource {
+    fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String> {
+        info!("Extracting dependencies from PDM project");
+        let doc = read_toml(&project_dir.join("pyproject.toml"))?;
+        let excludes = Self::resolution_excludes(&doc);
+        let mut dependencies = Vec::new();
+
+        if let Some(deps) = doc
+            .get("project")
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array())
+        {
+            debug!("Processing main dependencies from project section");
+            for dep_str in deps.iter().filter_map(|d| d.as_str()) {
+                if let Some(dep) = self.parse_dependency(dep_str, DependencyType::Main) {
+                    dependencies.push(dep);
+                }
+            }
+        }
+
+        if let Some(extras) = doc
+            .get("project")
+            .and_then(|p| p.get("optional-dependencies"))
+            .and_then(|o| o.as_table_like())
+        {
+            debug!("Processing optional dependencies from project section");
+            for (extra, entries) in extras.iter() {
+                let dep_type = DependencyType::Optional(extra.to_string());
+                for dep_str in entries
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|d| d.as_str())
+                {
+                    if let Some(dep) = self.parse_dependency(dep_str, dep_type.clone()) {
+                        dependencies.push(dep);
+                    }
+                }
+            }
+        }
+
+        if let Some(groups) = doc
+            .get("tool")
+            .and_then(|t| t.get("pdm"))
+            .and_then(|p| p.get("dev-dependencies"))
+            .and_then(|d| d.as_table_like())
+        {
+            debug!("Processing PDM dev-dependency groups");
+            for (group_name, group) in groups.iter() {
+                let dep_type = match group_name {
+                    "dev" => DependencyType::Dev,
+                    _ => DependencyType::Group(group_name.to_string()),
+                };
+
+                let Some(entries) = group.as_array() else {
+                    debug!("Skipping malformed PDM group: {}", group_name);
+                    continue;
+                };
+                for dep_str in entries.iter().filter_map(|d| d.as_str()) {
+                    if let Some(dep) = self.parse_dependency(dep_str, dep_type.clone()) {
+                        dependencies.push(dep);
+                    }
+                }
+            }
+        }
+
+        dependencies.retain(|dep| {
+            let excluded = excludes.contains(&dep.name.to_lowercase());
+            if excluded {
+                info!("Skipping '{}' excluded by [tool.pdm.resolution]", dep.name);
+            }
+            !excluded
+        });
+
+        info!("Extracted {} dependencies", dependencies.len());
+        Ok(dependencies)
+    }
+
+    fn skipped_items(&self, project_dir: &Path) -> Result<Vec<SkippedItem>, String> {
+        let doc = read_toml(&project_dir.join("pyproject.toml"))?;
+        Ok(Self::resolution_excludes(&doc)
+            .into_iter()
+            .map(|name| {
+                SkippedItem::new(
+                    SkippedKind::Dependency,
+                    name,
+                    "excluded by [tool.pdm.resolution]",
+                )
+            })
+            .collect())
+    }
+}
+
+impl PdmMigrationSource {
+    /// Checks for a `[tool.pdm]` table in pyproject.toml
+    pub fn detect_project_type(project_dir: &Path) -> bool {
+        read_toml(&project_dir.join("pyproject.toml"))
+            .map(|doc| doc.get("tool").and_then(|t| t.get("pdm")).is_some())
+            .unwrap_or(false)
+    }
+
+    /// Extracts `requires-python` from the backed up pyproject.toml, falling back to
+    /// pyproject.toml when it has not been renamed yet
+    pub fn extract_python_version(project_dir: &Path) -> Result<Option<String>, String> {
+        Pep621MigrationSource::extract_python_version(project_dir)
+    }
+
+    /// Package names listed under `[tool.pdm.resolution].excludes`, lowercased
+    fn resolution_excludes(doc: &DocumentMut) -> Vec<String> {
+        doc.get("tool")
+            .and_then(|t| t.get("pdm"))
+            .and_then(|p| p.get("resolution"))
+            .and_then(|r| r.get("excludes"))
+            .and_then(|e| e.as_array())
+            .map(|excludes| {
+                excludes
+                    .iter()
+                    .filter_map(|e| e.as_str())
+                    .map(|e| e.to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn parse_dependency(&self, dep_str: &str, dep_type: DependencyType) -> Option<Dependency> {
+        // Editable local installs such as `-e file:///${PROJECT_ROOT}/lib` have no uv add equivalent
+        if dep_str.trim_start().starts_with("-e") {
+            info!("Skipping editable PDM dependency: {}", dep_str);
+            return None;
+        }
+
+        match RequirementsMigrationSource.parse_requirement(dep_str) {
+            Ok(Some(dep)) => Some(Dependency { dep_type, ..dep }),
+            Ok(None) => None,
+            Err(e) => {
+                debug!("Skipping dependency '{}': {}", dep_str, e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use crate::migrators::requirements::RequirementsMigrationSource;
+use crate::migrators::{Dependency, DependencyType, MigrationSource};
+use crate::utils::toml::read_toml;
+use log::{debug, info, warn};
+use std::path::Path;
+use toml_edit::{DocumentMut, Item};
+
+/// A pyproject.toml that declares its dependencies with standard PEP 621 and
+/// PEP 735 tables only, independent of the build backend
+pub struct Pep621MigrationSource;
+
+impl MigrationSource for Pep621MigrationSource {
+    fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String> {
+        info!("Extracting dependencies from PEP 621 project");
+        let doc = read_toml(&project_dir.join("pyproject.toml"))?;
+        let mut dependencies = Vec::new();
+
+        let project = doc.get("project");
+        if let Some(deps) = project
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array())
+        {
+            debug!("Processing main dependencies from project section");
+            for dep_str in deps.iter().filter_map(|d| d.as_str()) {
+                push_dependency(&mut dependencies, dep_str, DependencyType::Main);
+            }
+        }
+
+        if let Some(extras) = project
+            .and_then(|p| p.get("optional-dependencies"))
+            .and_then(|o| o.as_table_like())
+        {
+            debug!("Processing optional dependencies from project section");
+            for (extra, deps) in extras.iter() {
+                let dep_type = DependencyType::Optional(extra.to_string());
+                for dep_str in deps
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|d| d.as_str())
+                {
+                    push_dependency(&mut dependencies, dep_str, dep_type.clone());
+                }
+            }
+        }
+
+        if let Some(groups) = doc.get("dependency-groups").and_then(|g| g.as_table_like()) {
+            debug!("Processing PEP 735 dependency groups");
+            for (group_name, _) in groups.iter() {
+                let dep_type = match group_name {
+                    "dev" => DependencyType::Dev,
+                    _ => DependencyType::Group(group_name.to_string()),
+                };
+                for dep_str in dependency_group_entries(&doc, group_name, &mut Vec::new()) {
+                    push_dependency(&mut dependencies, &dep_str, dep_type.clone());
+                }
+            }
+        }
+
+        info!("Extracted {} dependencies", dependencies.len());
+        Ok(dependencies)
+    }
+}
+
+impl Pep621MigrationSource {
+    /// Checks for a `[project]` table that declares dependencies, a
+    /// `[dependency-groups]` table, or a Hatch project
+    pub fn detect_project_type(project_dir: &Path) -> bool {
+        read_toml(&project_dir.join("pyproject.toml"))
+            .map(|doc| {
+                let project = doc.get("project");
+                project.and_then(|p| p.get("dependencies")).is_some()
+                    || project
+                        .and_then(|p| p.get("optional-dependencies"))
+                        .is_some()
+                    || doc.get("dependency-groups").is_some()
+                    || (project.is_some() && uses_hatch(&doc))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Checks for a `[tool.hatch]` table or the hatchling build backend
+    pub fn is_hatch_project(project_dir: &Path) -> bool {
+        read_toml(&project_dir.join("pyproject.toml"))
+            .map(|doc| uses_hatch(&doc))
+            .unwrap_or(false)
+    }
+
+    /// Extracts `requires-python` from the backed up pyproject.toml, falling back to
+    /// pyproject.toml when it has not been renamed yet
+    pub fn extract_python_version(project_dir: &Path) -> Result<Option<String>, String> {
+        let mut old_pyproject_path = project_dir.join("old.pyproject.toml");
+        if !old_pyproject_path.exists() {
+            old_pyproject_path = project_dir.join("pyproject.toml");
+        }
+        if !old_pyproject_path.exists() {
+            return Ok(None);
+        }
+
+        let doc = read_toml(&old_pyproject_path)?;
+        Ok(doc
+            .get("project")
+            .and_then(|p| p.get("requires-python"))
+            .and_then(|r| r.as_str())
+            .map(|version| {
+                version
+                    .trim_start_matches(['>', '=', '~', '^'])
+                    .split(',')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string()
+            }))
+    }
+}
+
+fn uses_hatch(doc: &DocumentMut) -> bool {
+    doc.get("tool").and_then(|t| t.get("hatch")).is_some()
+        || doc
+            .get("build-system")
+            .and_then(|b| b.get("build-backend"))
+            .and_then(|b| b.as_str())
+            .is_some_and(|backend| backend.starts_with("hatchling"))
+}
+
+fn push_dependency(dependencies: &mut Vec<Dependency>, dep_str: &str, dep_type: DependencyType) {
+    match RequirementsMigrationSource.parse_requirement(dep_str) {
+        Ok(Some(dep)) => dependencies.push(Dependency { dep_type, ..dep }),
+        Ok(None) => {}
+        Err(e) => debug!("Skipping dependency '{}': {}", dep_str, e),
+    }
+}
+
+/// Returns the requirement strings of a PEP 735 group, following
+/// `{ include-group = "..." }` entries
+fn dependency_group_entries(
+    doc: &DocumentMut,
+    group_name: &str,
+    visiting: &mut Vec<String>,
+) -> Vec<String> {
+    if visiting.iter().any(|g| g == group_name) {
+        warn!("Dependency group '{}' includes itself", group_name);
+        return Vec::new();
+    }
+    let Some(entries) = doc
+        .get("dependency-groups")
+        .and_then(|g| g.get(group_name))
+        .and_then(Item::as_array)
+    else {
+        warn!("Dependency group '{}' not found", group_name);
+        return Vec::new();
+    };
+
+    visiting.push(group_name.to_string());
+    let mut requirements = Vec::new();
+    for entry in entries.iter() {
+        if let Some(dep_str) = entry.as_str() {
+            requirements.push(dep_str.to_string());
+        } else if let Some(included) = entry
+            .as_inline_table()
+            .and_then(|t| t.get("include-group"))
+            .and_then(|g| g.as_str())
+        {
+            requirements.extend(dependency_group_entries(doc, included, visiting));
+        }
+    }
+    visiting.pop();
+    requirements
+}
@@ -1,7 +1,10 @@
 use crate::migrators::{Dependency, DependencyType, MigrationSource};
-use log::{debug, info};
+use crate::utils::pyproject::UvIndex;
+use crate::utils::toml::read_toml;
+use log::{debug, info, warn};
 use serde_json::Value;
-use std::{fs, path::Path};
+use std::fs;
+use std::path::{Path, PathBuf};
 
 pub struct PipenvMigrationSource;
 
@@ -10,6 +13,48 @@ impl PipenvMigrationSource {
         project_dir.join("Pipfile.lock").exists()
     }
 
+    /// Extracts the private package indexes declared as `[[source]]` in the Pipfile.
+    ///
+    /// The default `pypi` source is skipped since uv uses PyPI already.
+    pub fn extract_sources(project_dir: &Path) -> Result<Vec<UvIndex>, String> {
+        let pipfile_path = project_dir.join("Pipfile");
+        if !pipfile_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let doc = read_toml(&pipfile_path)?;
+        let Some(sources) = doc.get("source").and_then(|s| s.as_array_of_tables()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut indexes = Vec::new();
+        for source in sources.iter() {
+            let Some(url) = source.get("url").and_then(|u| u.as_str()) else {
+                debug!("Skipping Pipfile source without a url");
+                continue;
+            };
+            let name = source
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or(url)
+                .to_string();
+            if name == "pypi" || url.trim_end_matches('/') == "https://pypi.org/simple" {
+                debug!("Skipping default PyPI source");
+                continue;
+            }
+
+            if source.get("verify_ssl").and_then(|v| v.as_bool()) == Some(false) {
+                warn!(
+                    "Pipfile source '{}' disables SSL verification; uv needs --allow-insecure-host to use it",
+                    name
+                );
+            }
+            indexes.push(UvIndex::new(name, url));
+        }
+
+        Ok(indexes)
+    }
+
     fn parse_dependency(
         &self,
         name: &str,
@@ -21,9 +66,23 @@ impl PipenvMigrationSource {
             return Ok(None);
         }
 
-        let dep_obj = value
-            .as_object()
-            .ok_or_else(|| format!("Invalid dependency format for '{}': expected object", name))?;
+        // Hand-edited lock files sometimes use the Pipfile shorthand `"name": "==1.0"`
+        if let Some(version_str) = value.as_str() {
+            debug!("Treating string lock entry of '{}' as its version", name);
+            let version = Some(self.clean_version(version_str)).filter(|v| v != "*");
+            return Dependency::new(name)
+                .version(version)
+                .dep_type(dep_type)
+                .build()
+                .map(Some);
+        }
+
+        let dep_obj = value.as_object().ok_or_else(|| {
+            format!(
+                "Invalid dependency format for '{}': expected object or version string",
+                name
+            )
+        })?;
 
         // Handle git dependencies
         if dep_obj.contains_key("git") {
@@ -44,12 +103,12 @@ impl PipenvMigrationSource {
         // Handle platform-specific dependencies
         let markers = self.extract_markers(dep_obj)?;
 
-        Ok(Some(Dependency {
-            name: name.to_string(),
-            version,
-            dep_type,
-            environment_markers: markers,
-        }))
+        Dependency::new(name)
+            .version(version)
+            .dep_type(dep_type)
+            .markers(markers)
+            .build()
+            .map(Some)
     }
 
     fn parse_git_dependency(
@@ -74,12 +133,12 @@ impl PipenvMigrationSource {
 
         let markers = self.extract_markers(dep_obj)?;
 
-        Ok(Some(Dependency {
-            name: name.to_string(),
-            version,
-            dep_type,
-            environment_markers: markers,
-        }))
+        Dependency::new(name)
+            .version(version)
+            .dep_type(dep_type)
+            .markers(markers)
+            .build()
+            .map(Some)
     }
 
     fn extract_markers(
@@ -182,6 +241,14 @@ impl MigrationSource for PipenvMigrationSource {
 
         Ok(dependencies)
     }
+
+    fn source_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        Ok(vec![
+            project_dir.join("pyproject.toml"),
+            project_dir.join("Pipfile"),
+            project_dir.join("Pipfile.lock"),
+        ])
+    }
 }
 
 #[cfg(test)]
@@ -341,4 +408,63 @@ mod tests {
         assert_eq!(dependencies.len(), 1);
         assert_eq!(dependencies[0].name, "requests");
     }
+
+    #[test]
+    fn test_string_valued_lock_entries() {
+        let content = r#"{
+            "default": {
+                "requests": "==2.31.0",
+                "flask": "*"
+            },
+            "develop": {
+                "pytest": ">=7.0"
+            }
+        }"#;
+
+        let (_temp_dir, project_dir) = create_test_pipfile_lock(content);
+        let dependencies = PipenvMigrationSource
+            .extract_dependencies(&project_dir)
+            .unwrap();
+
+        let requests = dependencies.iter().find(|d| d.name == "requests").unwrap();
+        assert_eq!(requests.version.as_deref(), Some("==2.31.0"));
+        assert_eq!(requests.dep_type, DependencyType::Main);
+
+        let flask = dependencies.iter().find(|d| d.name == "flask").unwrap();
+        assert_eq!(flask.version, None);
+
+        let pytest = dependencies.iter().find(|d| d.name == "pytest").unwrap();
+        assert_eq!(pytest.version.as_deref(), Some(">=7.0"));
+        assert_eq!(pytest.dep_type, DependencyType::Dev);
+    }
+
+    #[test]
+    fn test_extract_pipfile_sources() {
+        let (_temp_dir, project_dir) = create_test_pipfile_lock(r#"{"default": {}}"#);
+        let pipfile = r#"
+[[source]]
+url = "https://pypi.org/simple"
+verify_ssl = true
+name = "pypi"
+
+[[source]]
+url = "https://pypi.internal.example.com/simple"
+verify_ssl = true
+name = "internal"
+
+[packages]
+requests = "*"
+"#;
+        fs::write(project_dir.join("Pipfile"), pipfile).unwrap();
+
+        let sources = PipenvMigrationSource::extract_sources(&project_dir).unwrap();
+
+        assert_eq!(
+            sources,
+            vec![UvIndex::new(
+                "internal",
+                "https://pypi.internal.example.com/simple"
+            )]
+        );
+    }
 }
@@ -1,7 +1,10 @@
 use super::{Dependency, DependencyType, MigrationSource};
 use crate::migrators::detect::PoetryProjectType;
+use crate::models::{compose_markers, SkippedItem, SkippedKind};
+use crate::utils::pyproject::GitDependency;
 use crate::utils::toml::read_toml;
-use log::{debug, info};
+use log::{debug, info, warn};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use toml_edit::{DocumentMut, Item, Value};
@@ -9,42 +12,58 @@ use toml_edit::{DocumentMut, Item, Value};
 pub struct PoetryMigrationSource;
 
 impl PoetryMigrationSource {
+    /// Classifies the project as a package, an application, or a library that also
+    /// ships console scripts
     pub fn detect_project_type(project_dir: &Path) -> Result<PoetryProjectType, String> {
         let pyproject_path = project_dir.join("pyproject.toml");
         let doc = read_toml(&pyproject_path)?;
+        let has_scripts = ["project", "tool.poetry"].iter().any(|path| {
+            path.split('.')
+                .try_fold(doc.as_item(), |item, key| item.get(key))
+                .and_then(|section| section.get("scripts"))
+                .and_then(|scripts| scripts.as_table_like())
+                .is_some_and(|scripts| !scripts.is_empty())
+        });
+        let library_type = if has_scripts {
+            PoetryProjectType::Mixed
+        } else {
+            PoetryProjectType::Package
+        };
 
         // First, check the project section (Poetry 2.0 style)
         if let Some(project) = doc.get("project") {
             // If project section has dependencies, it's likely a Poetry 2.0 package
             if project.get("dependencies").is_some() {
-                return Ok(PoetryProjectType::Package);
+                return Ok(library_type);
             }
         }
 
         // Traditional Poetry style detection
         if let Some(tool) = doc.get("tool") {
             if let Some(poetry) = tool.get("poetry") {
-                let is_package = poetry
+                let packages = poetry
                     .get("packages")
                     .and_then(|packages| packages.as_array())
-                    .is_some_and(|packages| {
-                        packages.iter().any(|pkg| {
-                            pkg.as_inline_table()
-                                .and_then(|t| t.get("include"))
-                                .and_then(|i| i.as_str())
-                                == Some("src")
-                        })
-                    });
-
-                debug!(
-                    "Poetry project type detected: {}",
-                    if is_package { "package" } else { "application" }
-                );
-                return Ok(if is_package {
+                    .filter(|packages| !packages.is_empty());
+                let is_package = packages.is_some_and(|packages| {
+                    packages.iter().any(|pkg| {
+                        pkg.as_inline_table()
+                            .and_then(|t| t.get("include"))
+                            .and_then(|i| i.as_str())
+                            == Some("src")
+                    })
+                });
+
+                // A library declaring its packages that also has scripts needs both
+                let project_type = if has_scripts && packages.is_some() {
+                    PoetryProjectType::Mixed
+                } else if is_package {
                     PoetryProjectType::Package
                 } else {
                     PoetryProjectType::Application
-                });
+                };
+                debug!("Poetry project type detected: {:?}", project_type);
+                return Ok(project_type);
             }
         }
 
@@ -52,8 +71,13 @@ impl PoetryMigrationSource {
         Ok(PoetryProjectType::Application)
     }
 
+    /// Extracts the Python version from the backed up pyproject.toml, falling back to
+    /// pyproject.toml when it has not been renamed yet (e.g. during a dry run).
     pub fn extract_python_version(project_dir: &Path) -> Result<Option<String>, String> {
-        let old_pyproject_path = project_dir.join("old.pyproject.toml");
+        let mut old_pyproject_path = project_dir.join("old.pyproject.toml");
+        if !old_pyproject_path.exists() {
+            old_pyproject_path = project_dir.join("pyproject.toml");
+        }
         if !old_pyproject_path.exists() {
             return Ok(None);
         }
@@ -147,6 +171,44 @@ impl PoetryMigrationSource {
         }
     }
 
+    /// Converts a dependency entry into one or more dependencies.
+    ///
+    /// Poetry's multiple-constraints form (a list of inline tables, or an array of
+    /// tables) yields one dependency per constraint, each gated by its own markers.
+    fn format_dependencies(
+        &self,
+        name: &str,
+        value: &Item,
+        dep_type: DependencyType,
+    ) -> Vec<Dependency> {
+        match value {
+            Item::Value(Value::Array(constraints)) => constraints
+                .iter()
+                .filter_map(|constraint| match constraint {
+                    Value::InlineTable(t) => self.format_dependency(
+                        name,
+                        &Item::Value(Value::InlineTable(t.clone())),
+                        dep_type.clone(),
+                    ),
+                    _ => {
+                        debug!("Skipping unsupported constraint for '{}'", name);
+                        None
+                    }
+                })
+                .collect(),
+            Item::ArrayOfTables(constraints) => constraints
+                .iter()
+                .filter_map(|t| {
+                    self.format_dependency(name, &Item::Table(t.clone()), dep_type.clone())
+                })
+                .collect(),
+            _ => self
+                .format_dependency(name, value, dep_type)
+                .into_iter()
+                .collect(),
+        }
+    }
+
     fn format_dependency(
         &self,
         name: &str,
@@ -158,7 +220,20 @@ impl PoetryMigrationSource {
             return None;
         }
 
+        if value
+            .as_table_like()
+            .is_some_and(|t| t.contains_key("path"))
+        {
+            debug!("Path dependency '{}' is added after uv init", name);
+            return None;
+        }
+
+        let git_dependency = value
+            .as_table_like()
+            .and_then(|t| GitDependency::from_poetry_table(name, t));
+
         let version = match value {
+            _ if git_dependency.is_some() => git_dependency.as_ref().map(GitDependency::url),
             Item::Value(Value::String(v)) => {
                 let v = v.value().trim();
                 if v == "*" {
@@ -192,17 +267,55 @@ impl PoetryMigrationSource {
             _ => None,
         };
 
-        Some(Dependency {
-            name: name.to_string(),
-            version,
-            dep_type,
-            environment_markers: None,
-        })
+        let explicit_markers = match value {
+            Item::Value(Value::InlineTable(t)) => t.get("markers").and_then(|m| m.as_str()),
+            Item::Table(t) => t.get("markers").and_then(|m| m.as_str()),
+            _ => None,
+        };
+        let python_marker = match value {
+            Item::Value(Value::InlineTable(t)) => t.get("python").and_then(|p| p.as_str()),
+            Item::Table(t) => t.get("python").and_then(|p| p.as_str()),
+            _ => None,
+        }
+        .and_then(python_constraint_to_marker);
+        let markers = compose_markers(python_marker.as_deref(), explicit_markers);
+
+        let extras = value
+            .as_table_like()
+            .and_then(|t| t.get("extras"))
+            .and_then(|extras| {
+                if let Some(extra) = extras.as_str() {
+                    // Some hand-written files use `extras = "cli"` for a single extra
+                    debug!("Treating string extras '{}' of '{}' as a list", extra, name);
+                    return Some(vec![extra.to_string()]);
+                }
+                extras.as_array().map(|extras| {
+                    extras
+                        .iter()
+                        .filter_map(|e| e.as_str().map(|e| e.to_string()))
+                        .collect::<Vec<_>>()
+                })
+            });
+
+        Dependency::new(name)
+            .version(version)
+            .dep_type(dep_type)
+            .extras(extras)
+            .markers(markers)
+            .build()
+            .map_err(|e| debug!("Skipping dependency '{}': {}", name, e))
+            .ok()
     }
-}
 
-impl MigrationSource for PoetryMigrationSource {
-    fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String> {
+    /// Extracts dependencies, choosing which section wins when a package is declared
+    /// in both `[project].dependencies` and `[tool.poetry.dependencies]`.
+    ///
+    /// The `[project]` entry is kept unless `prefer_poetry_table` is set.
+    pub fn extract_dependencies_with_precedence(
+        &self,
+        project_dir: &Path,
+        prefer_poetry_table: bool,
+    ) -> Result<Vec<Dependency>, String> {
         info!("Extracting dependencies from Poetry project");
         let pyproject_path = project_dir.join("pyproject.toml");
 
@@ -221,29 +334,25 @@ impl MigrationSource for PoetryMigrationSource {
             )
         })?;
 
-        let mut dependencies = Vec::new();
-
         // First, check the project section (Poetry 2.0 style)
-        if let Some(project) = doc.get("project") {
-            // Extract dependencies from project section
-            if let Some(proj_deps) = project.get("dependencies").and_then(|d| d.as_array()) {
-                debug!("Processing main dependencies from project section");
-                for dep_value in proj_deps.iter() {
-                    if let Some(dep_str) = dep_value.as_str() {
-                        // Split the dependency string into name and version
-                        let (name, version) = self.parse_poetry_v2_dep(dep_str);
-
-                        let dep = Dependency {
-                            name,
-                            version,
-                            dep_type: DependencyType::Main,
-                            environment_markers: None,
-                        };
+        let mut dependencies = self.project_dependencies(&doc);
+        if prefer_poetry_table {
+            let poetry_names = poetry_main_dependency_names(&doc);
+            dependencies.retain(|dep| !poetry_names.contains(&dep.name));
+        }
 
-                        dependencies.push(dep);
-                    }
+        for (name, project_version, poetry_version) in self.version_conflicts(&doc) {
+            warn!(
+                "Conflicting versions for '{}': [project] has '{}' but [tool.poetry.dependencies] has '{}'; using '{}'",
+                name,
+                project_version,
+                poetry_version,
+                if prefer_poetry_table {
+                    &poetry_version
+                } else {
+                    &project_version
                 }
-            }
+            );
         }
 
         // Then, check the tool.poetry section (traditional Poetry style)
@@ -252,12 +361,32 @@ impl MigrationSource for PoetryMigrationSource {
                 // Handle main dependencies
                 if let Some(deps) = poetry.get("dependencies").and_then(|d| d.as_table()) {
                     debug!("Processing main dependencies from tool.poetry section");
+                    let project_dep_count = dependencies.len();
+                    let extras = extras_by_dependency(poetry);
                     for (name, value) in deps.iter() {
-                        if let Some(dep) = self.format_dependency(name, value, DependencyType::Main)
-                        {
+                        for dep in self.format_dependencies(name, value, DependencyType::Main) {
+                            // Optional dependencies are only installed through their extras
+                            if is_optional(value) {
+                                let Some(dep_extras) = extras.get(&name.to_lowercase()) else {
+                                    info!(
+                                        "Skipping optional dependency '{}' that is not part of any extra",
+                                        name
+                                    );
+                                    continue;
+                                };
+                                for extra in dep_extras {
+                                    debug!("Added optional dependency {} to extra {}", name, extra);
+                                    dependencies.push(Dependency {
+                                        dep_type: DependencyType::Optional(extra.clone()),
+                                        ..dep.clone()
+                                    });
+                                }
+                                continue;
+                            }
+
                             debug!("Added main dependency: {}", name);
-                            // Avoid duplicates
-                            if !dependencies
+                            // Avoid duplicating dependencies from the project section
+                            if !dependencies[..project_dep_count]
                                 .iter()
                                 .any(|existing| existing.name == dep.name)
                             {
@@ -275,7 +404,11 @@ impl MigrationSource for PoetryMigrationSource {
                             "dev" => DependencyType::Dev,
                             _ => DependencyType::Group(group_name.to_string()),
                         };
-                        debug!("Processing group: {}", group_name);
+                        let optional = group
+                            .get("optional")
+                            .and_then(|o| o.as_bool())
+                            .unwrap_or(false);
+                        debug!("Processing group: {} (optional: {})", group_name, optional);
 
                         if let Some(deps) = group
                             .as_table()
@@ -283,11 +416,12 @@ impl MigrationSource for PoetryMigrationSource {
                             .and_then(|d| d.as_table())
                         {
                             for (name, value) in deps.iter() {
-                                if let Some(dep) =
-                                    self.format_dependency(name, value, dep_type.clone())
-                                {
+                                for dep in self.format_dependencies(name, value, dep_type.clone()) {
                                     debug!("Added {} dependency: {}", group_name, name);
-                                    dependencies.push(dep);
+                                    dependencies.push(Dependency {
+                                        optional_group: optional,
+                                        ..dep
+                                    });
                                 }
                             }
                         }
@@ -299,4 +433,247 @@ impl MigrationSource for PoetryMigrationSource {
         info!("Extracted {} dependencies", dependencies.len());
         Ok(dependencies)
     }
+
+    /// Main dependencies from the PEP 621 `[project]` section (Poetry 2.0 style)
+    fn project_dependencies(&self, doc: &DocumentMut) -> Vec<Dependency> {
+        let mut dependencies = Vec::new();
+        if let Some(proj_deps) = doc
+            .get("project")
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array())
+        {
+            debug!("Processing main dependencies from project section");
+            for dep_str in proj_deps.iter().filter_map(|d| d.as_str()) {
+                // Split the dependency string into name and version
+                let (name, version) = self.parse_poetry_v2_dep(dep_str);
+
+                match Dependency::new(name).version(version).build() {
+                    Ok(dep) => dependencies.push(dep),
+                    Err(e) => debug!("Skipping dependency '{}': {}", dep_str, e),
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Packages declared in both `[project].dependencies` and `[tool.poetry.dependencies]`
+    /// with different version specifiers, as `(name, project_version, poetry_version)`.
+    fn version_conflicts(&self, doc: &DocumentMut) -> Vec<(String, String, String)> {
+        let project_deps = self.project_dependencies(doc);
+        let Some(deps) = doc
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_table())
+        else {
+            return Vec::new();
+        };
+
+        let normalize = |v: &str| v.split_whitespace().collect::<String>();
+        let mut conflicts = Vec::new();
+        for (name, value) in deps.iter() {
+            for dep in self.format_dependencies(name, value, DependencyType::Main) {
+                let Some(existing) = project_deps.iter().find(|e| e.name == dep.name) else {
+                    continue;
+                };
+                if let (Some(project_version), Some(poetry_version)) =
+                    (&existing.version, &dep.version)
+                {
+                    if normalize(project_version) != normalize(poetry_version) {
+                        conflicts.push((
+                            dep.name.clone(),
+                            project_version.clone(),
+                            poetry_version.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// Names of the non-optional `[tool.poetry.dependencies]` entries
+fn poetry_main_dependency_names(doc: &DocumentMut) -> HashSet<String> {
+    doc.get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_table())
+        .map(|deps| {
+            deps.iter()
+                .filter(|(name, value)| *name != "python" && !is_optional(value))
+                .map(|(name, _)| name.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a Poetry dependency entry is marked `optional = true`
+fn is_optional(value: &Item) -> bool {
+    let optional = |item: Option<&Item>| item.and_then(|o| o.as_bool()).unwrap_or(false);
+    match value {
+        Item::Value(Value::Array(constraints)) => constraints.iter().any(|constraint| {
+            constraint
+                .as_inline_table()
+                .and_then(|t| t.get("optional"))
+                .and_then(|o| o.as_bool())
+                .unwrap_or(false)
+        }),
+        Item::ArrayOfTables(constraints) => constraints.iter().any(|t| optional(t.get("optional"))),
+        _ => optional(value.as_table_like().and_then(|t| t.get("optional"))),
+    }
+}
+
+/// Maps each dependency name (lowercased) to the `[tool.poetry.extras]` that include it
+fn extras_by_dependency(poetry: &Item) -> HashMap<String, Vec<String>> {
+    let mut extras: HashMap<String, Vec<String>> = HashMap::new();
+    if let Some(table) = poetry.get("extras").and_then(|e| e.as_table_like()) {
+        for (extra, members) in table.iter() {
+            for member in members
+                .as_array()
+                .into_iter()
+                .flat_map(|a| a.iter())
+                .filter_map(|m| m.as_str())
+            {
+                let entry = extras.entry(member.to_lowercase()).or_default();
+                if !entry.iter().any(|e| e == extra) {
+                    entry.push(extra.to_string());
+                }
+            }
+        }
+    }
+    extras
+}
+
+/// Translates a Poetry `python` constraint such as `^3.8` or `>=3.8,<3.11` into a
+/// `python_version` marker.
+///
+/// Alternatives separated by `||` are joined with `or`. Versions with a patch
+/// component, e.g. `>=3.8.1`, compare `python_full_version`, since `python_version`
+/// only holds the major and minor version.
+pub(crate) fn python_constraint_to_marker(constraint: &str) -> Option<String> {
+    let alternatives: Vec<String> = constraint
+        .split("||")
+        .filter_map(python_alternative_to_marker)
+        .collect();
+
+    match alternatives.len() {
+        0 => None,
+        1 => alternatives.into_iter().next(),
+        _ => Some(
+            alternatives
+                .iter()
+                .map(|marker| {
+                    if marker.contains(" and ") {
+                        format!("({})", marker)
+                    } else {
+                        marker.clone()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" or "),
+        ),
+    }
+}
+
+/// Translates a single `||` alternative of a Poetry `python` constraint
+fn python_alternative_to_marker(constraint: &str) -> Option<String> {
+    let clauses: Vec<String> = constraint
+        .split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty() && *c != "*")
+        .map(|clause| {
+            let (op, version) = if let Some(v) = clause.strip_prefix('^') {
+                (">=", v)
+            } else if let Some(v) = clause.strip_prefix("~=") {
+                (">=", v)
+            } else if let Some(v) = clause.strip_prefix('~') {
+                (">=", v)
+            } else if let Some(v) = clause.strip_prefix(">=") {
+                (">=", v)
+            } else if let Some(v) = clause.strip_prefix("<=") {
+                ("<=", v)
+            } else if let Some(v) = clause.strip_prefix("!=") {
+                ("!=", v)
+            } else if let Some(v) = clause.strip_prefix("==") {
+                ("==", v)
+            } else if let Some(v) = clause.strip_prefix('>') {
+                (">", v)
+            } else if let Some(v) = clause.strip_prefix('<') {
+                ("<", v)
+            } else {
+                ("==", clause)
+            };
+            let version = version.trim();
+            let variable = if version.split('.').count() >= 3 {
+                "python_full_version"
+            } else {
+                "python_version"
+            };
+            format!("{} {} '{}'", variable, op, version)
+        })
+        .collect();
+
+    (!clauses.is_empty()).then(|| clauses.join(" and "))
+}
+
+impl MigrationSource for PoetryMigrationSource {
+    fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String> {
+        self.extract_dependencies_with_precedence(project_dir, false)
+    }
+
+    fn skipped_items(&self, project_dir: &Path) -> Result<Vec<SkippedItem>, String> {
+        let doc = read_toml(&project_dir.join("pyproject.toml"))?;
+        let conflicts = self.version_conflicts(&doc).into_iter().map(
+            |(name, project_version, poetry_version)| {
+                SkippedItem::new(
+                    SkippedKind::Dependency,
+                    name,
+                    format!(
+                        "[tool.poetry.dependencies] version '{}' conflicts with [project] version '{}'",
+                        poetry_version, project_version
+                    ),
+                )
+            },
+        );
+        let gated_scripts = crate::utils::pyproject::poetry_scripts_with_extras(&doc)
+            .into_iter()
+            .map(|(name, extras)| {
+                SkippedItem::new(
+                    SkippedKind::Script,
+                    name,
+                    format!(
+                        "extras gating [{}] dropped; PEP 621 scripts are always installed",
+                        extras.join(", ")
+                    ),
+                )
+            });
+        Ok(conflicts.chain(gated_scripts).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(name: &str) -> Option<Dependency> {
+        PoetryMigrationSource.format_dependency(
+            name,
+            &toml_edit::value("^2.8"),
+            DependencyType::Main,
+        )
+    }
+
+    /// Only the interpreter entry `python` is skipped; packages whose names start
+    /// with or differ from it only in casing are real dependencies
+    #[test]
+    fn test_only_interpreter_python_is_skipped() {
+        assert!(format("python").is_none());
+
+        for name in ["python-dateutil", "pythonnet", "Python"] {
+            let dep = format(name).unwrap_or_else(|| panic!("{} was skipped", name));
+            assert_eq!(dep.name, name);
+            assert_eq!(dep.version.as_deref(), Some("^2.8"));
+        }
+    }
 }
@@ -1,6 +1,8 @@
-use super::dependency::DependencyType;
-use super::{Dependency, MigrationSource};
-use log::{debug, info};
+use super::{Dependency, DependencyType, MigrationSource};
+use crate::models::{SkippedItem, SkippedKind};
+use crate::utils::toml::read_toml;
+use log::{debug, info, warn};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -13,20 +15,90 @@ impl MigrationSource for RequirementsMigrationSource {
             return Err("No requirements files found.".to_string());
         }
 
-        let mut dependencies = Vec::new();
+        // Top-level files are migrated with their own type, so includes of them are skipped
+        let mut visited: HashSet<PathBuf> = requirements_files
+            .iter()
+            .map(|(path, _)| canonical_path(path))
+            .collect();
+
+        let mut dependencies: Vec<Dependency> = Vec::new();
         for (file_path, dep_type) in requirements_files {
             info!("Processing requirements file: {}", file_path.display());
-            let deps = self.process_requirements_file(&file_path, dep_type)?;
+            let deps = self.process_requirements_file(&file_path, dep_type, &mut visited)?;
             debug!("Extracted {} dependencies", deps.len());
-            dependencies.extend(deps);
+            // Both layouts may list the same package for the same group
+            for dep in deps {
+                let listed = dependencies
+                    .iter()
+                    .any(|d| d.dep_type == dep.dep_type && d.name.eq_ignore_ascii_case(&dep.name));
+                if listed {
+                    debug!(
+                        "Skipping {} already listed in another requirements file",
+                        dep.name
+                    );
+                } else {
+                    dependencies.push(dep);
+                }
+            }
         }
 
         debug!("Total dependencies extracted: {}", dependencies.len());
         Ok(dependencies)
     }
+
+    fn skipped_items(&self, project_dir: &Path) -> Result<Vec<SkippedItem>, String> {
+        let mut skipped = Vec::new();
+        for (file_path, _) in self.find_requirements_files(project_dir) {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| format!("Error reading file '{}': {}", file_path.display(), e))?;
+            let file_name = file_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+
+            for (line_num, line) in contents.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty()
+                    || line.starts_with('#')
+                    || self.parse_self_reference(line).is_some()
+                    || parse_include(line).is_some()
+                    || parse_constraint(line).is_some()
+                    || parse_find_links(line).is_some()
+                {
+                    continue;
+                }
+                if is_unsupported_option(line) {
+                    skipped.push(SkippedItem::new(
+                        SkippedKind::Line,
+                        format!("{}:{}", file_name, line_num + 1),
+                        format!("Unsupported requirements option ('{}')", line),
+                    ));
+                    continue;
+                }
+                if let Err(e) = self.parse_requirement(line) {
+                    skipped.push(SkippedItem::new(
+                        SkippedKind::Line,
+                        format!("{}:{}", file_name, line_num + 1),
+                        format!("{} ('{}')", e, line),
+                    ));
+                }
+            }
+        }
+        Ok(skipped)
+    }
+
+    fn source_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut files = vec![project_dir.join("pyproject.toml")];
+        for (file_path, _) in self.find_requirements_files(project_dir) {
+            self.referenced_files(&file_path, &mut files);
+        }
+        Ok(files)
+    }
 }
 
 impl RequirementsMigrationSource {
+    /// Finds the top-level `requirements.txt` and `requirements-<group>.txt` files,
+    /// followed by the `.txt` files of a `requirements/` directory
     pub(crate) fn find_requirements_files(&self, dir: &Path) -> Vec<(PathBuf, DependencyType)> {
         let mut requirements_files = Vec::new();
         if let Ok(entries) = fs::read_dir(dir) {
@@ -56,6 +128,42 @@ impl RequirementsMigrationSource {
                 }
             }
         }
+        // read_dir order depends on the file system; keep requirements.txt first
+        requirements_files.sort_by(|(a, a_type), (b, b_type)| {
+            (*a_type != DependencyType::Main, a).cmp(&(*b_type != DependencyType::Main, b))
+        });
+        requirements_files.extend(self.find_requirements_dir_files(&dir.join("requirements")));
+        requirements_files
+    }
+
+    /// Finds the files of a `requirements/` directory: `base.txt` holds the main
+    /// dependencies, `dev.txt` the dev dependencies and any other `<name>.txt` the
+    /// group `<name>`
+    fn find_requirements_dir_files(&self, dir: &Path) -> Vec<(PathBuf, DependencyType)> {
+        let mut requirements_files: Vec<(PathBuf, DependencyType)> = fs::read_dir(dir)
+            .into_iter()
+            .flatten()
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file())
+            .filter_map(|path| {
+                let name = path
+                    .file_name()?
+                    .to_str()?
+                    .strip_suffix(".txt")?
+                    .to_string();
+                let dep_type = match name.as_str() {
+                    "base" => DependencyType::Main,
+                    "dev" => DependencyType::Dev,
+                    _ => DependencyType::Group(name.clone()),
+                };
+                info!("Found {} requirements file: {}", name, path.display());
+                Some((path, dep_type))
+            })
+            .collect();
+        requirements_files.sort_by(|(a, a_type), (b, b_type)| {
+            (*a_type != DependencyType::Main, a).cmp(&(*b_type != DependencyType::Main, b))
+        });
         requirements_files
     }
 
@@ -63,15 +171,61 @@ impl RequirementsMigrationSource {
         !self.find_requirements_files(dir).is_empty()
     }
 
+    /// Collects the `--find-links`/`-f` sources of the project's requirements files.
+    ///
+    /// Local paths are made relative to the project directory; URLs are kept as-is.
+    pub fn find_links(&self, project_dir: &Path) -> Result<Vec<String>, String> {
+        let mut links: Vec<String> = Vec::new();
+        for (file_path, _) in self.find_requirements_files(project_dir) {
+            let contents = fs::read_to_string(&file_path)
+                .map_err(|e| format!("Error reading file '{}': {}", file_path.display(), e))?;
+            let base_dir = file_path
+                .parent()
+                .and_then(|parent| parent.strip_prefix(project_dir).ok())
+                .unwrap_or(Path::new(""));
+
+            for line in contents.lines().map(str::trim) {
+                let Some(link) = parse_find_links(line) else {
+                    continue;
+                };
+                let link = if link.contains("://") {
+                    link.to_string()
+                } else {
+                    base_dir.join(link).to_string_lossy().into_owned()
+                };
+                if !links.contains(&link) {
+                    info!("Found find-links source: {}", link);
+                    links.push(link);
+                }
+            }
+        }
+        Ok(links)
+    }
+
+    /// Parses a single requirements file and the files it includes as main dependencies.
+    pub(crate) fn parse_requirements_file(
+        &self,
+        file_path: &Path,
+    ) -> Result<Vec<Dependency>, String> {
+        let mut visited = HashSet::from([canonical_path(file_path)]);
+        self.process_requirements_file(file_path, DependencyType::Main, &mut visited)
+    }
+
+    /// Parses a requirements file, recursively following `-r` includes.
+    ///
+    /// Included files inherit `dep_type`. Files already in `visited` are not read
+    /// again, which also breaks include cycles.
     fn process_requirements_file(
         &self,
         file_path: &Path,
         dep_type: DependencyType,
+        visited: &mut HashSet<PathBuf>,
     ) -> Result<Vec<Dependency>, String> {
         let contents = fs::read_to_string(file_path)
             .map_err(|e| format!("Error reading file '{}': {}", file_path.display(), e))?;
 
         let mut dependencies = Vec::new();
+        let mut constraints: HashMap<String, String> = HashMap::new();
 
         for (line_num, line) in contents.lines().enumerate() {
             let line = line.trim();
@@ -81,14 +235,75 @@ impl RequirementsMigrationSource {
                 continue;
             }
 
+            if let Some(constraint_file) = parse_constraint(line) {
+                let base_dir = file_path.parent().unwrap_or(Path::new("."));
+                constraints.extend(self.read_constraints(&base_dir.join(constraint_file))?);
+                continue;
+            }
+
+            // Link sources are migrated to [tool.uv] find-links by `find_links`
+            if parse_find_links(line).is_some() {
+                debug!("Line {} is a find-links source: {}", line_num + 1, line);
+                continue;
+            }
+
+            if let Some(include) = parse_include(line) {
+                let base_dir = file_path.parent().unwrap_or(Path::new("."));
+                let include_path = base_dir.join(include);
+                if !visited.insert(canonical_path(&include_path)) {
+                    info!(
+                        "Skipping '{}' included from {} line {}: already processed",
+                        include,
+                        file_path.display(),
+                        line_num + 1
+                    );
+                    continue;
+                }
+                info!("Including requirements file: {}", include_path.display());
+                dependencies.extend(self.process_requirements_file(
+                    &include_path,
+                    dep_type.clone(),
+                    visited,
+                )?);
+                continue;
+            }
+
+            // The project itself (e.g. `.[dev]`) is not a dependency
+            if let Some(extras) = self.parse_self_reference(line) {
+                info!(
+                    "Line {} references the project itself ('{}'); not adding it as a dependency",
+                    line_num + 1,
+                    line
+                );
+                let project_dir = file_path.parent().unwrap_or(Path::new("."));
+                for extra in extras {
+                    for dep in self.project_extra_dependencies(project_dir, &extra)? {
+                        debug!("Including '{}' from project extra '{}'", dep.name, extra);
+                        dependencies.push(Dependency {
+                            dep_type: dep_type.clone(),
+                            ..dep
+                        });
+                    }
+                }
+                continue;
+            }
+
+            if is_unsupported_option(line) {
+                warn!(
+                    "Ignoring unsupported option on {} line {}: {}",
+                    file_path.display(),
+                    line_num + 1,
+                    line
+                );
+                continue;
+            }
+
             match self.parse_requirement(line) {
                 Ok(Some(dep)) => {
                     debug!("Parsed dependency on line {}: {:?}", line_num + 1, dep);
                     dependencies.push(Dependency {
-                        name: dep.name,
-                        version: dep.version,
                         dep_type: dep_type.clone(),
-                        environment_markers: dep.environment_markers,
+                        ..dep
                     });
                 }
                 Ok(None) => debug!(
@@ -100,10 +315,131 @@ impl RequirementsMigrationSource {
             }
         }
 
+        // Constraints only pin declared dependencies; they never add new ones
+        for dep in dependencies.iter_mut().filter(|d| d.version.is_none()) {
+            if let Some(version) = constraints.get(&dep.name.to_lowercase()) {
+                debug!("Pinning '{}' to constraint '{}'", dep.name, version);
+                dep.version = Some(version.clone());
+            }
+        }
+
         debug!("Processed {} dependencies", dependencies.len());
         Ok(dependencies)
     }
 
+    /// Adds `file_path` and the files it references with `-r` and `-c` to `files`,
+    /// following includes recursively
+    pub(crate) fn referenced_files(&self, file_path: &Path, files: &mut Vec<PathBuf>) {
+        let canonical = canonical_path(file_path);
+        if files.iter().any(|file| canonical_path(file) == canonical) {
+            return;
+        }
+        files.push(file_path.to_path_buf());
+
+        let Ok(contents) = fs::read_to_string(file_path) else {
+            return;
+        };
+        let base_dir = file_path.parent().unwrap_or(Path::new("."));
+        for line in contents.lines().map(str::trim) {
+            if let Some(include) = parse_include(line) {
+                self.referenced_files(&base_dir.join(include), files);
+            } else if let Some(constraint_file) = parse_constraint(line) {
+                files.push(base_dir.join(constraint_file));
+            }
+        }
+    }
+
+    /// Reads a `-c` constraints file into a map of lowercased package name to version
+    fn read_constraints(&self, file_path: &Path) -> Result<HashMap<String, String>, String> {
+        info!("Reading constraints file: {}", file_path.display());
+        let contents = fs::read_to_string(file_path)
+            .map_err(|e| format!("Error reading file '{}': {}", file_path.display(), e))?;
+
+        let mut constraints = HashMap::new();
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+                continue;
+            }
+            match self.parse_requirement(line) {
+                Ok(Some(Dependency {
+                    name,
+                    version: Some(version),
+                    ..
+                })) => {
+                    constraints.insert(name.to_lowercase(), version);
+                }
+                Ok(_) => {}
+                Err(e) => debug!("Skipping constraint '{}': {}", line, e),
+            }
+        }
+        Ok(constraints)
+    }
+
+    /// Recognizes a reference to the project itself such as `.`, `.[dev]` or `-e .[test]`.
+    ///
+    /// Returns the requested extras when the line is a self-reference.
+    fn parse_self_reference(&self, line: &str) -> Option<Vec<String>> {
+        let spec = line
+            .strip_prefix("-e")
+            .or_else(|| line.strip_prefix("--editable"))
+            .unwrap_or(line)
+            .trim();
+        let spec = spec.split(';').next().unwrap_or(spec).trim();
+
+        let rest = spec.strip_prefix('.')?;
+        let rest = rest.strip_prefix('/').unwrap_or(rest);
+        if rest.is_empty() {
+            return Some(vec![]);
+        }
+
+        let extras = rest.strip_prefix('[')?.strip_suffix(']')?;
+        Some(
+            extras
+                .split(',')
+                .map(|e| e.trim().to_string())
+                .filter(|e| !e.is_empty())
+                .collect(),
+        )
+    }
+
+    /// Reads the dependencies of a named extra from the project's own pyproject.toml
+    fn project_extra_dependencies(
+        &self,
+        project_dir: &Path,
+        extra: &str,
+    ) -> Result<Vec<Dependency>, String> {
+        let pyproject_path = project_dir.join("pyproject.toml");
+        if !pyproject_path.exists() {
+            info!(
+                "No pyproject.toml found to resolve project extra '{}'; skipping",
+                extra
+            );
+            return Ok(vec![]);
+        }
+
+        let doc = read_toml(&pyproject_path)?;
+        let Some(entries) = doc
+            .get("project")
+            .and_then(|p| p.get("optional-dependencies"))
+            .and_then(|o| o.get(extra))
+            .and_then(|e| e.as_array())
+        else {
+            info!("Project extra '{}' not found in pyproject.toml", extra);
+            return Ok(vec![]);
+        };
+
+        let mut dependencies = Vec::new();
+        for entry in entries.iter().filter_map(|e| e.as_str()) {
+            match self.parse_requirement(entry) {
+                Ok(Some(dep)) => dependencies.push(dep),
+                Ok(None) => {}
+                Err(e) => debug!("Failed to parse extra requirement '{}': {}", entry, e),
+            }
+        }
+
+        Ok(dependencies)
+    }
+
     fn process_version_spec(&self, version_spec: &str) -> String {
         let version_spec = version_spec.trim();
 
@@ -134,7 +470,7 @@ impl RequirementsMigrationSource {
         }
     }
 
-    fn parse_requirement(&self, line: &str) -> Result<Option<Dependency>, String> {
+    pub(crate) fn parse_requirement(&self, line: &str) -> Result<Option<Dependency>, String> {
         // Handle editable installs (-e flag)
         let line = if line.starts_with("-e") {
             let parts: Vec<&str> = line.splitn(2, ' ').collect();
@@ -156,17 +492,33 @@ impl RequirementsMigrationSource {
         }
 
         // Handle URLs and git repositories
-        let (name, version) =
-            if package_spec.starts_with("git+") || package_spec.starts_with("http") {
-                self.parse_url_requirement(package_spec)?
-            } else {
-                self.parse_regular_requirement(package_spec)?
-            };
+        let (name, version) = if package_spec.starts_with("git+")
+            || package_spec.starts_with("http://")
+            || package_spec.starts_with("https://")
+        {
+            self.parse_url_requirement(package_spec)?
+        } else {
+            self.parse_regular_requirement(package_spec)?
+        };
 
         if name == "python" {
             return Ok(None);
         }
 
+        // Split extras such as `pydantic[email]` off the package name
+        let (name, extras) = match name.split_once('[') {
+            Some((base, rest)) => {
+                let extras = rest
+                    .trim_end_matches(']')
+                    .split(',')
+                    .map(|e| e.trim().to_string())
+                    .filter(|e| !e.is_empty())
+                    .collect::<Vec<_>>();
+                (base.trim().to_string(), Some(extras))
+            }
+            None => (name, None),
+        };
+
         // Handle environment markers
         let environment_markers = if parts.len() > 1 {
             Some(parts[1..].join(";").trim().to_string())
@@ -174,25 +526,26 @@ impl RequirementsMigrationSource {
             None
         };
 
-        Ok(Some(Dependency {
-            name,
-            version,
-            dep_type: DependencyType::Main, // This will be overridden by the caller
-            environment_markers,
-        }))
+        // The dependency type is overridden by the caller
+        Dependency::new(name)
+            .version(version)
+            .extras(extras)
+            .markers(environment_markers)
+            .build()
+            .map(Some)
     }
 
     fn parse_url_requirement(
         &self,
         package_spec: &str,
     ) -> Result<(String, Option<String>), String> {
-        let name = if let Some(egg_part) = package_spec.split('#').last() {
+        let name = if let Some(egg_part) = package_spec.split('#').next_back() {
             if egg_part.starts_with("egg=") {
                 egg_part.trim_start_matches("egg=").to_string()
             } else if package_spec.ends_with(".whl") {
                 package_spec
                     .split('/')
-                    .last()
+                    .next_back()
                     .and_then(|f| f.split('-').next())
                     .ok_or("Invalid wheel filename")?
                     .to_string()
@@ -202,7 +555,7 @@ impl RequirementsMigrationSource {
         } else {
             package_spec
                 .split('/')
-                .last()
+                .next_back()
                 .and_then(|f| f.split('.').next())
                 .ok_or("Invalid URL format")?
                 .to_string()
@@ -231,3 +584,47 @@ impl RequirementsMigrationSource {
         Ok((name, version))
     }
 }
+
+/// Returns the referenced file of an include line such as `-r base.txt` or
+/// `--requirement=base.txt`.
+pub(crate) fn parse_include(line: &str) -> Option<&str> {
+    parse_file_option(line, "--requirement", "-r")
+}
+
+/// Returns the referenced file of a constraints line such as `-c constraints.txt` or
+/// `--constraint=constraints.txt`.
+fn parse_constraint(line: &str) -> Option<&str> {
+    parse_file_option(line, "--constraint", "-c")
+}
+
+/// Returns the source of a `--find-links`/`-f` line, warning when it has no value.
+fn parse_find_links(line: &str) -> Option<&str> {
+    if !(line.starts_with("--find-links") || line.starts_with("-f")) {
+        return None;
+    }
+    let link = parse_file_option(line, "--find-links", "-f");
+    if link.is_none() {
+        warn!("Ignoring find-links option without a location: {}", line);
+    }
+    link
+}
+
+/// Whether the line is a pip option other than an editable install, such as
+/// `--index-url` or `--no-binary`, which is not a dependency
+fn is_unsupported_option(line: &str) -> bool {
+    line.starts_with('-') && !line.starts_with("-e") && !line.starts_with("--editable")
+}
+
+fn parse_file_option<'a>(line: &'a str, long: &str, short: &str) -> Option<&'a str> {
+    let rest = line
+        .strip_prefix(long)
+        .or_else(|| line.strip_prefix(short))?;
+    let rest = rest.strip_prefix('=').unwrap_or(rest).trim();
+    let file = rest.split(" #").next().unwrap_or(rest).trim();
+    (!file.is_empty()).then_some(file)
+}
+
+/// Canonicalizes a path for cycle detection, keeping it as-is when it does not exist
+fn canonical_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
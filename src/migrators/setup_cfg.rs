@@ -0,0 +1,162 @@
+use super::requirements::RequirementsMigrationSource;
+use super::{Dependency, DependencyType};
+use log::{debug, info};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// The `[metadata]` and `[options]` sections of a setuptools `setup.cfg`
+#[derive(Debug, Default)]
+pub struct SetupCfg {
+    sections: HashMap<String, Vec<(String, String)>>,
+}
+
+impl SetupCfg {
+    /// Reads `setup.cfg` from the project directory, if there is one
+    pub fn load(project_dir: &Path) -> Result<Option<SetupCfg>, String> {
+        let setup_cfg_path = project_dir.join("setup.cfg");
+        if !setup_cfg_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&setup_cfg_path)
+            .map_err(|e| format!("Failed to read setup.cfg: {}", e))?;
+        Ok(Some(Self::parse(&content)))
+    }
+
+    /// Parses INI content, joining indented continuation lines onto their key
+    pub fn parse(content: &str) -> SetupCfg {
+        let mut sections: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        let mut current_section: Option<String> = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+                continue;
+            }
+
+            if let Some(name) = trimmed.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                current_section = Some(name.trim().to_string());
+                sections.entry(name.trim().to_string()).or_default();
+                continue;
+            }
+
+            let Some(section) = current_section.as_ref() else {
+                debug!("Ignoring setup.cfg line outside of a section: {}", trimmed);
+                continue;
+            };
+            let entries = sections.entry(section.clone()).or_default();
+
+            if line.starts_with(char::is_whitespace) {
+                if let Some((_, value)) = entries.last_mut() {
+                    value.push('\n');
+                    value.push_str(trimmed);
+                }
+                continue;
+            }
+
+            if let Some((key, value)) = trimmed.split_once(['=', ':']) {
+                entries.push((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+
+        SetupCfg { sections }
+    }
+
+    /// Returns a single value, or `None` when it is missing or empty
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections
+            .get(section)?
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Returns a metadata value that setuptools would use verbatim.
+    ///
+    /// `attr:` and `file:` directives are resolved by setuptools at build time, so
+    /// they are skipped.
+    pub fn metadata(&self, key: &str) -> Option<String> {
+        let value = self.get("metadata", key)?;
+        if value.starts_with("attr:") || value.starts_with("file:") {
+            debug!("Skipping setup.cfg directive for '{}': {}", key, value);
+            return None;
+        }
+        Some(value.to_string())
+    }
+
+    /// Returns the names of all sections, sorted
+    pub fn section_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.sections.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Splits a dangling list value into its entries
+    pub fn list(&self, section: &str, key: &str) -> Vec<String> {
+        self.get(section, key)
+            .map(|value| {
+                value
+                    .lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                    .map(|l| l.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Extracts `install_requires`, `tests_require` and `[options.extras_require]`
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        let mut dependencies = Vec::new();
+
+        let mut push = |entry: &str, dep_type: DependencyType| match RequirementsMigrationSource
+            .parse_requirement(entry)
+        {
+            Ok(Some(dep)) => dependencies.push(Dependency { dep_type, ..dep }),
+            Ok(None) => {}
+            Err(e) => debug!("Skipping setup.cfg requirement '{}': {}", entry, e),
+        };
+
+        for entry in self.list("options", "install_requires") {
+            push(&entry, DependencyType::Main);
+        }
+        for entry in self.list("options", "tests_require") {
+            push(&entry, DependencyType::Dev);
+        }
+        if let Some(extras) = self.sections.get("options.extras_require") {
+            for (extra, _) in extras {
+                for entry in self.list("options.extras_require", extra) {
+                    push(&entry, DependencyType::Optional(extra.clone()));
+                }
+            }
+        }
+
+        info!(
+            "Extracted {} dependencies from setup.cfg",
+            dependencies.len()
+        );
+        dependencies
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sections_and_continuations() {
+        let cfg = SetupCfg::parse(
+            "[metadata]\nname = demo\nversion = attr: demo.__version__\n\n\
+            [options]\ninstall_requires =\n    requests>=2.0\n    # comment\n    click\n",
+        );
+
+        assert_eq!(cfg.metadata("name"), Some("demo".to_string()));
+        assert_eq!(cfg.metadata("version"), None);
+        assert_eq!(
+            cfg.list("options", "install_requires"),
+            vec!["requests>=2.0", "click"]
+        );
+    }
+}
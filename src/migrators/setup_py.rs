@@ -1,8 +1,9 @@
 use super::requirements::RequirementsMigrationSource;
+use super::setup_cfg::SetupCfg;
 use super::{Dependency, DependencyType, MigrationSource};
 use log::{debug, info};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 pub struct SetupPyMigrationSource;
 
@@ -15,8 +16,34 @@ impl MigrationSource for SetupPyMigrationSource {
             return requirements_source.extract_dependencies(project_dir);
         }
 
-        info!("No requirements files found, parsing setup.py directly");
-        self.parse_setup_py(project_dir)
+        let setup_cfg = SetupCfg::load(project_dir)?;
+        let mut dependencies = if project_dir.join("setup.py").exists() {
+            info!("No requirements files found, parsing setup.py directly");
+            self.parse_setup_py(project_dir)?
+        } else {
+            Vec::new()
+        };
+
+        // setup.cfg entries take precedence over the same package in setup.py
+        if let Some(setup_cfg) = setup_cfg {
+            let cfg_dependencies = setup_cfg.dependencies();
+            dependencies.retain(|dep| {
+                !cfg_dependencies
+                    .iter()
+                    .any(|cfg_dep| cfg_dep.name == dep.name && cfg_dep.dep_type == dep.dep_type)
+            });
+            dependencies.extend(cfg_dependencies);
+        }
+
+        Ok(dependencies)
+    }
+
+    fn source_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        // Requirements files, when present, are used instead of setup.py
+        let mut files = RequirementsMigrationSource.source_files(project_dir)?;
+        files.push(project_dir.join("setup.py"));
+        files.push(project_dir.join("setup.cfg"));
+        Ok(files)
     }
 }
 
@@ -28,17 +55,21 @@ impl SetupPyMigrationSource {
 
         debug!("Parsing setup.py content");
         let mut dependencies = Vec::new();
+        let arguments = Self::setup_call(&content).unwrap_or_else(|| content.clone());
 
         // Extract main dependencies
-        if let Some(mut deps) = self.extract_install_requires(&content) {
+        if let Some(mut deps) = self.extract_install_requires(&arguments) {
             dependencies.append(&mut deps);
         }
 
         // Extract test dependencies
-        if let Some(mut deps) = self.extract_tests_require(&content) {
+        if let Some(mut deps) = self.extract_tests_require(&arguments) {
             dependencies.append(&mut deps);
         }
 
+        // Extract optional dependencies as dependency groups
+        dependencies.append(&mut self.extract_extras_require(&content));
+
         Ok(dependencies)
     }
 
@@ -58,16 +89,199 @@ impl SetupPyMigrationSource {
         Some(self.parse_dependencies(&bracket_content, DependencyType::Dev))
     }
 
+    /// Extracts each `extras_require` entry as a dependency group.
+    ///
+    /// Both a dict literal in the `setup()` call and a module-level dict passed by
+    /// name are supported. Item assignments such as
+    /// `extras_require['all'] = extras_require['dev'] + extras_require['docs']` are
+    /// flattened on a best-effort basis.
+    fn extract_extras_require(&self, content: &str) -> Vec<Dependency> {
+        let Some(setup_content) = Self::setup_call(content) else {
+            return Vec::new();
+        };
+        let Some(value) = Self::keyword_values(&setup_content, "extras_require")
+            .into_iter()
+            .next()
+        else {
+            return Vec::new();
+        };
+
+        let mut extras = if value.starts_with('{') {
+            self.extract_delimited_content(value, 0, '{', '}')
+                .map(|dict| self.parse_extras_dict(&dict))
+                .unwrap_or_default()
+        } else {
+            let variable: String = value
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            let mut extras = Self::keyword_values(content, &variable)
+                .into_iter()
+                .find(|value| value.starts_with('{'))
+                .and_then(|value| self.extract_delimited_content(value, 0, '{', '}'))
+                .map(|dict| self.parse_extras_dict(&dict))
+                .unwrap_or_default();
+            self.apply_extras_assignments(content, &variable, &mut extras);
+            extras
+        };
+        extras.retain(|(_, requirements)| !requirements.trim().is_empty());
+
+        let mut dependencies = Vec::new();
+        for (extra, requirements) in extras {
+            let dep_type = match extra.as_str() {
+                "dev" | "test" | "tests" => DependencyType::Dev,
+                _ => DependencyType::Group(extra.clone()),
+            };
+            for dep in self.parse_dependencies(&requirements, dep_type) {
+                if !dependencies
+                    .iter()
+                    .any(|d: &Dependency| d.name == dep.name && d.dep_type == dep.dep_type)
+                {
+                    dependencies.push(dep);
+                }
+            }
+        }
+
+        info!(
+            "Extracted {} dependencies from extras_require",
+            dependencies.len()
+        );
+        dependencies
+    }
+
+    /// Splits the body of an `extras_require` dict into `(extra, list content)` pairs
+    fn parse_extras_dict(&self, dict: &str) -> Vec<(String, String)> {
+        let dict: String = dict
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut extras = Vec::new();
+        let mut rest = dict.as_str();
+
+        while let Some(quote_pos) = rest.find(['\'', '"']) {
+            let quote = &rest[quote_pos..quote_pos + 1];
+            let after_quote = &rest[quote_pos + 1..];
+            let Some(key_end) = after_quote.find(quote) else {
+                break;
+            };
+            let key = after_quote[..key_end].to_string();
+            let after_key = after_quote[key_end + 1..].trim_start();
+            let Some(value) = after_key.strip_prefix(':').map(str::trim_start) else {
+                rest = &after_quote[key_end + 1..];
+                continue;
+            };
+
+            if value.starts_with('[') {
+                if let Some(list) = self.extract_bracket_content(value, 0) {
+                    rest = &value[list.len() + 2..];
+                    extras.push((key, list));
+                    continue;
+                }
+            }
+
+            debug!("Skipping extras_require entry '{}' that is not a list", key);
+            rest = value.find(',').map(|pos| &value[pos + 1..]).unwrap_or("");
+        }
+
+        extras
+    }
+
+    /// Applies `variable['extra'] = ...` assignments, concatenating referenced extras
+    /// and list literals
+    fn apply_extras_assignments(
+        &self,
+        content: &str,
+        variable: &str,
+        extras: &mut Vec<(String, String)>,
+    ) {
+        let prefix = format!("{}[", variable);
+        for line in content.lines() {
+            let Some(rest) = line.trim().strip_prefix(&prefix) else {
+                continue;
+            };
+            let Some((key, expression)) = rest.split_once(']') else {
+                continue;
+            };
+            let key = key
+                .trim()
+                .trim_matches(|c| c == '\'' || c == '"')
+                .to_string();
+            let Some(expression) = expression
+                .trim_start()
+                .strip_prefix('=')
+                .filter(|e| !e.starts_with('='))
+            else {
+                continue;
+            };
+
+            let mut combined = Vec::new();
+            for term in expression.split('+').map(str::trim) {
+                if let Some(reference) = term.strip_prefix(&prefix) {
+                    let reference = reference
+                        .trim_end_matches(']')
+                        .trim_matches(|c| c == '\'' || c == '"');
+                    match extras.iter().find(|(name, _)| name == reference) {
+                        Some((_, requirements)) => combined.push(requirements.clone()),
+                        None => debug!("extras_require['{}'] references unknown extra", key),
+                    }
+                } else if term.starts_with('[') {
+                    if let Some(list) = self.extract_bracket_content(term, 0) {
+                        combined.push(list);
+                    }
+                } else {
+                    debug!("Cannot flatten extras_require['{}'] term: {}", key, term);
+                }
+            }
+
+            let requirements = combined.join(",");
+            match extras.iter_mut().find(|(name, _)| *name == key) {
+                Some(entry) => entry.1 = requirements,
+                None => extras.push((key, requirements)),
+            }
+        }
+    }
+
+    /// Returns the text following each `key=` keyword or assignment in `content`
+    fn keyword_values<'a>(content: &'a str, key: &str) -> Vec<&'a str> {
+        content
+            .match_indices(key)
+            .filter(|(idx, _)| {
+                !content[..*idx]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_')
+            })
+            .filter_map(|(idx, _)| {
+                content[idx + key.len()..]
+                    .trim_start()
+                    .strip_prefix('=')
+                    .filter(|rest| !rest.starts_with('='))
+                    .map(str::trim_start)
+            })
+            .collect()
+    }
+
     pub fn extract_bracket_content(&self, content: &str, start_pos: usize) -> Option<String> {
+        self.extract_delimited_content(content, start_pos, '[', ']')
+    }
+
+    fn extract_delimited_content(
+        &self,
+        content: &str,
+        start_pos: usize,
+        open: char,
+        close: char,
+    ) -> Option<String> {
         let content = &content[start_pos..];
-        let bracket_start = content.find('[')?;
+        let bracket_start = content.find(open)?;
         let mut bracket_count = 1;
         let mut pos = bracket_start + 1;
 
         while bracket_count > 0 && pos < content.len() {
             match content.chars().nth(pos)? {
-                '[' => bracket_count += 1,
-                ']' => bracket_count -= 1,
+                c if c == open => bracket_count += 1,
+                c if c == close => bracket_count -= 1,
                 _ => {}
             }
             pos += 1;
@@ -92,12 +306,14 @@ impl SetupPyMigrationSource {
             // Remove quotes and extract package name and version
             let dep_str = line.trim_matches(|c| c == '\'' || c == '"');
             if let Some((name, version)) = self.parse_dependency_spec(dep_str) {
-                dependencies.push(Dependency {
-                    name,
-                    version,
-                    dep_type: dep_type.clone(),
-                    environment_markers: None,
-                });
+                match Dependency::new(name)
+                    .version(version)
+                    .dep_type(dep_type.clone())
+                    .build()
+                {
+                    Ok(dep) => dependencies.push(dep),
+                    Err(e) => debug!("Skipping dependency '{}': {}", dep_str, e),
+                }
             }
         }
 
@@ -133,7 +349,20 @@ impl SetupPyMigrationSource {
         }
     }
 
+    /// Extracts the project name from setup.cfg
+    pub fn extract_name(project_dir: &Path) -> Result<Option<String>, String> {
+        Ok(SetupCfg::load(project_dir)?.and_then(|cfg| cfg.metadata("name")))
+    }
+
+    /// Extracts the project description, preferring setup.cfg over setup.py
     pub fn extract_description(project_dir: &Path) -> Result<Option<String>, String> {
+        if let Some(description) =
+            SetupCfg::load(project_dir)?.and_then(|cfg| cfg.metadata("description"))
+        {
+            debug!("Found description in setup.cfg");
+            return Ok(Some(description));
+        }
+
         let setup_py_path = project_dir.join("setup.py");
         if !setup_py_path.exists() {
             return Ok(None);
@@ -143,9 +372,7 @@ impl SetupPyMigrationSource {
             .map_err(|e| format!("Failed to read setup.py: {}", e))?;
 
         // Look for description in setup() call
-        if let Some(start_idx) = content.find("setup(") {
-            let bracket_content = Self::extract_setup_content(&content[start_idx..])?;
-
+        if let Some(bracket_content) = Self::setup_call(&content) {
             // First try to find long_description
             if let Some(desc) = Self::extract_parameter(&bracket_content, "long_description") {
                 debug!("Found long_description in setup.py");
@@ -162,57 +389,165 @@ impl SetupPyMigrationSource {
         Ok(None)
     }
 
-    pub fn extract_setup_content(content: &str) -> Result<String, String> {
-        let lines = content.lines().enumerate().peekable();
-        let mut setup_content = String::new();
-        let mut in_setup = false;
-        let mut paren_count = 0;
+    /// Returns the arguments of the `setup()` call in a setup.py.
+    ///
+    /// Qualified (`setuptools.setup(...)`) and aliased
+    /// (`from setuptools import setup as _s`) calls are recognised, and
+    /// `setup(**kwargs)` is expanded when `kwargs` is a dict defined in the same file.
+    pub fn setup_call(content: &str) -> Option<String> {
+        let start = Self::find_setup_call(content)?;
+        let arguments = match Self::extract_setup_content(&content[start..]) {
+            Ok(arguments) => arguments,
+            Err(e) => {
+                debug!("Failed to parse setup() call: {}", e);
+                return None;
+            }
+        };
+        Some(Self::expand_kwargs(content, &arguments))
+    }
 
-        for (_, line) in lines {
-            let trimmed = line.trim();
+    /// Finds where the first call to setuptools' `setup` starts
+    fn find_setup_call(content: &str) -> Option<usize> {
+        let mut callees = vec!["setuptools.setup".to_string(), "setup".to_string()];
+        for line in content.lines().map(str::trim) {
+            if let Some(imports) = line
+                .strip_prefix("from setuptools import ")
+                .or_else(|| line.strip_prefix("from distutils.core import "))
+            {
+                for import in imports.trim_matches(['(', ')']).split(',') {
+                    if let Some(("setup", alias)) = import
+                        .split_once(" as ")
+                        .map(|(name, alias)| (name.trim(), alias.trim()))
+                    {
+                        debug!("setup is imported as '{}'", alias);
+                        callees.push(alias.to_string());
+                    }
+                }
+            } else if let Some(alias) = line.strip_prefix("import setuptools as ") {
+                callees.push(format!("{}.setup", alias.trim()));
+            }
+        }
 
-            // Skip empty lines and comments
-            if trimmed.is_empty() || trimmed.starts_with('#') {
+        callees
+            .iter()
+            .flat_map(|callee| {
+                let pattern = format!("{}(", callee);
+                content
+                    .match_indices(&pattern)
+                    .map(|(idx, _)| idx)
+                    .filter(|&idx| {
+                        let before = &content[..idx];
+                        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+                        !before[line_start..].trim_start().starts_with('#')
+                            && !before
+                                .chars()
+                                .next_back()
+                                .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+                            && !before.trim_end_matches(' ').ends_with("def")
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .min()
+    }
+
+    /// Returns the text between the first `(` of `content` and its matching `)`,
+    /// ignoring parentheses in strings and comments
+    pub fn extract_setup_content(content: &str) -> Result<String, String> {
+        let open = content
+            .find('(')
+            .ok_or_else(|| "Could not find setup() call".to_string())?;
+        let arguments = &content[open + 1..];
+        let mut depth = 1;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        let mut in_comment = false;
+
+        for (idx, c) in arguments.char_indices() {
+            if in_comment {
+                in_comment = c != '\n';
                 continue;
             }
-
-            if !in_setup {
-                if trimmed.starts_with("setup(") {
-                    in_setup = true;
-                    paren_count = 1;
-                    // Extract everything after setup(
-                    if let Some(content) = line.split("setup(").nth(1) {
-                        setup_content.push_str(content);
-                        setup_content.push('\n');
-                    }
+            if let Some(q) = quote {
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    quote = None;
                 }
-            } else {
-                // Count parentheses in the line
-                for c in line.chars() {
-                    match c {
-                        '(' => paren_count += 1,
-                        ')' => paren_count -= 1,
-                        _ => {}
+                continue;
+            }
+            match c {
+                '#' => in_comment = true,
+                '\'' | '"' => quote = Some(c),
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(arguments[..idx].to_string());
                     }
                 }
+                _ => {}
+            }
+        }
 
-                setup_content.push_str(line);
-                setup_content.push('\n');
+        Err("Could not find matching closing parenthesis for setup()".to_string())
+    }
 
-                if paren_count == 0 {
-                    break;
+    /// Replaces a leading `**kwargs` argument with the keyword arguments of the
+    /// `kwargs = {...}` or `kwargs = dict(...)` it refers to
+    fn expand_kwargs(content: &str, arguments: &str) -> String {
+        let Some(unpacked) = arguments.trim_start().strip_prefix("**") else {
+            return arguments.to_string();
+        };
+        let variable: String = unpacked
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        let remaining = &unpacked[variable.len()..];
+
+        let source = SetupPyMigrationSource;
+        let keywords = Self::keyword_values(content, &variable)
+            .into_iter()
+            .find_map(|value| {
+                if value.starts_with('{') {
+                    source
+                        .extract_delimited_content(value, 0, '{', '}')
+                        .map(|dict| Self::dict_to_keywords(&dict))
+                } else if value.starts_with("dict(") {
+                    Self::extract_setup_content(value).ok()
+                } else {
+                    None
                 }
-            }
-        }
+            });
 
-        if !in_setup {
-            return Err("Could not find setup() call".to_string());
-        }
-        if paren_count > 0 {
-            return Err("Could not find matching closing parenthesis for setup()".to_string());
+        match keywords {
+            Some(keywords) => format!("{}\n{}", keywords, remaining),
+            None => {
+                debug!("Could not find the '{}' dict passed to setup()", variable);
+                arguments.to_string()
+            }
         }
+    }
 
-        Ok(setup_content)
+    /// Rewrites `'key': value` lines of a dict literal as `key=value`
+    fn dict_to_keywords(dict: &str) -> String {
+        dict.lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                let Some(quote) = trimmed.chars().next().filter(|c| *c == '\'' || *c == '"') else {
+                    return line.to_string();
+                };
+                let Some((key, rest)) = trimmed[1..].split_once(quote) else {
+                    return line.to_string();
+                };
+                match rest.trim_start().strip_prefix(':') {
+                    Some(value) => format!("{}={}", key, value.trim_start()),
+                    None => line.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
     pub fn extract_parameter(content: &str, param_name: &str) -> Option<String> {
@@ -264,7 +599,13 @@ impl SetupPyMigrationSource {
         Some(value)
     }
 
+    /// Extracts the project URL, preferring setup.cfg over setup.py
     pub fn extract_url(project_dir: &Path) -> Result<Option<String>, String> {
+        if let Some(url) = SetupCfg::load(project_dir)?.and_then(|cfg| cfg.metadata("url")) {
+            debug!("Found URL in setup.cfg");
+            return Ok(Some(url));
+        }
+
         let setup_py_path = project_dir.join("setup.py");
         if !setup_py_path.exists() {
             return Ok(None);
@@ -273,8 +614,7 @@ impl SetupPyMigrationSource {
         let content = fs::read_to_string(&setup_py_path)
             .map_err(|e| format!("Failed to read setup.py: {}", e))?;
 
-        if let Some(start_idx) = content.find("setup(") {
-            let bracket_content = Self::extract_setup_content(&content[start_idx..])?;
+        if let Some(bracket_content) = Self::setup_call(&content) {
             if let Some(url) = Self::extract_parameter(&bracket_content, "url") {
                 debug!("Found URL in setup.py");
                 return Ok(Some(url));
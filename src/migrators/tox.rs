@@ -0,0 +1,112 @@
+use super::requirements::{parse_include, RequirementsMigrationSource};
+use super::setup_cfg::SetupCfg;
+use super::{Dependency, DependencyType, MigrationSource};
+use log::{debug, info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The test environment dependencies declared in `tox.ini`.
+///
+/// This is an auxiliary source: its dependencies are merged into those of the
+/// project's primary source rather than detected as a project type of their own.
+pub struct ToxMigrationSource;
+
+impl MigrationSource for ToxMigrationSource {
+    fn extract_dependencies(&self, project_dir: &Path) -> Result<Vec<Dependency>, String> {
+        let tox_path = project_dir.join("tox.ini");
+        if !tox_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        info!("Extracting test dependencies from tox.ini");
+        let content =
+            fs::read_to_string(&tox_path).map_err(|e| format!("Failed to read tox.ini: {}", e))?;
+        let tox = SetupCfg::parse(&content);
+        let mut dependencies = Vec::new();
+
+        for section in tox.section_names() {
+            let dep_type = match section.strip_prefix("testenv") {
+                Some("") => DependencyType::Dev,
+                Some(env) => match env.strip_prefix(':').map(str::trim) {
+                    Some(env) if !env.is_empty() => DependencyType::Group(env.to_string()),
+                    _ => continue,
+                },
+                None => continue,
+            };
+
+            for entry in tox.list(section, "deps") {
+                self.push_entry(project_dir, &entry, &dep_type, &mut dependencies)?;
+            }
+        }
+
+        info!("Extracted {} dependencies from tox.ini", dependencies.len());
+        Ok(dependencies)
+    }
+
+    fn source_files(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let tox_path = project_dir.join("tox.ini");
+        let mut files = vec![tox_path.clone()];
+        let Ok(content) = fs::read_to_string(&tox_path) else {
+            return Ok(files);
+        };
+        let tox = SetupCfg::parse(&content);
+        for section in tox.section_names() {
+            for entry in tox.list(section, "deps") {
+                let entry = entry.replace("{toxinidir}/", "");
+                if let Some(include) = parse_include(&entry) {
+                    RequirementsMigrationSource
+                        .referenced_files(&project_dir.join(include), &mut files);
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl ToxMigrationSource {
+    fn push_entry(
+        &self,
+        project_dir: &Path,
+        entry: &str,
+        dep_type: &DependencyType,
+        dependencies: &mut Vec<Dependency>,
+    ) -> Result<(), String> {
+        let entry = entry.replace("{toxinidir}/", "");
+
+        if let Some(include) = parse_include(&entry) {
+            let include_path = project_dir.join(include);
+            info!("Including requirements file: {}", include_path.display());
+            let included = RequirementsMigrationSource.parse_requirements_file(&include_path)?;
+            dependencies.extend(included.into_iter().map(|dep| Dependency {
+                dep_type: dep_type.clone(),
+                ..dep
+            }));
+            return Ok(());
+        }
+
+        if entry.starts_with('-') || entry.contains('{') || is_factor_conditional(&entry) {
+            warn!("Ignoring unsupported tox.ini deps entry: {}", entry);
+            return Ok(());
+        }
+
+        match RequirementsMigrationSource.parse_requirement(&entry) {
+            Ok(Some(dep)) => dependencies.push(Dependency {
+                dep_type: dep_type.clone(),
+                ..dep
+            }),
+            Ok(None) => {}
+            Err(e) => debug!("Skipping tox.ini requirement '{}': {}", entry, e),
+        }
+        Ok(())
+    }
+}
+
+/// Whether an entry only applies to some environments, e.g. `py311: pytest-xdist`
+fn is_factor_conditional(entry: &str) -> bool {
+    entry.split_once(':').is_some_and(|(factors, _)| {
+        !factors.is_empty()
+            && factors
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, ',' | '!' | '-' | '_' | '.'))
+    })
+}
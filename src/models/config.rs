@@ -0,0 +1,97 @@
+use crate::cli::Args;
+use std::path::{Path, PathBuf};
+
+/// Options for a migration started through [`crate::migrate`].
+///
+/// Starts from the same defaults as the command line; setters consume and return
+/// the config so they can be chained.
+///
+/// ```
+/// use uv_migrator::MigrationConfig;
+///
+/// let config = MigrationConfig::new("path/to/project")
+///     .python("3.12")
+///     .keep_old(false)
+///     .dry_run(true);
+///
+/// assert_eq!(config.project_dir().to_str(), Some("path/to/project"));
+/// assert_eq!(config.args().python.as_deref(), Some("3.12"));
+/// assert!(config.args().dry_run);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MigrationConfig {
+    project_dir: PathBuf,
+    args: Args,
+}
+
+impl MigrationConfig {
+    pub fn new(project_dir: impl Into<PathBuf>) -> Self {
+        MigrationConfig {
+            project_dir: project_dir.into(),
+            args: Args::default(),
+        }
+    }
+
+    /// Replaces every option with `args`, e.g. ones parsed from a command line
+    pub fn with_args(mut self, args: Args) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// Only logs what would be done, without running uv or changing files
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.args.dry_run = dry_run;
+        self
+    }
+
+    /// Migrates a project that already has a uv.lock
+    pub fn force(mut self, force: bool) -> Self {
+        self.args.force = force;
+        self
+    }
+
+    /// Keeps the original pyproject.toml as old.pyproject.toml
+    pub fn keep_old(mut self, keep_old: bool) -> Self {
+        self.args.keep_old = keep_old;
+        self
+    }
+
+    /// Python version to pin instead of the detected one
+    pub fn python(mut self, version: impl Into<String>) -> Self {
+        self.args.python = Some(version.into());
+        self
+    }
+
+    /// Project type to migrate from when several are detected, e.g. `poetry`
+    pub fn from_type(mut self, project_type: impl Into<String>) -> Self {
+        self.args.from = Some(project_type.into());
+        self
+    }
+
+    /// Moves every dependency group into the dev dependencies
+    pub fn merge_groups(mut self, merge_groups: bool) -> Self {
+        self.args.merge_groups = merge_groups;
+        self
+    }
+
+    /// Adds an index URL to `[[tool.uv.index]]`
+    pub fn import_index(mut self, url: impl Into<String>) -> Self {
+        self.args.import_index.push(url.into());
+        self
+    }
+
+    /// Runs `uv lock` once the migration has succeeded
+    pub fn lock(mut self, lock: bool) -> Self {
+        self.args.lock = lock;
+        self
+    }
+
+    pub fn project_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    /// The options as they are passed to the migration
+    pub fn args(&self) -> &Args {
+        &self.args
+    }
+}
@@ -0,0 +1,284 @@
+#[derive(Debug, Hash, Eq, PartialEq, Clone)]
+pub enum DependencyType {
+    Main,
+    Dev,
+    Group(String),
+    /// An optional dependency installed through the named extra
+    Optional(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub name: String,
+    pub version: Option<String>,
+    pub dep_type: DependencyType,
+    pub extras: Option<Vec<String>>,
+    pub environment_markers: Option<String>,
+    /// Whether the dependency belongs to a group declared `optional = true`
+    pub optional_group: bool,
+}
+
+impl Dependency {
+    /// Starts building a main dependency with the given package name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use uv_migrator::models::{Dependency, DependencyType};
+    ///
+    /// let dep = Dependency::new("requests")
+    ///     .version(">=2.31.0".to_string())
+    ///     .dep_type(DependencyType::Dev)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(dep.name, "requests");
+    /// ```
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(name: impl Into<String>) -> DependencyBuilder {
+        DependencyBuilder {
+            name: name.into(),
+            version: None,
+            dep_type: DependencyType::Main,
+            extras: None,
+            environment_markers: None,
+            optional_group: false,
+        }
+    }
+}
+
+/// Builder for [`Dependency`] that normalizes and validates its fields
+#[derive(Debug, Clone)]
+pub struct DependencyBuilder {
+    name: String,
+    version: Option<String>,
+    dep_type: DependencyType,
+    extras: Option<Vec<String>>,
+    environment_markers: Option<String>,
+    optional_group: bool,
+}
+
+impl DependencyBuilder {
+    pub fn version(mut self, version: impl Into<Option<String>>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    pub fn dep_type(mut self, dep_type: DependencyType) -> Self {
+        self.dep_type = dep_type;
+        self
+    }
+
+    pub fn extras(mut self, extras: impl Into<Option<Vec<String>>>) -> Self {
+        self.extras = extras.into();
+        self
+    }
+
+    pub fn markers(mut self, markers: impl Into<Option<String>>) -> Self {
+        self.environment_markers = markers.into();
+        self
+    }
+
+    pub fn optional_group(mut self, optional_group: bool) -> Self {
+        self.optional_group = optional_group;
+        self
+    }
+
+    /// Validates the collected fields and produces the [`Dependency`].
+    ///
+    /// Surrounding whitespace is trimmed from every field, empty optional values are
+    /// dropped and an empty package name is rejected.
+    pub fn build(self) -> Result<Dependency, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("Dependency name cannot be empty".to_string());
+        }
+
+        let non_empty = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        let extras = self
+            .extras
+            .map(|extras| {
+                extras
+                    .into_iter()
+                    .map(|e| e.trim().to_string())
+                    .filter(|e| !e.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|extras| !extras.is_empty());
+
+        Ok(Dependency {
+            name,
+            version: non_empty(self.version),
+            dep_type: self.dep_type,
+            extras,
+            environment_markers: non_empty(self.environment_markers),
+            optional_group: self.optional_group,
+        })
+    }
+}
+
+/// Combines a `python_version` marker derived from a python constraint with an
+/// explicit marker expression.
+///
+/// Both are joined with `and`, and a side containing `or` is parenthesized so the
+/// combination keeps its meaning. Blank values count as absent.
+///
+/// # Examples
+///
+/// ```
+/// use uv_migrator::models::compose_markers;
+///
+/// assert_eq!(
+///     compose_markers(Some("python_version >= '3.8'"), Some("sys_platform == 'linux'")),
+///     Some("python_version >= '3.8' and sys_platform == 'linux'".to_string())
+/// );
+/// assert_eq!(compose_markers(None, None), None);
+/// ```
+pub fn compose_markers(python: Option<&str>, markers: Option<&str>) -> Option<String> {
+    let clauses: Vec<&str> = [python, markers]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|clause| !clause.is_empty())
+        .collect();
+
+    match clauses.as_slice() {
+        [] => None,
+        [clause] => Some(clause.to_string()),
+        _ => Some(
+            clauses
+                .iter()
+                .map(|clause| {
+                    if clause.contains(" or ") {
+                        format!("({})", clause)
+                    } else {
+                        clause.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" and "),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults() {
+        let dep = Dependency::new("requests").build().unwrap();
+        assert_eq!(dep.name, "requests");
+        assert_eq!(dep.version, None);
+        assert_eq!(dep.dep_type, DependencyType::Main);
+        assert_eq!(dep.extras, None);
+        assert_eq!(dep.environment_markers, None);
+        assert!(!dep.optional_group);
+    }
+
+    #[test]
+    fn test_builder_sets_all_fields() {
+        let dep = Dependency::new("uvicorn")
+            .version("^0.30.1".to_string())
+            .dep_type(DependencyType::Group("web".to_string()))
+            .extras(vec!["standard".to_string()])
+            .markers("sys_platform == 'linux'".to_string())
+            .build()
+            .unwrap();
+
+        assert_eq!(dep.name, "uvicorn");
+        assert_eq!(dep.version, Some("^0.30.1".to_string()));
+        assert_eq!(dep.dep_type, DependencyType::Group("web".to_string()));
+        assert_eq!(dep.extras, Some(vec!["standard".to_string()]));
+        assert_eq!(
+            dep.environment_markers,
+            Some("sys_platform == 'linux'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_builder_normalizes_values() {
+        let dep = Dependency::new("  flask ")
+            .version(" >=2.0 ".to_string())
+            .extras(vec![" async ".to_string(), "".to_string()])
+            .markers(None)
+            .build()
+            .unwrap();
+
+        assert_eq!(dep.name, "flask");
+        assert_eq!(dep.version, Some(">=2.0".to_string()));
+        assert_eq!(dep.extras, Some(vec!["async".to_string()]));
+    }
+
+    #[test]
+    fn test_builder_drops_empty_optionals() {
+        let dep = Dependency::new("flask")
+            .version("  ".to_string())
+            .extras(Vec::new())
+            .markers(String::new())
+            .build()
+            .unwrap();
+
+        assert_eq!(dep.version, None);
+        assert_eq!(dep.extras, None);
+        assert_eq!(dep.environment_markers, None);
+    }
+
+    #[test]
+    fn test_builder_rejects_empty_name() {
+        assert!(Dependency::new("").build().is_err());
+        assert!(Dependency::new("   ").build().is_err());
+    }
+
+    #[test]
+    fn test_compose_markers_both() {
+        assert_eq!(
+            compose_markers(
+                Some("python_version >= '3.8'"),
+                Some("sys_platform == 'linux'")
+            ),
+            Some("python_version >= '3.8' and sys_platform == 'linux'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compose_markers_parenthesizes_or() {
+        assert_eq!(
+            compose_markers(
+                Some("python_version < '3.8' or python_version >= '3.10'"),
+                Some("sys_platform == 'win32' or sys_platform == 'darwin'")
+            ),
+            Some(
+                "(python_version < '3.8' or python_version >= '3.10') and \
+                (sys_platform == 'win32' or sys_platform == 'darwin')"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_compose_markers_only_one() {
+        assert_eq!(
+            compose_markers(Some("python_version >= '3.8'"), None),
+            Some("python_version >= '3.8'".to_string())
+        );
+        assert_eq!(
+            compose_markers(None, Some("sys_platform == 'win32' or os_name == 'nt'")),
+            Some("sys_platform == 'win32' or os_name == 'nt'".to_string())
+        );
+        assert_eq!(
+            compose_markers(Some("  "), Some(" os_name == 'nt' ")),
+            Some("os_name == 'nt'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_compose_markers_neither() {
+        assert_eq!(compose_markers(None, None), None);
+        assert_eq!(compose_markers(Some(""), Some("  ")), None);
+    }
+}
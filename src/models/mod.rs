@@ -0,0 +1,13 @@
+pub mod config;
+pub mod dependency;
+pub mod plan;
+pub mod report;
+pub mod summary;
+
+pub use config::MigrationConfig;
+pub use dependency::{compose_markers, Dependency, DependencyBuilder, DependencyType};
+pub use plan::{
+    hash_source_files, MigrationPlan, PythonSelection, PythonSource, PLAN_FORMAT_VERSION,
+};
+pub use report::{SkippedItem, SkippedKind};
+pub use summary::{DependencyCounts, MigrationReport, MigrationSummary};
@@ -0,0 +1,148 @@
+use crate::cli::Args;
+use crate::migrators::detect::ProjectType;
+use crate::models::{DependencyCounts, SkippedItem};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where the Python version passed to `uv init` came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PythonSource {
+    /// Given with `--python`
+    Override,
+    /// The `python` dependency of a Poetry project
+    PoetryConstraint,
+    /// `requires-python` of a PDM or PEP 621 project
+    RequiresPython,
+    /// The `python` package of a Conda environment
+    CondaEnvironment,
+    /// A version was found but `--no-pin-python` was given
+    NotPinned,
+    /// The project does not declare a Python version
+    #[default]
+    NotFound,
+}
+
+impl fmt::Display for PythonSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PythonSource::Override => "--python",
+            PythonSource::PoetryConstraint => "poetry constraint",
+            PythonSource::RequiresPython => "requires-python",
+            PythonSource::CondaEnvironment => "conda environment",
+            PythonSource::NotPinned => "--no-pin-python",
+            PythonSource::NotFound => "none found",
+        })
+    }
+}
+
+/// The Python version chosen for `uv init`, or `None` when no version is pinned
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PythonSelection {
+    pub version: Option<String>,
+    pub source: PythonSource,
+}
+
+/// Version of the plan file format, bumped when a plan from an older release
+/// can no longer be applied safely
+pub const PLAN_FORMAT_VERSION: u32 = 2;
+
+/// A fully computed migration that can be reviewed before it is applied.
+///
+/// The plan holds the exact `uv` arguments that will run, so applying a saved
+/// plan executes what was reviewed rather than re-detecting the project.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationPlan {
+    /// See [`PLAN_FORMAT_VERSION`]
+    pub format_version: u32,
+    pub project_dir: PathBuf,
+    pub project_type: ProjectType,
+    pub is_package: bool,
+    pub init_args: Vec<String>,
+    /// The Python version passed to `uv init` and where it came from
+    #[serde(default)]
+    pub python: PythonSelection,
+    pub add_args: Vec<Vec<String>>,
+    /// `uv lock` arguments, when the migration ends by locking the project
+    #[serde(default)]
+    pub lock_args: Option<Vec<String>>,
+    pub dependencies: DependencyCounts,
+    pub skipped: Vec<SkippedItem>,
+    pub options: Args,
+    /// SHA-256 of the project's source files when the plan was made, see [`hash_source_files`]
+    pub source_hash: String,
+}
+
+impl MigrationPlan {
+    /// Writes the plan as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let json = self.to_json()?;
+        fs::write(path, json + "\n")
+            .map_err(|e| format!("Failed to write plan {}: {}", path.display(), e))
+    }
+
+    /// Reads a plan previously written with [`MigrationPlan::save`]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read plan {}: {}", path.display(), e))?;
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse plan {}: {}", path.display(), e))?;
+        let version = value.get("format_version").and_then(|v| v.as_u64());
+        if version != Some(u64::from(PLAN_FORMAT_VERSION)) {
+            return Err(format!(
+                "Plan {} was made by another version of uv-migrator (format {}, expected {}); create a new plan",
+                path.display(),
+                version.map_or_else(|| "1".to_string(), |v| v.to_string()),
+                PLAN_FORMAT_VERSION
+            ));
+        }
+        serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse plan {}: {}", path.display(), e))
+    }
+
+    /// Applies the options `apply` honors on the command line: `--dry-run` turns
+    /// the plan into a dry run, even when it was made without one
+    pub fn apply_cli_overrides(&mut self, args: &Args) {
+        if args.dry_run {
+            self.options.dry_run = true;
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| format!("Failed to serialize plan: {}", e))
+    }
+}
+
+/// Hashes the names, relative to `project_dir`, and contents of `files`.
+///
+/// A file that does not exist is hashed as missing, so creating it later, such as
+/// a `Pipfile.lock` or an included requirements file, changes the hash too.
+pub fn hash_source_files(project_dir: &Path, files: &[PathBuf]) -> Result<String, String> {
+    let mut files: Vec<&PathBuf> = files.iter().collect();
+    files.sort();
+    files.dedup();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let name = file.strip_prefix(project_dir).unwrap_or(file);
+        hasher.update(name.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        if file.is_file() {
+            let content =
+                fs::read(file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+            hasher.update([1]);
+            hasher.update((content.len() as u64).to_le_bytes());
+            hasher.update(&content);
+        } else {
+            hasher.update([0]);
+        }
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Category of an item that was left out of the migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkippedKind {
+    /// A package with no PyPI equivalent, such as a Conda system library
+    Package,
+    /// An environment marker that could not be carried over
+    Marker,
+    /// A line of an input file that could not be parsed
+    Line,
+    /// A dependency that was intentionally excluded
+    Dependency,
+    /// A script setting with no PEP 621 equivalent
+    Script,
+}
+
+impl SkippedKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SkippedKind::Package => "package",
+            SkippedKind::Marker => "marker",
+            SkippedKind::Line => "line",
+            SkippedKind::Dependency => "dependency",
+            SkippedKind::Script => "script",
+        }
+    }
+}
+
+impl fmt::Display for SkippedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An item that was skipped during migration together with the reason why
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkippedItem {
+    pub kind: SkippedKind,
+    pub name: String,
+    pub reason: String,
+}
+
+impl SkippedItem {
+    pub fn new(kind: SkippedKind, name: impl Into<String>, reason: impl Into<String>) -> Self {
+        SkippedItem {
+            kind,
+            name: name.into(),
+            reason: reason.into(),
+        }
+    }
+}
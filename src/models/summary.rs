@@ -0,0 +1,70 @@
+use crate::models::{Dependency, DependencyType, PythonSelection, SkippedItem};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Number of migrated dependencies per dependency type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyCounts {
+    pub main: usize,
+    pub dev: usize,
+    pub group: usize,
+    pub optional: usize,
+}
+
+impl DependencyCounts {
+    pub fn from_dependencies(dependencies: &[Dependency]) -> Self {
+        let mut counts = DependencyCounts::default();
+        for dep in dependencies {
+            match dep.dep_type {
+                DependencyType::Main => counts.main += 1,
+                DependencyType::Dev => counts.dev += 1,
+                DependencyType::Group(_) => counts.group += 1,
+                DependencyType::Optional(_) => counts.optional += 1,
+            }
+        }
+        counts
+    }
+}
+
+/// Structured summary of what a migration run did, printed with `--output-format json`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationSummary {
+    pub project_type: String,
+    pub dependencies: DependencyCounts,
+    /// The Python version passed to `uv init`
+    pub python: PythonSelection,
+    /// Entries written to `[tool.uv.sources]`
+    pub sources_pinned: usize,
+    pub tool_sections_copied: Vec<String>,
+    pub files_backed_up: Vec<PathBuf>,
+    /// Packages, markers and lines left out of the migration
+    pub skipped: Vec<SkippedItem>,
+    pub dry_run: bool,
+}
+
+/// The outcome of a migration started through [`crate::migrate`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MigrationReport {
+    /// The detected project type, e.g. `Requirements` or `Poetry(Application)`
+    pub project_type: String,
+    pub dependencies: DependencyCounts,
+    /// The Python version passed to `uv init` and where it came from
+    pub python: PythonSelection,
+    /// Packages, markers and lines left out of the migration
+    pub skipped: Vec<SkippedItem>,
+    pub files_backed_up: Vec<PathBuf>,
+    pub dry_run: bool,
+}
+
+impl From<MigrationSummary> for MigrationReport {
+    fn from(summary: MigrationSummary) -> Self {
+        MigrationReport {
+            project_type: summary.project_type,
+            dependencies: summary.dependencies,
+            python: summary.python,
+            skipped: summary.skipped,
+            files_backed_up: summary.files_backed_up,
+            dry_run: summary.dry_run,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::migrators::setup_cfg::SetupCfg;
 use crate::migrators::setup_py::SetupPyMigrationSource;
 use std::path::Path;
 use toml_edit::DocumentMut;
@@ -9,6 +10,14 @@ pub struct Author {
 }
 
 pub fn extract_authors_from_setup_py(project_dir: &Path) -> Result<Vec<Author>, String> {
+    // setuptools reads metadata from setup.cfg, so its author wins over setup.py
+    if let Some(setup_cfg) = SetupCfg::load(project_dir)? {
+        if let Some(name) = setup_cfg.metadata("author") {
+            let email = setup_cfg.metadata("author_email");
+            return Ok(vec![Author { name, email }]);
+        }
+    }
+
     let setup_py_path = project_dir.join("setup.py");
     if !setup_py_path.exists() {
         return Ok(vec![]);
@@ -20,9 +29,7 @@ pub fn extract_authors_from_setup_py(project_dir: &Path) -> Result<Vec<Author>,
     let mut authors = Vec::new();
 
     // Extract author and author_email from setup()
-    if let Some(start_idx) = content.find("setup(") {
-        let bracket_content = SetupPyMigrationSource::extract_setup_content(&content[start_idx..])?;
-
+    if let Some(bracket_content) = SetupPyMigrationSource::setup_call(&content) {
         if let Some(name) = SetupPyMigrationSource::extract_parameter(&bracket_content, "author") {
             let email = SetupPyMigrationSource::extract_parameter(&bracket_content, "author_email");
             authors.push(Author { name, email });
@@ -1,6 +1,7 @@
+use crate::utils::toml::{read_toml, update_section};
 use log::debug;
 use std::path::Path;
-use toml_edit::{DocumentMut, Item, Table, Value};
+use toml_edit::{Array, DocumentMut, Item, Table, Value};
 
 /// Updates the build system configuration in pyproject.toml
 ///
@@ -8,11 +9,16 @@ use toml_edit::{DocumentMut, Item, Table, Value};
 ///
 /// * `doc` - The TOML document to update
 /// * `project_dir` - The project directory path
+/// * `backend` - The build backend to use, one of [`BUILD_BACKENDS`]
 ///
 /// # Returns
 ///
 /// * `bool` - Whether any changes were made to the document
-pub fn update_build_system(doc: &mut DocumentMut, project_dir: &Path) -> Result<bool, String> {
+pub fn update_build_system(
+    doc: &mut DocumentMut,
+    project_dir: &Path,
+    backend: &str,
+) -> Result<bool, String> {
     debug!("Checking for Poetry build system to migrate");
     let old_pyproject_path = project_dir.join("old.pyproject.toml");
     if !old_pyproject_path.exists() {
@@ -55,7 +61,33 @@ pub fn update_build_system(doc: &mut DocumentMut, project_dir: &Path) -> Result<
         return Ok(false);
     }
 
-    debug!("Converting Poetry build system to Hatchling");
+    debug!("Converting Poetry build system to {}", backend);
+    set_build_system(doc, backend)?;
+
+    Ok(true)
+}
+
+/// Names accepted by `--build-backend`
+pub const BUILD_BACKENDS: &[&str] = &["hatchling", "setuptools", "flit", "pdm-backend"];
+
+/// The `requires` entry and `build-backend` value of a backend from [`BUILD_BACKENDS`]
+pub fn build_backend_spec(backend: &str) -> Result<(&'static str, &'static str), String> {
+    match backend {
+        "hatchling" => Ok(("hatchling", "hatchling.build")),
+        "setuptools" => Ok(("setuptools", "setuptools.build_meta")),
+        "flit" => Ok(("flit_core", "flit_core.buildapi")),
+        "pdm-backend" => Ok(("pdm-backend", "pdm.backend")),
+        _ => Err(format!(
+            "unsupported build backend '{}': expected one of {}",
+            backend,
+            BUILD_BACKENDS.join(", ")
+        )),
+    }
+}
+
+/// Replaces the `[build-system]` table with one that builds with the given backend
+pub fn set_build_system(doc: &mut DocumentMut, backend: &str) -> Result<(), String> {
+    let (requirement, build_backend) = build_backend_spec(backend)?;
 
     // Create new build-system table
     let mut build_system = Table::new();
@@ -63,7 +95,7 @@ pub fn update_build_system(doc: &mut DocumentMut, project_dir: &Path) -> Result<
     // Add requires array
     let mut requires = toml_edit::Array::new();
     requires.push(Value::String(toml_edit::Formatted::new(
-        "hatchling".to_string(),
+        requirement.to_string(),
     )));
     build_system.insert("requires", Item::Value(Value::Array(requires)));
 
@@ -71,14 +103,153 @@ pub fn update_build_system(doc: &mut DocumentMut, project_dir: &Path) -> Result<
     build_system.insert(
         "build-backend",
         Item::Value(Value::String(toml_edit::Formatted::new(
-            "hatchling.build".to_string(),
+            build_backend.to_string(),
         ))),
     );
 
     // Update the document
     doc.insert("build-system", Item::Table(build_system));
+    Ok(())
+}
 
-    Ok(true)
+/// Extra files of one Hatchling build target
+#[derive(Default)]
+struct TargetIncludes {
+    /// Paths copied to the same location in the archive
+    force_include: Table,
+    /// Glob patterns of additional files
+    artifacts: Array,
+}
+
+impl TargetIncludes {
+    fn push(&mut self, path: &str) {
+        if path.contains(['*', '?', '[']) {
+            self.artifacts.push(path);
+        } else {
+            self.force_include.insert(path, toml_edit::value(path));
+        }
+    }
+}
+
+/// Migrates Poetry `include` and `exclude` entries to Hatchling build target configuration
+///
+/// Include entries may be plain strings or tables with a `path` and an optional `format`
+/// (`"sdist"`, `"wheel"` or a list of both). Each entry is routed to the matching
+/// `[tool.hatch.build.targets.<format>]` table, defaulting to both targets when no
+/// format is given. Exclude patterns apply to both targets.
+///
+/// Poetry includes add files on top of the package, whereas Hatch's `include`
+/// replaces the file selection. Plain paths therefore go to `force-include` at the
+/// same location, and glob patterns to `artifacts`.
+///
+/// # Arguments
+///
+/// * `doc` - The TOML document to update
+/// * `project_dir` - The project directory path
+///
+/// # Returns
+///
+/// * `bool` - Whether any changes were made to the document
+pub fn update_hatch_includes(doc: &mut DocumentMut, project_dir: &Path) -> Result<bool, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(false);
+    }
+
+    let old_doc = read_toml(&old_pyproject_path)?;
+    let poetry = old_doc.get("tool").and_then(|t| t.get("poetry"));
+    let includes = poetry
+        .and_then(|p| p.get("include"))
+        .and_then(|i| i.as_array());
+    let excludes = poetry
+        .and_then(|p| p.get("exclude"))
+        .and_then(|e| e.as_array());
+    if includes.is_none() && excludes.is_none() {
+        return Ok(false);
+    }
+
+    let mut sdist = TargetIncludes::default();
+    let mut wheel = TargetIncludes::default();
+
+    for entry in includes.into_iter().flatten() {
+        let (path, formats) = match entry {
+            Value::String(s) => (s.value().to_string(), vec![]),
+            Value::InlineTable(t) => {
+                let Some(path) = t.get("path").and_then(|p| p.as_str()) else {
+                    debug!("Skipping include entry without a path: {}", entry);
+                    continue;
+                };
+                let formats = match t.get("format") {
+                    Some(Value::String(f)) => vec![f.value().to_string()],
+                    Some(Value::Array(arr)) => arr
+                        .iter()
+                        .filter_map(|f| f.as_str().map(|f| f.to_string()))
+                        .collect(),
+                    _ => vec![],
+                };
+                (path.to_string(), formats)
+            }
+            _ => {
+                debug!("Skipping unsupported include entry: {}", entry);
+                continue;
+            }
+        };
+
+        let to_sdist = formats.is_empty() || formats.iter().any(|f| f == "sdist");
+        let to_wheel = formats.is_empty() || formats.iter().any(|f| f == "wheel");
+
+        if to_sdist {
+            debug!("Routing include '{}' to sdist target", path);
+            sdist.push(&path);
+        }
+        if to_wheel {
+            debug!("Routing include '{}' to wheel target", path);
+            wheel.push(&path);
+        }
+    }
+
+    let exclude: Array = excludes
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let pattern = entry.as_str();
+            if pattern.is_none() {
+                debug!("Skipping unsupported exclude entry: {}", entry);
+            }
+            pattern
+        })
+        .collect();
+
+    let mut changed = false;
+    for (target, includes) in [("sdist", sdist), ("wheel", wheel)] {
+        if !includes.force_include.is_empty() {
+            update_section(
+                doc,
+                &["tool", "hatch", "build", "targets", target, "force-include"],
+                Item::Table(includes.force_include),
+            );
+            changed = true;
+        }
+        if !includes.artifacts.is_empty() {
+            update_section(
+                doc,
+                &["tool", "hatch", "build", "targets", target, "artifacts"],
+                Item::Value(Value::Array(includes.artifacts)),
+            );
+            changed = true;
+        }
+        if !exclude.is_empty() {
+            debug!("Routing excludes to {} target", target);
+            update_section(
+                doc,
+                &["tool", "hatch", "build", "targets", target, "exclude"],
+                Item::Value(Value::Array(exclude.clone())),
+            );
+            changed = true;
+        }
+    }
+
+    Ok(changed)
 }
 
 #[cfg(test)]
@@ -122,7 +293,7 @@ version = "0.1.0"
 
         let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
 
-        let result = update_build_system(&mut doc, &project_dir).unwrap();
+        let result = update_build_system(&mut doc, &project_dir, "hatchling").unwrap();
         assert!(result);
 
         let build_system = doc.get("build-system").unwrap();
@@ -159,7 +330,7 @@ version = "0.1.0"
 
         let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
 
-        let result = update_build_system(&mut doc, &project_dir).unwrap();
+        let result = update_build_system(&mut doc, &project_dir, "hatchling").unwrap();
         assert!(result);
 
         let build_system = doc.get("build-system").unwrap();
@@ -196,7 +367,7 @@ version = "0.1.0"
 
         let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
 
-        let result = update_build_system(&mut doc, &project_dir).unwrap();
+        let result = update_build_system(&mut doc, &project_dir, "hatchling").unwrap();
         assert!(result);
 
         let build_system = doc.get("build-system").unwrap();
@@ -228,7 +399,7 @@ version = "0.1.0"
 
         let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
 
-        let result = update_build_system(&mut doc, &project_dir).unwrap();
+        let result = update_build_system(&mut doc, &project_dir, "hatchling").unwrap();
         assert!(!result);
     }
 
@@ -243,7 +414,214 @@ version = "0.1.0"
         let temp_dir = TempDir::new().unwrap();
         let mut doc = new_content.parse::<DocumentMut>().unwrap();
 
-        let result = update_build_system(&mut doc, temp_dir.path()).unwrap();
+        let result = update_build_system(&mut doc, temp_dir.path(), "hatchling").unwrap();
+        assert!(!result);
+    }
+
+    /// The `force-include` paths of a target, checking each maps to the same path
+    fn target_force_includes(doc: &DocumentMut, target: &str) -> Vec<String> {
+        doc["tool"]["hatch"]["build"]["targets"][target]["force-include"]
+            .as_table()
+            .unwrap()
+            .iter()
+            .map(|(source, destination)| {
+                assert_eq!(destination.as_str(), Some(source));
+                source.to_string()
+            })
+            .collect()
+    }
+
+    fn target_artifacts(doc: &DocumentMut, target: &str) -> Vec<String> {
+        doc["tool"]["hatch"]["build"]["targets"][target]["artifacts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_format_specific_includes_are_routed() {
+        let old_content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+include = [
+    "CHANGELOG.md",
+    { path = "tests", format = "sdist" },
+    { path = "data/*.json", format = "wheel" },
+    { path = "extra/*.txt", format = ["sdist", "wheel"] },
+    { path = "docs" },
+]
+"#;
+
+        let new_content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+        let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
+
+        let result = update_hatch_includes(&mut doc, &project_dir).unwrap();
+        assert!(result);
+
+        assert_eq!(
+            target_force_includes(&doc, "sdist"),
+            vec!["CHANGELOG.md", "tests", "docs"]
+        );
+        assert_eq!(target_artifacts(&doc, "sdist"), vec!["extra/*.txt"]);
+        assert_eq!(
+            target_force_includes(&doc, "wheel"),
+            vec!["CHANGELOG.md", "docs"]
+        );
+        assert_eq!(
+            target_artifacts(&doc, "wheel"),
+            vec!["data/*.json", "extra/*.txt"]
+        );
+        for target in ["sdist", "wheel"] {
+            assert!(doc["tool"]["hatch"]["build"]["targets"][target]
+                .get("include")
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_excludes_apply_to_both_targets() {
+        let old_content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+include = ["CHANGELOG.md"]
+exclude = ["src/test_project/fixtures/*", "**/*.bak"]
+"#;
+
+        let new_content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+        let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
+
+        assert!(update_hatch_includes(&mut doc, &project_dir).unwrap());
+
+        for target in ["sdist", "wheel"] {
+            assert_eq!(target_force_includes(&doc, target), vec!["CHANGELOG.md"]);
+            let excludes: Vec<&str> = doc["tool"]["hatch"]["build"]["targets"][target]["exclude"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v.as_str().unwrap())
+                .collect();
+            assert_eq!(excludes, vec!["src/test_project/fixtures/*", "**/*.bak"]);
+        }
+    }
+
+    #[test]
+    fn test_includes_keep_package_selection() {
+        let old_content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+packages = [{ include = "test_project" }]
+include = ["CHANGELOG.md"]
+"#;
+
+        let new_content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+        let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
+
+        assert!(update_hatch_includes(&mut doc, &project_dir).unwrap());
+
+        // An `include` list would select only CHANGELOG.md and drop the package code
+        for target in ["sdist", "wheel"] {
+            let table = doc["tool"]["hatch"]["build"]["targets"][target]
+                .as_table()
+                .unwrap();
+            assert!(table.get("include").is_none());
+            assert!(table.get("only-include").is_none());
+            assert_eq!(target_force_includes(&doc, target), vec!["CHANGELOG.md"]);
+        }
+        assert!(doc.to_string().contains(
+            "[tool.hatch.build.targets.wheel.force-include]\n\"CHANGELOG.md\" = \"CHANGELOG.md\""
+        ));
+    }
+
+    #[test]
+    fn test_no_includes_leaves_document_untouched() {
+        let old_content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+        let new_content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+        let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
+
+        let result = update_hatch_includes(&mut doc, &project_dir).unwrap();
         assert!(!result);
+        assert!(doc.get("tool").is_none());
+    }
+
+    #[test]
+    fn test_poetry_to_flit_conversion() {
+        let old_content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[build-system]
+requires = ["poetry-core"]
+build-backend = "poetry.core.masonry.api"
+"#;
+
+        let new_content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+"#;
+
+        let (_temp_dir, mut doc, project_dir) = setup_test_environment(old_content, new_content);
+
+        assert!(update_build_system(&mut doc, &project_dir, "flit").unwrap());
+
+        let build_system = doc.get("build-system").unwrap();
+        let requires = build_system.get("requires").unwrap().as_array().unwrap();
+        assert_eq!(requires.get(0).unwrap().as_str().unwrap(), "flit_core");
+        let backend = build_system.get("build-backend").unwrap().as_str().unwrap();
+        assert_eq!(backend, "flit_core.buildapi");
+    }
+
+    #[test]
+    fn test_pdm_backend_build_system() {
+        let mut doc = "[project]\nname = \"test-project\"\n"
+            .parse::<DocumentMut>()
+            .unwrap();
+
+        set_build_system(&mut doc, "pdm-backend").unwrap();
+
+        let build_system = doc.get("build-system").unwrap();
+        let requires = build_system.get("requires").unwrap().as_array().unwrap();
+        assert_eq!(requires.get(0).unwrap().as_str().unwrap(), "pdm-backend");
+        let backend = build_system.get("build-backend").unwrap().as_str().unwrap();
+        assert_eq!(backend, "pdm.backend");
+    }
+
+    #[test]
+    fn test_unsupported_build_backend() {
+        let mut doc = DocumentMut::new();
+        let err = set_build_system(&mut doc, "maturin").unwrap_err();
+        assert!(err.contains("unsupported build backend 'maturin'"));
+        assert!(doc.get("build-system").is_none());
     }
 }
@@ -19,6 +19,10 @@ pub struct FileChange {
 
 pub struct FileTracker {
     pub(crate) changes: HashMap<PathBuf, FileChange>,
+    /// Contents of files deleted during the migration, restored on rollback
+    pub(crate) deleted: HashMap<PathBuf, Vec<u8>>,
+    /// Files and directories that did not exist before the migration, removed on rollback
+    pub(crate) created: Vec<PathBuf>,
 }
 
 impl Default for FileTracker {
@@ -31,6 +35,8 @@ impl FileTracker {
     pub fn new() -> Self {
         FileTracker {
             changes: HashMap::new(),
+            deleted: HashMap::new(),
+            created: Vec::new(),
         }
     }
 
@@ -83,6 +89,31 @@ impl FileTracker {
         Ok(())
     }
 
+    pub fn track_delete(&mut self, path: &Path) -> Result<(), String> {
+        debug!("Attempting to track deletion of '{}'", path.display());
+        let content = fs::read(path).map_err(|e| {
+            format!(
+                "Failed to read file '{}' for tracking: {}",
+                path.display(),
+                e
+            )
+        })?;
+        self.deleted.insert(path.to_path_buf(), content);
+        info!("Successfully tracked deletion of '{}'", path.display());
+        Ok(())
+    }
+
+    /// Registers a file or directory that the migration created, so a rollback removes it.
+    ///
+    /// Only paths known not to have existed before the migration may be registered.
+    pub fn track_created_file(&mut self, path: &Path) -> Result<(), String> {
+        if !self.created.iter().any(|created| created == path) {
+            debug!("Tracking created path: {}", path.display());
+            self.created.push(path.to_path_buf());
+        }
+        Ok(())
+    }
+
     pub(crate) fn ensure_parent_dir_exists(path: &Path) -> Result<(), String> {
         if let Some(parent) = path.parent() {
             if !parent.exists() {
@@ -100,7 +131,7 @@ impl FileTracker {
     }
 
     pub fn rollback(&self) -> Result<(), String> {
-        if self.changes.is_empty() {
+        if self.changes.is_empty() && self.deleted.is_empty() && self.created.is_empty() {
             info!("No changes to roll back");
             return Ok(());
         }
@@ -111,6 +142,26 @@ impl FileTracker {
             fs::remove_file(pyproject_path)
                 .map_err(|e| format!("Failed to remove pyproject.toml: {}", e))?;
         }
+        for (path, content) in &self.deleted {
+            info!("Restoring deleted file: {}", path.display());
+            Self::ensure_parent_dir_exists(path)?;
+            fs::write(path, content)
+                .map_err(|e| format!("Failed to restore '{}': {}", path.display(), e))?;
+        }
+        for path in &self.created {
+            info!("Removing created path: {}", path.display());
+            let removed = if path.is_dir() {
+                fs::remove_dir_all(path)
+            } else if path.exists() {
+                fs::remove_file(path)
+            } else {
+                Ok(())
+            };
+            removed.map_err(|e| format!("Failed to remove '{}': {}", path.display(), e))?;
+        }
+        if self.changes.is_empty() {
+            return Ok(());
+        }
         info!("Phase 2: Restoring original pyproject.toml");
         for change in self.changes.values() {
             if let FileAction::Renamed {
@@ -138,6 +189,7 @@ pub struct FileTrackerGuard {
     tracker: FileTracker,
     should_rollback: bool,
     has_performed_rollback: bool,
+    dry_run: bool,
 }
 
 impl Default for FileTrackerGuard {
@@ -152,19 +204,52 @@ impl FileTrackerGuard {
             tracker: FileTracker::new(),
             should_rollback: false,
             has_performed_rollback: false,
+            dry_run: false,
+        }
+    }
+
+    /// Creates a guard that tracks nothing and never rolls back, for `--dry-run`
+    pub fn new_dry_run() -> Self {
+        FileTrackerGuard {
+            tracker: FileTracker::new(),
+            should_rollback: false,
+            has_performed_rollback: false,
+            dry_run: true,
         }
     }
 
     pub fn track_file(&mut self, path: &Path) -> Result<(), String> {
+        if self.dry_run {
+            return Ok(());
+        }
         self.tracker.track_file(path)
     }
 
     pub fn track_rename(&mut self, from: &Path, to: &Path) -> Result<(), String> {
+        if self.dry_run {
+            return Ok(());
+        }
         self.tracker.track_rename(from, to)
     }
 
+    pub fn track_delete(&mut self, path: &Path) -> Result<(), String> {
+        if self.dry_run {
+            return Ok(());
+        }
+        self.tracker.track_delete(path)
+    }
+
+    pub fn track_created_file(&mut self, path: &Path) -> Result<(), String> {
+        if self.dry_run {
+            return Ok(());
+        }
+        self.tracker.track_created_file(path)
+    }
+
     pub fn force_rollback(&mut self) {
-        self.should_rollback = true;
+        if !self.dry_run {
+            self.should_rollback = true;
+        }
     }
 
     fn perform_rollback(&mut self) {
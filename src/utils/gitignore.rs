@@ -0,0 +1,99 @@
+use log::{debug, info};
+use std::fs;
+use std::path::Path;
+
+/// Entries every UV-managed project should ignore
+const UV_GITIGNORE_ENTRIES: &[&str] = &[
+    ".venv/",
+    "__pycache__/",
+    "*.py[cod]",
+    "build/",
+    "dist/",
+    "*.egg-info/",
+];
+
+/// Appends UV-relevant entries to `.gitignore`, creating the file if needed.
+///
+/// Entries that are already present (ignoring a trailing `/`) are not duplicated,
+/// so running this repeatedly leaves the file unchanged.
+///
+/// # Returns
+///
+/// * `bool` - Whether the file was created or modified
+pub fn update_gitignore(project_dir: &Path) -> Result<bool, String> {
+    let gitignore_path = project_dir.join(".gitignore");
+    let existing = if gitignore_path.exists() {
+        fs::read_to_string(&gitignore_path)
+            .map_err(|e| format!("Failed to read .gitignore: {}", e))?
+    } else {
+        String::new()
+    };
+
+    let normalize = |entry: &str| entry.trim().trim_end_matches('/').to_string();
+    let present: Vec<String> = existing.lines().map(normalize).collect();
+
+    let missing: Vec<&str> = UV_GITIGNORE_ENTRIES
+        .iter()
+        .copied()
+        .filter(|entry| !present.contains(&normalize(entry)))
+        .collect();
+
+    if missing.is_empty() {
+        debug!(".gitignore already contains all UV entries");
+        return Ok(false);
+    }
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !content.is_empty() {
+        content.push('\n');
+    }
+    content.push_str("# uv\n");
+    for entry in &missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+
+    fs::write(&gitignore_path, content)
+        .map_err(|e| format!("Failed to write .gitignore: {}", e))?;
+    info!("Added {} entries to .gitignore", missing.len());
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_creates_gitignore() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert!(update_gitignore(temp_dir.path()).unwrap());
+
+        let content = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+        for entry in UV_GITIGNORE_ENTRIES {
+            assert!(content.lines().any(|l| l == *entry), "missing {}", entry);
+        }
+    }
+
+    #[test]
+    fn test_appends_only_missing_entries_idempotently() {
+        let temp_dir = TempDir::new().unwrap();
+        let gitignore_path = temp_dir.path().join(".gitignore");
+        fs::write(&gitignore_path, "node_modules/\n.venv\n").unwrap();
+
+        assert!(update_gitignore(temp_dir.path()).unwrap());
+        let first = fs::read_to_string(&gitignore_path).unwrap();
+
+        assert!(!update_gitignore(temp_dir.path()).unwrap());
+        let second = fs::read_to_string(&gitignore_path).unwrap();
+
+        assert_eq!(first, second);
+        assert!(first.starts_with("node_modules/\n.venv\n"));
+        assert!(!first.contains(".venv/"));
+        assert_eq!(first.matches("__pycache__/").count(), 1);
+    }
+}
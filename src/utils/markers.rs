@@ -0,0 +1,253 @@
+/// Environment marker variables defined by PEP 508
+const MARKER_VARIABLES: &[&str] = &[
+    "python_version",
+    "python_full_version",
+    "os_name",
+    "sys_platform",
+    "platform_release",
+    "platform_system",
+    "platform_version",
+    "platform_machine",
+    "platform_python_implementation",
+    "implementation_name",
+    "implementation_version",
+    "extra",
+];
+
+const COMPARISON_OPERATORS: &[&str] = &["===", "==", "!=", "<=", ">=", "~=", "<", ">"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Variable(String),
+    String(String),
+    Operator(String),
+    And,
+    Or,
+    Not,
+    In,
+    OpenParen,
+    CloseParen,
+}
+
+/// Checks that `marker` is a valid PEP 508 environment marker expression.
+///
+/// Returns a description of the first problem found, such as an unknown variable,
+/// an unterminated string or a missing operator.
+pub fn validate_marker(marker: &str) -> Result<(), String> {
+    let tokens = tokenize(marker)?;
+    if tokens.is_empty() {
+        return Err("marker is empty".to_string());
+    }
+
+    let mut parser = Parser { tokens, pos: 0 };
+    parser.parse_or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(()),
+        Some(token) => Err(format!("unexpected {}", describe(token))),
+    }
+}
+
+fn tokenize(marker: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = marker.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::OpenParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::CloseParen);
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut value = String::new();
+                let mut terminated = false;
+                for (_, ch) in chars.by_ref() {
+                    if ch == c {
+                        terminated = true;
+                        break;
+                    }
+                    value.push(ch);
+                }
+                if !terminated {
+                    return Err(format!(
+                        "unterminated string starting at position {}",
+                        start
+                    ));
+                }
+                tokens.push(Token::String(value));
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut word = String::new();
+                while let Some(&(_, ch)) = chars.peek() {
+                    if ch.is_ascii_alphanumeric() || ch == '_' || ch == '.' {
+                        word.push(ch);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(match word.as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "not" => Token::Not,
+                    "in" => Token::In,
+                    _ if MARKER_VARIABLES.contains(&word.as_str()) => Token::Variable(word),
+                    _ => return Err(format!("unknown marker variable '{}'", word)),
+                });
+            }
+            _ => {
+                let rest = &marker[start..];
+                let Some(op) = COMPARISON_OPERATORS
+                    .iter()
+                    .find(|op| rest.starts_with(**op))
+                else {
+                    return Err(format!("unexpected character '{}'", c));
+                };
+                for _ in 0..op.len() {
+                    chars.next();
+                }
+                tokens.push(Token::Operator(op.to_string()));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Variable(name) => format!("variable '{}'", name),
+        Token::String(value) => format!("string '{}'", value),
+        Token::Operator(op) => format!("operator '{}'", op),
+        Token::And => "'and'".to_string(),
+        Token::Or => "'or'".to_string(),
+        Token::Not => "'not'".to_string(),
+        Token::In => "'in'".to_string(),
+        Token::OpenParen => "'('".to_string(),
+        Token::CloseParen => "')'".to_string(),
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> Result<(), String> {
+        self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            self.parse_and()?;
+        }
+        Ok(())
+    }
+
+    fn parse_and(&mut self) -> Result<(), String> {
+        self.parse_expression()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            self.parse_expression()?;
+        }
+        Ok(())
+    }
+
+    fn parse_expression(&mut self) -> Result<(), String> {
+        if self.peek() == Some(&Token::OpenParen) {
+            self.pos += 1;
+            self.parse_or()?;
+            return match self.next() {
+                Some(Token::CloseParen) => Ok(()),
+                Some(token) => Err(format!("expected ')' but found {}", describe(&token))),
+                None => Err("missing closing ')'".to_string()),
+            };
+        }
+
+        self.parse_value()?;
+        match self.next() {
+            Some(Token::Operator(_)) | Some(Token::In) => {}
+            Some(Token::Not) => {
+                if self.next() != Some(Token::In) {
+                    return Err("expected 'in' after 'not'".to_string());
+                }
+            }
+            Some(token) => {
+                return Err(format!(
+                    "expected a comparison operator but found {}",
+                    describe(&token)
+                ))
+            }
+            None => return Err("expected a comparison operator".to_string()),
+        }
+        self.parse_value()
+    }
+
+    fn parse_value(&mut self) -> Result<(), String> {
+        match self.next() {
+            Some(Token::Variable(_)) | Some(Token::String(_)) => Ok(()),
+            Some(token) => Err(format!(
+                "expected a variable or quoted string but found {}",
+                describe(&token)
+            )),
+            None => Err("expected a variable or quoted string".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_markers() {
+        for marker in [
+            "python_version >= '3.8'",
+            "sys_platform == \"win32\" and platform_machine == 'x86_64'",
+            "(os_name == 'nt' or os_name == 'posix') and extra == 'test'",
+            "'linux' in sys_platform",
+            "platform_system not in 'Windows Darwin'",
+        ] {
+            assert!(
+                validate_marker(marker).is_ok(),
+                "{} should be valid",
+                marker
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_markers() {
+        for marker in [
+            "",
+            "python_version >= 3.8",
+            "python_version >= '3.8",
+            "python_version '3.8'",
+            "platform == 'linux'",
+            "(sys_platform == 'win32'",
+            "sys_platform == 'win32' and",
+        ] {
+            assert!(
+                validate_marker(marker).is_err(),
+                "{} should be invalid",
+                marker
+            );
+        }
+    }
+}
@@ -1,7 +1,13 @@
 pub mod author;
 pub mod file_tracker;
+pub mod gitignore;
+pub mod markers;
+pub mod package_source;
 pub mod pip;
+pub mod pycache;
 pub mod pyproject;
+pub mod report;
+pub mod specifiers;
 pub mod toml;
 #[cfg(feature = "self_update")]
 mod update;
@@ -13,6 +19,7 @@ pub use pip::parse_pip_conf;
 pub use pyproject::update_pyproject_toml;
 pub use pyproject::update_url;
 pub use uv::check_uv_requirements;
+pub use uv::UvCommandBuilder;
 pub mod build_system;
 
 #[cfg(feature = "self_update")]
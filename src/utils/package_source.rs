@@ -0,0 +1,204 @@
+use log::{debug, info};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files that `uv init --package` scaffolds inside `src/<name>/`
+const SCAFFOLD_FILES: &[&str] = &["__init__.py", "py.typed"];
+
+/// Snapshot of the package source tree under `src/` taken before `uv init`.
+///
+/// `uv init --package` scaffolds `src/<name>/__init__.py` and `src/<name>/py.typed`.
+/// When the project already ships its own package sources, including namespace
+/// packages without an `__init__.py`, the snapshot is used to put the user's files
+/// back and to drop only the stub files created by the scaffold.
+#[derive(Debug, Default)]
+pub struct PackageSourceSnapshot {
+    /// Package directories that existed under `src/`
+    package_dirs: HashSet<PathBuf>,
+    /// Scaffold-relevant files that existed, with their contents
+    files: HashMap<PathBuf, Vec<u8>>,
+}
+
+impl PackageSourceSnapshot {
+    /// Records the package directories and scaffold-relevant files that already
+    /// exist under `src/`
+    pub fn capture(project_dir: &Path) -> Result<Self, String> {
+        let package_dirs: HashSet<PathBuf> = package_dirs(project_dir)?.into_iter().collect();
+        let mut files = HashMap::new();
+
+        for package_dir in &package_dirs {
+            for file_name in SCAFFOLD_FILES {
+                let path = package_dir.join(file_name);
+                if path.is_file() {
+                    let content = fs::read(&path)
+                        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                    debug!("Recorded existing package file: {}", path.display());
+                    files.insert(path, content);
+                }
+            }
+        }
+
+        Ok(PackageSourceSnapshot {
+            package_dirs,
+            files,
+        })
+    }
+
+    /// Whether the project had a package source tree before `uv init`
+    pub fn has_existing_sources(&self) -> bool {
+        !self.package_dirs.is_empty()
+    }
+
+    /// Lists the scaffold files that did not exist when the snapshot was taken
+    pub fn created_stubs(&self, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut created = Vec::new();
+        for package_dir in package_dirs(project_dir)? {
+            for file_name in SCAFFOLD_FILES {
+                let path = package_dir.join(file_name);
+                if path.is_file() && !self.files.contains_key(&path) {
+                    created.push(path);
+                }
+            }
+        }
+        Ok(created)
+    }
+
+    /// Restores user package files and removes freshly scaffolded stubs
+    pub fn restore(&self, project_dir: &Path) -> Result<(), String> {
+        if !self.has_existing_sources() {
+            return Ok(());
+        }
+
+        for (path, content) in &self.files {
+            let current = fs::read(path).ok();
+            if current.as_deref() != Some(content.as_slice()) {
+                fs::write(path, content)
+                    .map_err(|e| format!("Failed to restore {}: {}", path.display(), e))?;
+                info!("Restored existing package file: {}", path.display());
+            }
+        }
+
+        for path in self.created_stubs(project_dir)? {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove scaffolded {}: {}", path.display(), e))?;
+            info!("Removed scaffolded stub: {}", path.display());
+        }
+
+        for package_dir in package_dirs(project_dir)? {
+            if self.package_dirs.contains(&package_dir) {
+                continue;
+            }
+            let is_empty = fs::read_dir(&package_dir)
+                .map(|mut entries| entries.next().is_none())
+                .unwrap_or(false);
+            if is_empty {
+                fs::remove_dir(&package_dir).map_err(|e| {
+                    format!(
+                        "Failed to remove scaffolded directory {}: {}",
+                        package_dir.display(),
+                        e
+                    )
+                })?;
+                debug!(
+                    "Removed empty scaffold directory: {}",
+                    package_dir.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Lists the package directories directly under `src/`
+fn package_dirs(project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let src_dir = project_dir.join("src");
+    if !src_dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let entries = fs::read_dir(&src_dir)
+        .map_err(|e| format!("Failed to read {}: {}", src_dir.display(), e))?;
+
+    Ok(entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_existing_package_survives_scaffold() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path();
+        let pkg_dir = project_dir.join("src").join("pkg");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(pkg_dir.join("__init__.py"), "from .core import run\n").unwrap();
+
+        let snapshot = PackageSourceSnapshot::capture(project_dir).unwrap();
+        assert!(snapshot.has_existing_sources());
+
+        // Simulate `uv init --package` overwriting and scaffolding stubs
+        fs::write(pkg_dir.join("__init__.py"), "def hello(): ...\n").unwrap();
+        fs::write(pkg_dir.join("py.typed"), "").unwrap();
+        let stub_dir = project_dir.join("src").join("other_pkg");
+        fs::create_dir_all(&stub_dir).unwrap();
+        fs::write(stub_dir.join("__init__.py"), "def hello(): ...\n").unwrap();
+
+        snapshot.restore(project_dir).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(pkg_dir.join("__init__.py")).unwrap(),
+            "from .core import run\n"
+        );
+        assert!(!pkg_dir.join("py.typed").exists());
+        assert!(!stub_dir.exists());
+    }
+
+    #[test]
+    fn test_namespace_package_loses_scaffolded_stubs() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path();
+        let ns_dir = project_dir.join("src").join("ns");
+        fs::create_dir_all(&ns_dir).unwrap();
+        fs::write(ns_dir.join("core.py"), "def run(): ...\n").unwrap();
+
+        let snapshot = PackageSourceSnapshot::capture(project_dir).unwrap();
+        assert!(snapshot.has_existing_sources());
+
+        fs::write(ns_dir.join("__init__.py"), "def hello(): ...\n").unwrap();
+        fs::write(ns_dir.join("py.typed"), "").unwrap();
+        assert_eq!(snapshot.created_stubs(project_dir).unwrap().len(), 2);
+
+        snapshot.restore(project_dir).unwrap();
+
+        assert!(!ns_dir.join("__init__.py").exists());
+        assert!(!ns_dir.join("py.typed").exists());
+        assert!(ns_dir.join("core.py").exists());
+    }
+
+    #[test]
+    fn test_fresh_project_keeps_scaffold() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = temp_dir.path();
+
+        let snapshot = PackageSourceSnapshot::capture(project_dir).unwrap();
+        assert!(!snapshot.has_existing_sources());
+
+        let pkg_dir = project_dir.join("src").join("pkg");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(pkg_dir.join("__init__.py"), "def hello(): ...\n").unwrap();
+        fs::write(pkg_dir.join("py.typed"), "").unwrap();
+
+        snapshot.restore(project_dir).unwrap();
+
+        assert!(pkg_dir.join("__init__.py").exists());
+        assert!(pkg_dir.join("py.typed").exists());
+    }
+}
@@ -1,29 +1,156 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use crate::migrators::setup_cfg::SetupCfg;
+use log::debug;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
 
+/// pip.conf sections whose index settings apply to `pip install`
+const PIP_CONF_SECTIONS: &[&str] = &["global", "install"];
+
+/// Reads the index URLs from the user's pip configuration.
+///
+/// The file named by `PIP_CONFIG_FILE` is used when that variable is set,
+/// otherwise `~/.pip/pip.conf`. See [`parse_pip_conf_content`] for what is read.
 pub fn parse_pip_conf() -> Result<Vec<String>, String> {
-    let home_dir =
-        dirs::home_dir().ok_or_else(|| "Unable to determine home directory".to_string())?;
-    let pip_conf_path = home_dir.join(".pip").join("pip.conf");
+    let pip_conf_path = match env::var_os("PIP_CONFIG_FILE") {
+        Some(path) => PathBuf::from(path),
+        None => dirs::home_dir()
+            .ok_or_else(|| "Unable to determine home directory".to_string())?
+            .join(".pip")
+            .join("pip.conf"),
+    };
+    parse_pip_conf_at(&pip_conf_path)
+}
 
+/// Reads the index URLs from the pip configuration at `pip_conf_path`, returning
+/// none when the file does not exist.
+pub fn parse_pip_conf_at(pip_conf_path: &Path) -> Result<Vec<String>, String> {
     if !pip_conf_path.exists() {
+        debug!("No pip configuration found at {}", pip_conf_path.display());
         return Ok(vec![]);
     }
 
-    let file = File::open(&pip_conf_path).map_err(|e| format!("Failed to open pip.conf: {}", e))?;
-    let reader = BufReader::new(file);
-
-    let mut extra_urls = vec![];
-    for line in reader.lines() {
-        let line = line.map_err(|e| format!("Failed to read line from pip.conf: {}", e))?;
-        let trimmed = line.trim();
-        if trimmed.starts_with("extra-index-url") {
-            let parts: Vec<&str> = trimmed.splitn(2, '=').collect();
-            if parts.len() == 2 {
-                extra_urls.push(parts[1].trim().to_string());
+    let content = fs::read_to_string(pip_conf_path)
+        .map_err(|e| format!("Failed to read {}: {}", pip_conf_path.display(), e))?;
+    Ok(parse_pip_conf_content(&content))
+}
+
+/// Collects `index-url` and `extra-index-url` from the `[global]` and `[install]`
+/// sections of a pip configuration.
+///
+/// Values may list several URLs separated by whitespace or on continuation
+/// lines. Each URL is returned once, in the order it first appears.
+pub fn parse_pip_conf_content(content: &str) -> Vec<String> {
+    let config = SetupCfg::parse(content);
+    let mut urls: Vec<String> = Vec::new();
+
+    for section in PIP_CONF_SECTIONS {
+        for key in ["index-url", "extra-index-url"] {
+            // pip accepts underscores in place of dashes in option names
+            let value = config
+                .get(section, key)
+                .or_else(|| config.get(section, &key.replace('-', "_")));
+            for url in value.into_iter().flat_map(str::split_whitespace) {
+                if !urls.iter().any(|u| u == url) {
+                    urls.push(url.to_string());
+                }
             }
         }
     }
 
-    Ok(extra_urls)
+    urls
+}
+
+/// Environment variables that replace PyPI, in the order uv prefers them
+const DEFAULT_INDEX_URL_VARS: &[&str] = &["UV_INDEX_URL", "PIP_INDEX_URL"];
+
+/// Environment variables pip and uv read index URLs from
+const INDEX_URL_VARS: &[&str] = &[
+    "PIP_INDEX_URL",
+    "PIP_EXTRA_INDEX_URL",
+    "UV_INDEX_URL",
+    "UV_EXTRA_INDEX_URL",
+];
+
+/// Reads the index that replaces PyPI from `UV_INDEX_URL` or, failing that,
+/// `PIP_INDEX_URL`.
+pub fn default_index_url_from_env() -> Option<String> {
+    DEFAULT_INDEX_URL_VARS
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .map(|value| value.trim().to_string())
+        .find(|value| !value.is_empty())
+}
+
+/// Reads index URLs from the pip and uv environment variables.
+///
+/// The extra index variables may hold several URLs separated by whitespace or
+/// newlines. Each URL is returned once, in the order the variables are listed.
+/// This includes the URL returned by [`default_index_url_from_env`].
+pub fn index_urls_from_env() -> Vec<String> {
+    let mut urls: Vec<String> = Vec::new();
+    for var in INDEX_URL_VARS {
+        let Ok(value) = env::var(var) else {
+            continue;
+        };
+        for url in value.split_whitespace() {
+            if !urls.iter().any(|u| u == url) {
+                urls.push(url.to_string());
+            }
+        }
+    }
+    urls
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_multi_section_pip_conf() {
+        let temp_dir = TempDir::new().unwrap();
+        let pip_conf = temp_dir.path().join("pip.conf");
+        fs::write(
+            &pip_conf,
+            "[global]\n\
+            index-url = https://mirror.example.com/simple\n\
+            timeout = 60\n\n\
+            [install]\n\
+            extra-index-url = https://pypi.internal.example.com/simple\n\n\
+            [download]\n\
+            extra-index-url = https://ignored.example.com/simple\n",
+        )
+        .unwrap();
+
+        let urls = parse_pip_conf_at(&pip_conf).unwrap();
+
+        assert_eq!(
+            urls,
+            vec![
+                "https://mirror.example.com/simple",
+                "https://pypi.internal.example.com/simple",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_multi_line_extra_index_url() {
+        let urls = parse_pip_conf_content(
+            "[global]\n\
+            extra-index-url =\n    https://one.example.com/simple\n    \
+            https://two.example.com/simple https://three.example.com/simple\n\n\
+            [install]\n\
+            extra_index_url = https://one.example.com/simple\n",
+        );
+
+        assert_eq!(
+            urls,
+            vec![
+                "https://one.example.com/simple",
+                "https://two.example.com/simple",
+                "https://three.example.com/simple",
+            ]
+        );
+    }
 }
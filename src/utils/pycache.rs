@@ -0,0 +1,84 @@
+use log::{debug, info, warn};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory names that are never descended into when cleaning caches
+const SKIPPED_DIRS: &[&str] = &["node_modules", "site-packages"];
+
+/// Removes `__pycache__` directories and stray `.pyc` files below `project_dir`.
+///
+/// The walk is conservative: symlinks are never followed or removed, hidden
+/// directories such as `.git`, `.venv` and `.tox` are skipped, as is any
+/// virtual environment (a directory containing `pyvenv.cfg`). A
+/// `__pycache__` directory is only removed when it holds nothing but
+/// compiled Python files.
+///
+/// # Returns
+///
+/// * `Vec<PathBuf>` - The removed directories and files
+pub fn clean_pycache(project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut removed = Vec::new();
+    clean_dir(project_dir, &mut removed)?;
+    info!("Removed {} Python cache artifacts", removed.len());
+    Ok(removed)
+}
+
+fn clean_dir(dir: &Path, removed: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read directory {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_symlink() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if file_type.is_dir() {
+            if name == "__pycache__" {
+                remove_pycache_dir(&path, removed)?;
+            } else if !is_skipped_dir(&path, &name) {
+                clean_dir(&path, removed)?;
+            }
+        } else if file_type.is_file() && is_compiled_python(&name) {
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            debug!("Removed {}", path.display());
+            removed.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn remove_pycache_dir(path: &Path, removed: &mut Vec<PathBuf>) -> Result<(), String> {
+    let only_caches = fs::read_dir(path)
+        .map_err(|e| format!("Failed to read directory {}: {}", path.display(), e))?
+        .flatten()
+        .all(|entry| {
+            entry.file_type().is_ok_and(|t| t.is_file())
+                && is_compiled_python(&entry.file_name().to_string_lossy())
+        });
+    if !only_caches {
+        warn!(
+            "Keeping {}: it contains files that are not Python caches",
+            path.display()
+        );
+        return Ok(());
+    }
+
+    fs::remove_dir_all(path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+    debug!("Removed {}", path.display());
+    removed.push(path.to_path_buf());
+    Ok(())
+}
+
+fn is_skipped_dir(path: &Path, name: &str) -> bool {
+    name.starts_with('.') || SKIPPED_DIRS.contains(&name) || path.join("pyvenv.cfg").exists()
+}
+
+fn is_compiled_python(name: &str) -> bool {
+    name.ends_with(".pyc") || name.ends_with(".pyo")
+}
@@ -1,7 +1,10 @@
-use crate::utils::toml::{read_toml, update_section, write_toml};
-use log::{debug, info};
+use crate::migrators::poetry::python_constraint_to_marker;
+use crate::migrators::setup_cfg::SetupCfg;
+use crate::models::compose_markers;
+use crate::utils::toml::{deep_merge, read_toml, update_section, write_toml, MergeStrategy};
+use log::{debug, info, warn};
 use std::path::Path;
-use toml_edit::{Array, DocumentMut, Formatted, Item, Table, Value};
+use toml_edit::{Array, DocumentMut, Formatted, Item, Table, TableLike, Value};
 
 fn read_and_parse_toml(path: &Path) -> Result<DocumentMut, String> {
     let content = std::fs::read_to_string(path)
@@ -34,6 +37,17 @@ pub fn update_pyproject_toml(project_dir: &Path, _extra_urls: &[String]) -> Resu
         if let Some(version) = project.get("version") {
             update_section(&mut new_doc, &["project", "version"], version.clone());
         }
+        if let Some(readme) = project.get("readme") {
+            update_section(&mut new_doc, &["project", "readme"], readme.clone());
+        }
+        if let Some(license) = project.get("license") {
+            update_section(&mut new_doc, &["project", "license"], license.clone());
+        }
+        for key in ["keywords", "classifiers"] {
+            if let Some(values) = project.get(key) {
+                update_section(&mut new_doc, &["project", key], values.clone());
+            }
+        }
     }
 
     // Fallback to Poetry 1.0 format (tool.poetry section)
@@ -49,6 +63,46 @@ pub fn update_pyproject_toml(project_dir: &Path, _extra_urls: &[String]) -> Resu
             if let Some(version) = poetry.get("version") {
                 update_section(&mut new_doc, &["project", "version"], version.clone());
             }
+            for key in ["keywords", "classifiers"] {
+                let in_project = old_doc.get("project").is_some_and(|p| p.get(key).is_some());
+                if let Some(values) = poetry.get(key).filter(|v| v.is_array() && !in_project) {
+                    update_section(&mut new_doc, &["project", key], values.clone());
+                }
+            }
+            let has_project_license = old_doc
+                .get("project")
+                .is_some_and(|p| p.get("license").is_some());
+            if let Some(license) = poetry
+                .get("license")
+                .and_then(|l| l.as_str())
+                .filter(|_| !has_project_license)
+            {
+                update_section(
+                    &mut new_doc,
+                    &["project", "license"],
+                    pep621_license(project_dir, license),
+                );
+            }
+            let has_project_readme = old_doc
+                .get("project")
+                .is_some_and(|p| p.get("readme").is_some());
+            if let Some(readme) = poetry.get("readme").filter(|_| !has_project_readme) {
+                if let Some(readmes) = readme.as_array().filter(|a| a.len() > 1) {
+                    let ignored: Vec<&str> =
+                        readmes.iter().skip(1).filter_map(|r| r.as_str()).collect();
+                    warn!(
+                        "[project].readme takes a single file, ignoring Poetry readme(s): {}",
+                        ignored.join(", ")
+                    );
+                }
+                if let Some(readme) = poetry_readme(readme) {
+                    update_section(
+                        &mut new_doc,
+                        &["project", "readme"],
+                        Item::Value(Value::String(Formatted::new(readme))),
+                    );
+                }
+            }
         }
     }
 
@@ -80,11 +134,18 @@ pub fn update_url(project_dir: &Path, url: &str) -> Result<(), String> {
     let mut urls_table = toml_edit::InlineTable::new();
     urls_table.insert("repository", Value::String(Formatted::new(url.to_string())));
 
-    update_section(
-        &mut doc,
-        &["project", "urls"],
-        Item::Value(Value::InlineTable(urls_table)),
+    // URLs already declared, such as a homepage, are kept alongside the repository
+    let mut urls = doc
+        .get("project")
+        .and_then(|p| p.get("urls"))
+        .cloned()
+        .unwrap_or_default();
+    deep_merge(
+        &mut urls,
+        &Item::Value(Value::InlineTable(urls_table)),
+        MergeStrategy::PreferSource,
     );
+    update_section(&mut doc, &["project", "urls"], urls);
 
     write_toml(&pyproject_path, &mut doc)?;
     info!("Successfully updated project URL");
@@ -92,18 +153,58 @@ pub fn update_url(project_dir: &Path, url: &str) -> Result<(), String> {
 }
 
 pub fn migrate_poetry_scripts(doc: &DocumentMut) -> Option<Table> {
-    let poetry_scripts = doc.get("tool")?.get("poetry")?.get("scripts")?.as_table()?;
+    let poetry_scripts = doc
+        .get("tool")?
+        .get("poetry")?
+        .get("scripts")?
+        .as_table_like()?;
 
     let mut scripts_table = Table::new();
     scripts_table.set_implicit(true);
 
     for (script_name, script_value) in poetry_scripts.iter() {
-        if let Some(script_str) = script_value.as_str() {
-            let converted_script = convert_script_format(script_str);
-            scripts_table.insert(
-                script_name,
-                toml_edit::Item::Value(Value::String(Formatted::new(converted_script))),
-            );
+        let reference = if let Some(script_str) = script_value.as_str() {
+            Some(script_str)
+        } else if let Some(script_table) = script_value.as_table_like() {
+            // Table form: { reference = "pkg:main", type = "console" }
+            if let Some(extras) = script_extras(script_table) {
+                warn!(
+                    "Poetry script '{}' is only installed with extras [{}]; PEP 621 scripts \
+                    cannot be gated on extras, so it will always be installed",
+                    script_name,
+                    extras.join(", ")
+                );
+            }
+            match script_table.get("type").and_then(|t| t.as_str()) {
+                None | Some("console") => script_table
+                    .get("reference")
+                    .or_else(|| script_table.get("callable"))
+                    .and_then(|r| r.as_str()),
+                Some(script_type) => {
+                    warn!(
+                        "Skipping Poetry script '{}' of type '{}', only console scripts can be migrated",
+                        script_name, script_type
+                    );
+                    continue;
+                }
+            }
+        } else {
+            None
+        };
+
+        match reference {
+            Some(reference) => {
+                scripts_table.insert(
+                    script_name,
+                    Item::Value(Value::String(Formatted::new(convert_script_format(
+                        reference,
+                    )))),
+                );
+            }
+            None => warn!(
+                "Skipping Poetry script '{}' without a reference",
+                script_name
+            ),
         }
     }
 
@@ -114,6 +215,79 @@ pub fn migrate_poetry_scripts(doc: &DocumentMut) -> Option<Table> {
     }
 }
 
+/// The Poetry scripts whose installation is gated on extras, with those extras
+pub fn poetry_scripts_with_extras(doc: &DocumentMut) -> Vec<(String, Vec<String>)> {
+    doc.get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("scripts"))
+        .and_then(|s| s.as_table_like())
+        .map(|scripts| {
+            scripts
+                .iter()
+                .filter_map(|(name, value)| {
+                    let extras = script_extras(value.as_table_like()?)?;
+                    Some((name.to_string(), extras))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The `extras` of a table-form Poetry script, if it has any
+fn script_extras(script_table: &dyn TableLike) -> Option<Vec<String>> {
+    let extras: Vec<String> = script_table
+        .get("extras")?
+        .as_array()?
+        .iter()
+        .filter_map(|e| e.as_str())
+        .map(str::to_string)
+        .collect();
+    (!extras.is_empty()).then_some(extras)
+}
+
+/// Converts `[tool.poetry.plugins]` groups into a `[project.entry-points]` table
+pub fn migrate_poetry_plugins(doc: &DocumentMut) -> Option<Table> {
+    let poetry_plugins = doc
+        .get("tool")?
+        .get("poetry")?
+        .get("plugins")?
+        .as_table_like()?;
+
+    let mut entry_points = Table::new();
+    entry_points.set_implicit(true);
+
+    for (group_name, group) in poetry_plugins.iter() {
+        let Some(group) = group.as_table_like() else {
+            warn!(
+                "Skipping Poetry plugin group '{}' that is not a table",
+                group_name
+            );
+            continue;
+        };
+
+        let mut group_table = Table::new();
+        for (name, reference) in group.iter() {
+            if let Some(reference) = reference.as_str() {
+                group_table.insert(
+                    name,
+                    Item::Value(Value::String(Formatted::new(convert_script_format(
+                        reference,
+                    )))),
+                );
+            }
+        }
+        if !group_table.is_empty() {
+            entry_points.insert(group_name, Item::Table(group_table));
+        }
+    }
+
+    if !entry_points.is_empty() {
+        Some(entry_points)
+    } else {
+        None
+    }
+}
+
 pub fn update_scripts(project_dir: &Path) -> Result<(), String> {
     let pyproject_path = project_dir.join("pyproject.toml");
     let old_pyproject_path = project_dir.join("old.pyproject.toml");
@@ -124,7 +298,13 @@ pub fn update_scripts(project_dir: &Path) -> Result<(), String> {
     // Then read the new pyproject.toml
     let mut doc = read_and_parse_toml(&pyproject_path)?;
 
-    if let Some(scripts_table) = migrate_poetry_scripts(&old_doc) {
+    let scripts_table = migrate_poetry_scripts(&old_doc);
+    let entry_points = migrate_poetry_plugins(&old_doc);
+    if scripts_table.is_none() && entry_points.is_none() {
+        return Ok(());
+    }
+
+    if let Some(scripts_table) = scripts_table {
         // Remove any existing scripts section if present
         if let Some(project) = doc.get_mut("project") {
             if let Some(table) = project.as_table_mut() {
@@ -138,20 +318,29 @@ pub fn update_scripts(project_dir: &Path) -> Result<(), String> {
             &["project", "scripts"],
             Item::Table(scripts_table),
         );
+    }
 
-        // Remove the old scripts section if it exists
-        if let Some(tool) = doc.get_mut("tool") {
-            if let Some(poetry) = tool.get_mut("poetry") {
-                if let Some(table) = poetry.as_table_mut() {
-                    table.remove("scripts");
-                }
+    if let Some(entry_points) = entry_points {
+        update_section(
+            &mut doc,
+            &["project", "entry-points"],
+            Item::Table(entry_points),
+        );
+    }
+
+    // Remove the old scripts and plugins sections if they exist
+    if let Some(tool) = doc.get_mut("tool") {
+        if let Some(poetry) = tool.get_mut("poetry") {
+            if let Some(table) = poetry.as_table_mut() {
+                table.remove("scripts");
+                table.remove("plugins");
             }
         }
-
-        write_toml(&pyproject_path, &mut doc)?;
-        info!("Successfully migrated Poetry scripts to project scripts format");
     }
 
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Successfully migrated Poetry scripts to project scripts format");
+
     Ok(())
 }
 
@@ -176,13 +365,318 @@ pub fn update_project_version(project_dir: &Path, version: &str) -> Result<(), S
     Ok(())
 }
 
-pub fn append_tool_sections(project_dir: &Path) -> Result<(), String> {
+/// Reads the Python requirement of the original project from old.pyproject.toml.
+///
+/// A PEP 621 `[project].requires-python` is used verbatim, otherwise the `python`
+/// entry of `[tool.poetry.dependencies]` is converted to a PEP 440 specifier.
+pub fn extract_requires_python(project_dir: &Path) -> Result<Option<String>, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(None);
+    }
+
+    let doc = read_toml(&old_pyproject_path)?;
+    if let Some(requires_python) = doc
+        .get("project")
+        .and_then(|p| p.get("requires-python"))
+        .and_then(|r| r.as_str())
+    {
+        return Ok(Some(requires_python.trim().to_string()));
+    }
+
+    let poetry_python = doc
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.get("python"))
+        .and_then(|p| p.as_str());
+    Ok(poetry_python.and_then(|python| {
+        let requires_python = crate::migrators::requires_python_from_poetry(python);
+        if requires_python.is_none() {
+            warn!(
+                "Cannot convert Poetry python constraint '{}' to requires-python",
+                python
+            );
+        }
+        requires_python
+    }))
+}
+
+pub fn update_requires_python(project_dir: &Path, requires_python: &str) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    debug!("Updating requires-python to {}", requires_python);
+    update_section(
+        &mut doc,
+        &["project", "requires-python"],
+        Item::Value(Value::String(Formatted::new(requires_python.to_string()))),
+    );
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Successfully updated requires-python");
+    Ok(())
+}
+
+/// README names checked when the original project does not reference one
+const README_CANDIDATES: &[&str] = &["README.md", "README.rst", "README.txt", "README"];
+
+/// Finds the README the original project uses as its long description.
+///
+/// `[project].readme`, `[tool.poetry].readme` and a setup.cfg `long_description = file:`
+/// are checked in that order before falling back to a README in the project root.
+/// The returned path is relative to the project and may not exist.
+pub fn extract_readme(project_dir: &Path) -> Result<Option<String>, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if old_pyproject_path.exists() {
+        let doc = read_toml(&old_pyproject_path)?;
+        let project_readme = doc
+            .get("project")
+            .and_then(|p| p.get("readme"))
+            .and_then(|readme| match readme.as_str() {
+                Some(path) => Some(path.to_string()),
+                None => readme
+                    .get("file")
+                    .and_then(|f| f.as_str())
+                    .map(String::from),
+            });
+        let poetry_readme = doc
+            .get("tool")
+            .and_then(|t| t.get("poetry"))
+            .and_then(|p| p.get("readme"))
+            .and_then(poetry_readme);
+        if let Some(readme) = project_readme.or(poetry_readme) {
+            return Ok(Some(readme));
+        }
+    }
+
+    if let Some(readme) = SetupCfg::load(project_dir)?
+        .and_then(|cfg| {
+            cfg.get("metadata", "long_description")
+                .and_then(|d| d.strip_prefix("file:"))
+                .map(|files| {
+                    files
+                        .split(',')
+                        .next()
+                        .unwrap_or_default()
+                        .trim()
+                        .to_string()
+                })
+        })
+        .filter(|readme| !readme.is_empty())
+    {
+        return Ok(Some(readme));
+    }
+
+    Ok(README_CANDIDATES
+        .iter()
+        .find(|name| project_dir.join(name).exists())
+        .map(|name| name.to_string()))
+}
+
+/// Converts a Poetry `license` into `[project].license`.
+///
+/// SPDX expressions such as `MIT` use the string form, while a value naming a
+/// license file becomes `{ file = "..." }`.
+fn pep621_license(project_dir: &Path, license: &str) -> Item {
+    let license = license.trim();
+    let upper = license.to_uppercase();
+    let is_file = license.contains(['/', '\\'])
+        || ["LICENSE", "LICENCE", "COPYING"]
+            .iter()
+            .any(|name| upper.starts_with(name))
+        || project_dir.join(license).is_file();
+
+    if is_file {
+        let mut table = toml_edit::InlineTable::new();
+        table.insert("file", Value::from(license));
+        Item::Value(Value::InlineTable(table))
+    } else {
+        Item::Value(Value::from(license))
+    }
+}
+
+/// The readme path of a Poetry `readme` value, the first one when it lists several
+fn poetry_readme(readme: &Item) -> Option<String> {
+    match readme.as_str() {
+        Some(path) => Some(path.to_string()),
+        None => readme
+            .as_array()
+            .and_then(|a| a.get(0))
+            .and_then(|f| f.as_str())
+            .map(String::from),
+    }
+}
+
+pub fn update_readme(project_dir: &Path, readme: &str) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    debug!("Updating project readme to {}", readme);
+    update_section(
+        &mut doc,
+        &["project", "readme"],
+        Item::Value(Value::String(Formatted::new(readme.to_string()))),
+    );
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Successfully updated project readme");
+    Ok(())
+}
+
+/// Writes a comment above the `[project]` table listing the original Conda channels,
+/// e.g. `# Migrated from Conda; original channels: conda-forge, defaults`.
+pub fn add_conda_channels_comment(project_dir: &Path, channels: &[String]) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    let Some(project) = doc.get_mut("project").and_then(|p| p.as_table_mut()) else {
+        warn!("No [project] table to annotate with the Conda channels");
+        return Ok(());
+    };
+    let comment = format!(
+        "# Migrated from Conda; original channels: {}\n",
+        channels.join(", ")
+    );
+    let existing = project
+        .decor()
+        .prefix()
+        .and_then(|p| p.as_str())
+        .unwrap_or_default()
+        .to_string();
+    if !existing.contains(comment.trim_end()) {
+        project
+            .decor_mut()
+            .set_prefix(format!("{}{}", existing, comment));
+    }
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Recorded Conda channels in pyproject.toml");
+    Ok(())
+}
+
+/// Adds the given find-links sources to `[tool.uv] find-links`, keeping existing entries.
+pub fn update_find_links(project_dir: &Path, links: &[String]) -> Result<(), String> {
+    if links.is_empty() {
+        return Ok(());
+    }
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    let mut find_links = doc
+        .get("tool")
+        .and_then(|t| t.get("uv"))
+        .and_then(|u| u.get("find-links"))
+        .and_then(|f| f.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for link in links {
+        if !find_links
+            .iter()
+            .any(|existing| existing.as_str() == Some(link))
+        {
+            find_links.push(link.as_str());
+        }
+    }
+
+    debug!("Setting tool.uv.find-links to {:?}", links);
+    update_section(
+        &mut doc,
+        &["tool", "uv", "find-links"],
+        Item::Value(Value::Array(find_links)),
+    );
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Successfully configured find-links sources");
+    Ok(())
+}
+
+/// Sets `[tool.uv] index-strategy`, replacing any existing value.
+pub fn update_index_strategy(project_dir: &Path, strategy: &str) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    debug!("Setting tool.uv.index-strategy to {}", strategy);
+    update_section(
+        &mut doc,
+        &["tool", "uv", "index-strategy"],
+        Item::Value(Value::String(Formatted::new(strategy.to_string()))),
+    );
+
+    write_toml(&pyproject_path, &mut doc)?;
+    Ok(())
+}
+
+/// Writes a comment at the top of pyproject.toml recording which tool migrated it and when,
+/// e.g. `# Migrated from Poetry by uv-migrator 2025.5.0 on 2025-06-01`.
+///
+/// The date is left out unless `dated` is set. An existing stamp is replaced rather
+/// than repeated.
+pub fn add_migration_stamp(project_dir: &Path, source: &str, dated: bool) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    let mut stamp = format!(
+        "# Migrated from {} by uv-migrator {}",
+        source,
+        env!("CARGO_PKG_VERSION")
+    );
+    if dated {
+        stamp.push_str(&format!(" on {}", today_utc()));
+    }
+    stamp.push('\n');
+    let decor = doc.as_table_mut().decor_mut();
+    let existing: String = decor
+        .prefix()
+        .and_then(|p| p.as_str())
+        .unwrap_or_default()
+        .split_inclusive('\n')
+        .filter(|line| !line.starts_with("# Migrated from "))
+        .collect();
+    decor.set_prefix(format!("{}{}", stamp, existing));
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Added migration stamp: {}", stamp.trim_end());
+    Ok(())
+}
+
+/// The current UTC date as `YYYY-MM-DD`
+fn today_utc() -> String {
+    let days = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or_default() as i64;
+
+    // Converts days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Copies tool sections from old.pyproject.toml that are missing from pyproject.toml.
+///
+/// Sections present in both files keep their values and gain any nested keys
+/// only the old file has.
+///
+/// # Returns
+///
+/// * `Vec<String>` - Names of the copied or merged tool sections
+pub fn append_tool_sections(project_dir: &Path) -> Result<Vec<String>, String> {
     let old_pyproject_path = project_dir.join("old.pyproject.toml");
     let pyproject_path = project_dir.join("pyproject.toml");
 
     if !old_pyproject_path.exists() {
         debug!("old.pyproject.toml not found. Skipping tool section migration.");
-        return Ok(());
+        return Ok(Vec::new());
     }
 
     debug!("Reading old and new pyproject.toml files");
@@ -198,34 +692,452 @@ pub fn append_tool_sections(project_dir: &Path) -> Result<(), String> {
                 .map(|t| t.iter().map(|(k, _)| k.to_string()).collect())
                 .unwrap_or_default();
 
-            // Track if any sections were actually copied
-            let mut sections_copied = false;
+            // Track which sections were actually copied
+            let mut sections_copied = Vec::new();
 
-            // Copy each non-poetry tool section that doesn't already exist
+            // Copy each tool section, except the configuration of the package
+            // manager being migrated away from. Sections that already exist only
+            // gain the nested keys they are missing.
             for (section_name, section_value) in tool_table.iter() {
-                if section_name != "poetry"
-                    && !existing_sections.contains(&section_name.to_string())
-                    && !section_value.as_table().is_some_and(|t| t.is_empty())
+                if section_name == "poetry"
+                    || section_name == "pdm"
+                    || section_value.as_table().is_some_and(|t| t.is_empty())
                 {
+                    continue;
+                }
+                if !existing_sections.contains(&section_name.to_string()) {
                     debug!("Copying tool section: {}", section_name);
                     update_section(&mut new_doc, &["tool", section_name], section_value.clone());
-                    sections_copied = true;
+                    sections_copied.push(section_name.to_string());
+                } else if deep_merge(
+                    &mut new_doc["tool"][section_name],
+                    section_value,
+                    MergeStrategy::PreferTarget,
+                ) {
+                    debug!("Merged missing keys into tool section: {}", section_name);
+                    sections_copied.push(section_name.to_string());
                 }
             }
 
-            if sections_copied {
+            if !sections_copied.is_empty() {
                 write_toml(&pyproject_path, &mut new_doc)?;
                 info!("Successfully managed tool sections in new pyproject.toml");
             } else {
                 debug!("No tool sections needed migration");
             }
+            return Ok(sections_copied);
         }
     }
 
-    Ok(())
+    Ok(Vec::new())
+}
+
+/// The tool section `--keep-poetry-section` copies `[tool.poetry]` to
+pub const POETRY_LEGACY_SECTION: &str = "poetry-legacy";
+
+/// Copies `[tool.poetry]` from old.pyproject.toml to `[tool.poetry-legacy]`.
+///
+/// # Returns
+///
+/// * `bool` - Whether a Poetry section was found and copied
+pub fn copy_poetry_section(project_dir: &Path) -> Result<bool, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(false);
+    }
+    let old_doc = read_toml(&old_pyproject_path)?;
+    let Some(poetry) = old_doc.get("tool").and_then(|t| t.get("poetry")) else {
+        return Ok(false);
+    };
+
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+    update_section(&mut doc, &["tool", POETRY_LEGACY_SECTION], poetry.clone());
+    write_toml(&pyproject_path, &mut doc)?;
+    Ok(true)
+}
+
+/// Carries `[project.optional-dependencies]` from old.pyproject.toml into pyproject.toml.
+///
+/// Extras that already exist in pyproject.toml keep their entries, and requirements
+/// from the old file are appended only when their package is not listed yet.
+///
+/// # Returns
+///
+/// * `usize` - The number of requirements carried over
+pub fn merge_optional_dependencies(project_dir: &Path) -> Result<usize, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(0);
+    }
+    let old_doc = read_toml(&old_pyproject_path)?;
+    let Some(old_extras) = old_doc
+        .get("project")
+        .and_then(|p| p.get("optional-dependencies"))
+        .and_then(|o| o.as_table_like())
+    else {
+        return Ok(0);
+    };
+
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+    let mut merged = 0;
+
+    for (extra, old_deps) in old_extras.iter() {
+        let Some(old_deps) = old_deps.as_array() else {
+            continue;
+        };
+        let mut deps = doc
+            .get("project")
+            .and_then(|p| p.get("optional-dependencies"))
+            .and_then(|o| o.get(extra))
+            .and_then(|d| d.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        for old_dep in old_deps.iter().filter_map(|d| d.as_str()) {
+            let name = requirement_name(old_dep);
+            if deps
+                .iter()
+                .filter_map(|d| d.as_str())
+                .any(|d| requirement_name(d) == name)
+            {
+                debug!(
+                    "Optional dependency {} already present in extra {}",
+                    old_dep, extra
+                );
+                continue;
+            }
+            debug!("Keeping optional dependency {} in extra {}", old_dep, extra);
+            deps.push(old_dep);
+            merged += 1;
+        }
+
+        if deps.is_empty() {
+            debug!("Not keeping empty extra {}", extra);
+            continue;
+        }
+        update_section(
+            &mut doc,
+            &["project", "optional-dependencies", extra],
+            Item::Value(Value::Array(deps)),
+        );
+    }
+
+    if merged > 0 {
+        write_toml(&pyproject_path, &mut doc)?;
+        info!("Preserved {} existing optional dependencies", merged);
+    }
+    Ok(merged)
+}
+
+/// Removes empty entries from `[dependency-groups]` and `[project.optional-dependencies]`,
+/// dropping either table once it has no entries left.
+///
+/// # Returns
+///
+/// * `Vec<String>` - The names of the removed groups and extras
+pub fn prune_empty_groups(project_dir: &Path) -> Result<Vec<String>, String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+    let mut pruned = Vec::new();
+
+    let mut prune = |table: &mut dyn TableLike| {
+        let empty: Vec<String> = table
+            .iter()
+            .filter(|(_, deps)| deps.as_array().is_some_and(|a| a.is_empty()))
+            .map(|(name, _)| name.to_string())
+            .collect();
+        for name in empty {
+            debug!("Removing empty dependency group {}", name);
+            table.remove(&name);
+            pruned.push(name);
+        }
+        table.is_empty()
+    };
+
+    if doc
+        .get_mut("dependency-groups")
+        .and_then(|g| g.as_table_like_mut())
+        .is_some_and(&mut prune)
+    {
+        doc.remove("dependency-groups");
+    }
+    if let Some(project) = doc.get_mut("project").and_then(|p| p.as_table_like_mut()) {
+        if project
+            .get_mut("optional-dependencies")
+            .and_then(|o| o.as_table_like_mut())
+            .is_some_and(&mut prune)
+        {
+            project.remove("optional-dependencies");
+        }
+    }
+
+    if !pruned.is_empty() {
+        write_toml(&pyproject_path, &mut doc)?;
+    }
+    Ok(pruned)
+}
+
+/// Collects the trailing comments of the dependencies in the Poetry tables of
+/// old.pyproject.toml, e.g. `# CUDA build` for `torch = "^2.0"  # CUDA build`.
+///
+/// # Returns
+///
+/// * `Vec<(String, String)>` - Normalized package names and their comments, in file order
+pub fn extract_poetry_dependency_comments(
+    project_dir: &Path,
+) -> Result<Vec<(String, String)>, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(Vec::new());
+    }
+    let doc = read_toml(&old_pyproject_path)?;
+    let Some(poetry) = doc.get("tool").and_then(|t| t.get("poetry")) else {
+        return Ok(Vec::new());
+    };
+
+    let mut comments: Vec<(String, String)> = Vec::new();
+    for table in poetry_dependency_tables(poetry) {
+        for (name, item) in table.iter() {
+            let Some(comment) = item
+                .as_value()
+                .and_then(|v| v.decor().suffix())
+                .and_then(|s| s.as_str())
+                .and_then(|s| s.trim().strip_prefix('#'))
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+            else {
+                continue;
+            };
+            let name = requirement_name(name);
+            if !comments.iter().any(|(existing, _)| *existing == name) {
+                comments.push((name, comment.to_string()));
+            }
+        }
+    }
+    Ok(comments)
+}
+
+/// Re-attaches dependency comments to the matching requirement strings of
+/// `[project.dependencies]`, `[project.optional-dependencies]` and
+/// `[dependency-groups]`, as a comment line above each entry.
+///
+/// # Returns
+///
+/// * `usize` - The number of entries a comment was attached to
+pub fn reattach_dependency_comments(
+    project_dir: &Path,
+    comments: &[(String, String)],
+) -> Result<usize, String> {
+    if comments.is_empty() {
+        return Ok(0);
+    }
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    let mut attached = 0;
+    let mut attach = |array: &mut Array| {
+        let mut changed = false;
+        for value in array.iter_mut() {
+            let Some(name) = value.as_str().map(requirement_name) else {
+                continue;
+            };
+            let Some((_, comment)) = comments.iter().find(|(n, _)| *n == name) else {
+                continue;
+            };
+            let prefix = value
+                .decor()
+                .prefix()
+                .and_then(|p| p.as_str())
+                .unwrap_or_default()
+                .to_string();
+            if prefix.contains(comment.as_str()) {
+                continue;
+            }
+            let prefix = match prefix.rfind('\n') {
+                Some(pos) => format!("{}# {}\n{}", prefix, comment, &prefix[pos + 1..]),
+                None => format!("\n    # {}\n    ", comment),
+            };
+            value.decor_mut().set_prefix(prefix);
+            attached += 1;
+            changed = true;
+        }
+        if changed && !array.trailing().as_str().unwrap_or_default().contains('\n') {
+            array.set_trailing("\n");
+        }
+    };
+
+    if let Some(project) = doc.get_mut("project").and_then(|p| p.as_table_like_mut()) {
+        if let Some(deps) = project
+            .get_mut("dependencies")
+            .and_then(|d| d.as_array_mut())
+        {
+            attach(deps);
+        }
+        if let Some(extras) = project
+            .get_mut("optional-dependencies")
+            .and_then(|o| o.as_table_like_mut())
+        {
+            for (_, deps) in extras.iter_mut() {
+                if let Some(deps) = deps.as_array_mut() {
+                    attach(deps);
+                }
+            }
+        }
+    }
+    if let Some(groups) = doc
+        .get_mut("dependency-groups")
+        .and_then(|g| g.as_table_like_mut())
+    {
+        for (_, deps) in groups.iter_mut() {
+            if let Some(deps) = deps.as_array_mut() {
+                attach(deps);
+            }
+        }
+    }
+
+    if attached > 0 {
+        write_toml(&pyproject_path, &mut doc)?;
+    }
+    Ok(attached)
+}
+
+/// The normalized package name of a PEP 508 requirement string
+fn requirement_name(requirement: &str) -> String {
+    requirement
+        .split(|c: char| "[<>=!~;@ (".contains(c))
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+        .replace(['_', '.'], "-")
 }
 
-pub fn update_uv_indices(project_dir: &Path, sources: &[(String, String)]) -> Result<(), String> {
+/// Appends every `[project.optional-dependencies]` entry to `[project.dependencies]`.
+///
+/// The optional-dependencies table itself is left in place. Packages already present
+/// in the main dependencies are not duplicated, even with a different version
+/// specifier; the main dependency's specifier wins.
+///
+/// # Returns
+///
+/// * `bool` - Whether any dependency was appended
+pub fn merge_extras_into_main(project_dir: &Path) -> Result<bool, String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    let extras: Vec<String> = doc
+        .get("project")
+        .and_then(|p| p.get("optional-dependencies"))
+        .and_then(|o| o.as_table_like())
+        .map(|table| {
+            table
+                .iter()
+                .filter_map(|(_, deps)| deps.as_array())
+                .flat_map(|deps| deps.iter().filter_map(|d| d.as_str()))
+                .map(|d| d.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if extras.is_empty() {
+        debug!("No optional dependencies to merge into main dependencies");
+        return Ok(false);
+    }
+
+    warn!(
+        "Merging optional dependencies into main dependencies; \
+        extras will now be installed by default"
+    );
+
+    let mut dependencies = doc
+        .get("project")
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    let mut appended = false;
+    for extra in extras {
+        let name = requirement_name(&extra);
+        if dependencies
+            .iter()
+            .filter_map(|d| d.as_str())
+            .any(|d| requirement_name(d) == name)
+        {
+            debug!("{} is already a main dependency, skipping {}", name, extra);
+            continue;
+        }
+        debug!("Adding optional dependency to main: {}", extra);
+        dependencies.push(extra);
+        appended = true;
+    }
+
+    if appended {
+        update_section(
+            &mut doc,
+            &["project", "dependencies"],
+            Item::Value(Value::Array(dependencies)),
+        );
+        write_toml(&pyproject_path, &mut doc)?;
+        info!("Merged optional dependencies into main dependencies");
+    }
+
+    Ok(appended)
+}
+
+/// A package index written to `[[tool.uv.index]]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UvIndex {
+    pub name: String,
+    pub url: String,
+    /// Only used by packages pinned to this index in `[tool.uv.sources]`
+    pub explicit: bool,
+    /// Replaces PyPI as the default index
+    pub default: bool,
+}
+
+impl UvIndex {
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        UvIndex {
+            name: name.into(),
+            url: url.into(),
+            explicit: false,
+            default: false,
+        }
+    }
+
+    /// Builds an index from a Poetry source, translating its `priority`.
+    ///
+    /// `explicit` and `default` map directly onto uv's index flags. `primary`,
+    /// `supplemental` and the deprecated `secondary` become regular indexes; uv
+    /// has no supplemental tier, so such indexes are consulted before PyPI.
+    pub fn from_poetry_source(
+        name: impl Into<String>,
+        url: impl Into<String>,
+        priority: Option<&str>,
+    ) -> Self {
+        let mut index = UvIndex::new(name, url);
+        match priority {
+            Some("explicit") => index.explicit = true,
+            Some("default") => index.default = true,
+            Some("supplemental") | Some("secondary") => warn!(
+                "Poetry source '{}' is {}; uv will query it before PyPI",
+                index.name,
+                priority.unwrap_or_default()
+            ),
+            Some("primary") | None => {}
+            Some(other) => warn!(
+                "Unknown priority '{}' for Poetry source '{}'; treating it as primary",
+                other, index.name
+            ),
+        }
+        index
+    }
+}
+
+pub fn update_uv_indices(project_dir: &Path, sources: &[UvIndex]) -> Result<(), String> {
     if sources.is_empty() {
         return Ok(());
     }
@@ -235,10 +1147,16 @@ pub fn update_uv_indices(project_dir: &Path, sources: &[(String, String)]) -> Re
 
     let index_array: Array = sources
         .iter()
-        .map(|(name, url)| {
+        .map(|index| {
             let mut index_table = toml_edit::InlineTable::new();
-            index_table.insert("name", Value::String(Formatted::new(name.clone())));
-            index_table.insert("url", Value::String(Formatted::new(url.clone())));
+            index_table.insert("name", Value::String(Formatted::new(index.name.clone())));
+            index_table.insert("url", Value::String(Formatted::new(index.url.clone())));
+            if index.explicit {
+                index_table.insert("explicit", Value::Boolean(Formatted::new(true)));
+            }
+            if index.default {
+                index_table.insert("default", Value::Boolean(Formatted::new(true)));
+            }
             Value::InlineTable(index_table)
         })
         .collect();
@@ -252,7 +1170,590 @@ pub fn update_uv_indices(project_dir: &Path, sources: &[(String, String)]) -> Re
     Ok(())
 }
 
-pub fn extract_poetry_sources(project_dir: &Path) -> Result<Vec<(String, String)>, String> {
+/// Adds index URLs, e.g. from `--import-index` or pip configuration, to
+/// `[[tool.uv.index]]`.
+///
+/// Indexes already in the file are kept and URLs they cover are skipped. Each new
+/// index is named after its host. Credentials embedded in a URL are removed, and
+/// the index at `default_url`, e.g. from `PIP_INDEX_URL`, replaces PyPI unless
+/// another index already does.
+pub fn update_uv_indices_from_urls(
+    project_dir: &Path,
+    urls: &[String],
+    default_url: Option<&str>,
+) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let doc = read_and_parse_toml(&pyproject_path)?;
+    let mut indices = existing_uv_indices(&doc);
+    let initial = indices.clone();
+    let default_url = default_url.map(|url| strip_url_credentials(url.trim()).0);
+
+    for url in urls {
+        let (url, had_credentials) = strip_url_credentials(url.trim());
+        if url.is_empty() {
+            continue;
+        }
+        if let Some(index) = indices.iter().find(|index| index.url == url) {
+            if had_credentials {
+                warn_credentials_removed(&index.name);
+            }
+            continue;
+        }
+        let host = url
+            .split("://")
+            .nth(1)
+            .unwrap_or(&url)
+            .split(['/', ':', '@'])
+            .find(|part| part.contains('.'))
+            .unwrap_or("extra");
+        let mut name = host.to_string();
+        let mut suffix = 2;
+        while indices.iter().any(|index| index.name == name) {
+            name = format!("{}-{}", host, suffix);
+            suffix += 1;
+        }
+        info!("Adding index '{}': {}", name, url);
+        if had_credentials {
+            warn_credentials_removed(&name);
+        }
+        indices.push(UvIndex::new(name, url));
+    }
+
+    if let Some(default_url) = default_url {
+        if let Some(existing) = indices
+            .iter()
+            .find(|index| index.default && index.url != default_url)
+        {
+            warn!(
+                "Index '{}' is already the default index; {} is kept as a regular index",
+                existing.name, default_url
+            );
+        } else if let Some(index) = indices.iter_mut().find(|index| index.url == default_url) {
+            index.default = true;
+        }
+    }
+
+    if indices == initial {
+        return Ok(());
+    }
+    update_uv_indices(project_dir, &indices)
+}
+
+/// Removes the `user:password@` part of an index URL, which would otherwise be
+/// committed along with pyproject.toml. Returns whether anything was removed.
+fn strip_url_credentials(url: &str) -> (String, bool) {
+    let (scheme, rest) = match url.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, url),
+    };
+    let authority_end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let Some(at) = rest[..authority_end].rfind('@') else {
+        return (url.to_string(), false);
+    };
+    let host_and_path = &rest[at + 1..];
+    let stripped = match scheme {
+        Some(scheme) => format!("{}://{}", scheme, host_and_path),
+        None => host_and_path.to_string(),
+    };
+    (stripped, true)
+}
+
+fn warn_credentials_removed(index: &str) {
+    let auth = IndexAuthEnv::for_index(index);
+    warn!(
+        "Removed credentials from the URL of index '{}'; provide them through {} and {} instead",
+        auth.index, auth.username, auth.password
+    );
+}
+
+/// The environment variables uv reads credentials for a named index from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexAuthEnv {
+    pub index: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl IndexAuthEnv {
+    /// Derives the variable names the way uv does: the index name is uppercased
+    /// and every character that is not alphanumeric becomes `_`
+    pub fn for_index(name: &str) -> Self {
+        let normalized: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        IndexAuthEnv {
+            index: name.to_string(),
+            username: format!("UV_INDEX_{}_USERNAME", normalized),
+            password: format!("UV_INDEX_{}_PASSWORD", normalized),
+        }
+    }
+}
+
+/// Lists the credential environment variables of every index in
+/// `[[tool.uv.index]]` other than PyPI, and records them in a comment above `[tool.uv]`.
+pub fn add_index_auth_hints(project_dir: &Path) -> Result<Vec<IndexAuthEnv>, String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+
+    let hints: Vec<IndexAuthEnv> = existing_uv_indices(&doc)
+        .iter()
+        .filter(|index| !index.url.contains("://pypi.org/"))
+        .map(|index| IndexAuthEnv::for_index(&index.name))
+        .collect();
+    if hints.is_empty() {
+        return Ok(hints);
+    }
+
+    let Some(uv) = doc
+        .get_mut("tool")
+        .and_then(|t| t.get_mut("uv"))
+        .and_then(|u| u.as_table_mut())
+    else {
+        return Ok(hints);
+    };
+    let mut prefix = uv
+        .decor()
+        .prefix()
+        .and_then(|p| p.as_str())
+        .unwrap_or_default()
+        .to_string();
+    for hint in &hints {
+        let comment = format!(
+            "# Credentials for index '{}': {}, {}\n",
+            hint.index, hint.username, hint.password
+        );
+        if !prefix.contains(comment.trim_end()) {
+            prefix.push_str(&comment);
+        }
+    }
+    uv.decor_mut().set_prefix(prefix);
+
+    write_toml(&pyproject_path, &mut doc)?;
+    Ok(hints)
+}
+
+/// The indexes already listed in `[[tool.uv.index]]`
+fn existing_uv_indices(doc: &DocumentMut) -> Vec<UvIndex> {
+    let Some(index) = doc
+        .get("tool")
+        .and_then(|t| t.get("uv"))
+        .and_then(|u| u.get("index"))
+    else {
+        return Vec::new();
+    };
+
+    let tables: Vec<&dyn TableLike> = match index {
+        Item::ArrayOfTables(tables) => tables.iter().map(|t| t as &dyn TableLike).collect(),
+        Item::Value(Value::Array(array)) => array
+            .iter()
+            .filter_map(|v| v.as_inline_table())
+            .map(|t| t as &dyn TableLike)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    tables
+        .into_iter()
+        .filter_map(|table| {
+            let field = |key: &str| table.get(key).and_then(|v| v.as_str());
+            let flag = |key: &str| table.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+            let mut index = UvIndex::new(field("name")?, field("url")?);
+            index.explicit = flag("explicit");
+            index.default = flag("default");
+            Some(index)
+        })
+        .collect()
+}
+
+/// A Poetry dependency pinned to a named package source with `source = "<name>"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePin {
+    pub name: String,
+    /// The name of the package source, written as the uv index
+    pub index: String,
+    /// Environment marker limiting where the source applies
+    pub marker: Option<String>,
+}
+
+/// Extracts Poetry dependencies pinned to a named package source.
+///
+/// Poetry pins a dependency to an index with `source = "<name>"`; both main and
+/// group dependencies are inspected.
+///
+/// # Returns
+///
+/// * `Vec<SourcePin>` - The pinned dependencies with their source and markers
+pub fn extract_poetry_source_pins(project_dir: &Path) -> Result<Vec<SourcePin>, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let doc = read_and_parse_toml(&old_pyproject_path)?;
+    let Some(poetry) = doc.get("tool").and_then(|t| t.get("poetry")) else {
+        return Ok(Vec::new());
+    };
+
+    let mut pins = Vec::new();
+    for deps in poetry_dependency_tables(poetry) {
+        for (name, value) in deps.iter() {
+            let Some(table) = value.as_table_like() else {
+                continue;
+            };
+            if let Some(source) = table.get("source").and_then(|s| s.as_str()) {
+                debug!("Found source pin for {}: {}", name, source);
+                if !pins.iter().any(|pin: &SourcePin| pin.name == name) {
+                    pins.push(SourcePin {
+                        name: name.to_string(),
+                        index: source.to_string(),
+                        marker: poetry_marker(table),
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(pins)
+}
+
+/// The environment marker of a Poetry dependency table, combining its `python`
+/// constraint and `markers`
+fn poetry_marker(table: &dyn TableLike) -> Option<String> {
+    let python = table
+        .get("python")
+        .and_then(|p| p.as_str())
+        .and_then(python_constraint_to_marker);
+    compose_markers(
+        python.as_deref(),
+        table.get("markers").and_then(|m| m.as_str()),
+    )
+}
+
+/// Inserts `marker` into a `[tool.uv.sources]` entry when the dependency had one
+fn insert_marker(source_table: &mut toml_edit::InlineTable, marker: &Option<String>) {
+    if let Some(marker) = marker {
+        source_table.insert("marker", Value::String(Formatted::new(marker.clone())));
+    }
+}
+
+/// The main and group dependency tables of a `[tool.poetry]` section
+fn poetry_dependency_tables(poetry: &Item) -> Vec<&dyn TableLike> {
+    let mut dependency_tables = Vec::new();
+    if let Some(deps) = poetry.get("dependencies").and_then(|d| d.as_table_like()) {
+        dependency_tables.push(deps);
+    }
+    if let Some(groups) = poetry.get("group").and_then(|g| g.as_table_like()) {
+        for (_, group) in groups.iter() {
+            if let Some(deps) = group.get("dependencies").and_then(|d| d.as_table_like()) {
+                dependency_tables.push(deps);
+            }
+        }
+    }
+    dependency_tables
+}
+
+/// A Poetry dependency installed from a git repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitDependency {
+    pub name: String,
+    pub git: String,
+    pub branch: Option<String>,
+    pub tag: Option<String>,
+    pub rev: Option<String>,
+    /// Path of the package inside the repository, for monorepos
+    pub subdirectory: Option<String>,
+    /// Environment marker limiting where the source applies
+    pub marker: Option<String>,
+}
+
+impl GitDependency {
+    /// Reads a Poetry dependency table such as
+    /// `{ git = "...", branch = "main", subdirectory = "packages/foo" }`.
+    pub fn from_poetry_table(name: &str, table: &dyn TableLike) -> Option<Self> {
+        let field = |key: &str| {
+            table
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        };
+        Some(GitDependency {
+            name: name.to_string(),
+            git: field("git")?,
+            branch: field("branch"),
+            tag: field("tag"),
+            rev: field("rev"),
+            subdirectory: field("subdirectory"),
+            marker: poetry_marker(table),
+        })
+    }
+
+    /// Renders the PEP 508 direct reference, e.g. `git+https://...@v1.0#subdirectory=foo`
+    pub fn url(&self) -> String {
+        let mut url = format!("git+{}", self.git);
+        if let Some(reference) = self
+            .rev
+            .as_ref()
+            .or(self.tag.as_ref())
+            .or(self.branch.as_ref())
+        {
+            url.push('@');
+            url.push_str(reference);
+        }
+        if let Some(subdirectory) = &self.subdirectory {
+            url.push_str("#subdirectory=");
+            url.push_str(subdirectory);
+        }
+        url
+    }
+}
+
+/// A Poetry dependency installed from a local directory, such as a sibling package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathDependency {
+    pub name: String,
+    /// The path as written in the Poetry configuration, relative to the project root
+    pub path: String,
+    /// Installed in editable mode (Poetry's `develop = true`)
+    pub editable: bool,
+    /// The dependency group, or `None` for a main dependency
+    pub group: Option<String>,
+    /// Environment marker limiting where the source applies
+    pub marker: Option<String>,
+}
+
+impl PathDependency {
+    /// Reads a Poetry dependency table such as `{ path = "../other", develop = true }`
+    pub fn from_poetry_table(
+        name: &str,
+        table: &dyn TableLike,
+        group: Option<&str>,
+    ) -> Option<Self> {
+        Some(PathDependency {
+            name: name.to_string(),
+            path: table.get("path")?.as_str()?.to_string(),
+            editable: table
+                .get("develop")
+                .and_then(|d| d.as_bool())
+                .unwrap_or(false),
+            group: group.map(|g| g.to_string()),
+            marker: poetry_marker(table),
+        })
+    }
+}
+
+/// Extracts Poetry path dependencies from both main and group dependencies.
+///
+/// These are not passed to `uv add`, which would fail on a missing directory, and are
+/// written by [`update_path_dependencies`] instead.
+pub fn extract_poetry_path_dependencies(project_dir: &Path) -> Result<Vec<PathDependency>, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let doc = read_and_parse_toml(&old_pyproject_path)?;
+    let Some(poetry) = doc.get("tool").and_then(|t| t.get("poetry")) else {
+        return Ok(Vec::new());
+    };
+
+    let mut tables: Vec<(Option<&str>, &dyn TableLike)> = Vec::new();
+    if let Some(deps) = poetry.get("dependencies").and_then(|d| d.as_table_like()) {
+        tables.push((None, deps));
+    }
+    if let Some(groups) = poetry.get("group").and_then(|g| g.as_table_like()) {
+        for (group_name, group) in groups.iter() {
+            if let Some(deps) = group.get("dependencies").and_then(|d| d.as_table_like()) {
+                tables.push((Some(group_name), deps));
+            }
+        }
+    }
+
+    let mut path_deps = Vec::new();
+    for (group, deps) in tables {
+        for (name, value) in deps.iter() {
+            if let Some(path_dep) = value
+                .as_table_like()
+                .and_then(|t| PathDependency::from_poetry_table(name, t, group))
+            {
+                debug!("Found path dependency {}: {}", name, path_dep.path);
+                path_deps.push(path_dep);
+            }
+        }
+    }
+
+    Ok(path_deps)
+}
+
+/// Adds path dependencies to the project and writes their `[tool.uv.sources]` entries.
+///
+/// Paths are checked relative to the project directory but written exactly as they
+/// were declared, so sibling packages such as `../other` stay relative.
+///
+/// # Returns
+///
+/// * `Vec<String>` - Names of the dependencies whose path does not exist
+pub fn update_path_dependencies(
+    project_dir: &Path,
+    path_deps: &[PathDependency],
+) -> Result<Vec<String>, String> {
+    if path_deps.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_and_parse_toml(&pyproject_path)?;
+    let mut missing = Vec::new();
+
+    for path_dep in path_deps {
+        if !project_dir.join(&path_dep.path).exists() {
+            warn!(
+                "Path dependency '{}' points to '{}', which does not exist",
+                path_dep.name, path_dep.path
+            );
+            missing.push(path_dep.name.clone());
+        }
+
+        let section: Vec<&str> = match &path_dep.group {
+            None => vec!["project", "dependencies"],
+            Some(group) => vec!["dependency-groups", group.as_str()],
+        };
+        let mut requirements = doc
+            .as_item()
+            .get(section[0])
+            .and_then(|s| s.get(section[1]))
+            .and_then(|r| r.as_array())
+            .cloned()
+            .unwrap_or_default();
+        if !requirements
+            .iter()
+            .any(|r| r.as_str() == Some(path_dep.name.as_str()))
+        {
+            requirements.push(path_dep.name.as_str());
+        }
+        update_section(&mut doc, &section, Item::Value(Value::Array(requirements)));
+
+        let mut source_table = toml_edit::InlineTable::new();
+        source_table.insert("path", Value::String(Formatted::new(path_dep.path.clone())));
+        if path_dep.editable {
+            source_table.insert("editable", Value::Boolean(Formatted::new(true)));
+        }
+        insert_marker(&mut source_table, &path_dep.marker);
+        update_section(
+            &mut doc,
+            &["tool", "uv", "sources", &path_dep.name],
+            Item::Value(Value::InlineTable(source_table)),
+        );
+    }
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Migrated {} path dependencies", path_deps.len());
+    Ok(missing)
+}
+
+/// Extracts Poetry dependencies installed from git, from both main and group dependencies.
+pub fn extract_poetry_git_dependencies(project_dir: &Path) -> Result<Vec<GitDependency>, String> {
+    let old_pyproject_path = project_dir.join("old.pyproject.toml");
+    if !old_pyproject_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let doc = read_and_parse_toml(&old_pyproject_path)?;
+    let Some(poetry) = doc.get("tool").and_then(|t| t.get("poetry")) else {
+        return Ok(Vec::new());
+    };
+
+    let mut git_deps: Vec<GitDependency> = Vec::new();
+    for deps in poetry_dependency_tables(poetry) {
+        for (name, value) in deps.iter() {
+            let Some(git_dep) = value
+                .as_table_like()
+                .and_then(|t| GitDependency::from_poetry_table(name, t))
+            else {
+                continue;
+            };
+            debug!("Found git dependency {}: {}", name, git_dep.url());
+            if !git_deps.iter().any(|existing| existing.name == name) {
+                git_deps.push(git_dep);
+            }
+        }
+    }
+
+    Ok(git_deps)
+}
+
+/// Writes git dependencies into `[tool.uv.sources]`, keeping the branch, tag or rev
+/// and any subdirectory.
+pub fn update_git_dependencies(
+    project_dir: &Path,
+    git_deps: &[GitDependency],
+) -> Result<(), String> {
+    if git_deps.is_empty() {
+        return Ok(());
+    }
+
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_and_parse_toml(&pyproject_path)?;
+
+    for git_dep in git_deps {
+        let mut source_table = toml_edit::InlineTable::new();
+        source_table.insert("git", Value::String(Formatted::new(git_dep.git.clone())));
+        for (key, value) in [
+            ("branch", &git_dep.branch),
+            ("tag", &git_dep.tag),
+            ("rev", &git_dep.rev),
+            ("subdirectory", &git_dep.subdirectory),
+        ] {
+            if let Some(value) = value {
+                source_table.insert(key, Value::String(Formatted::new(value.clone())));
+            }
+        }
+        insert_marker(&mut source_table, &git_dep.marker);
+        update_section(
+            &mut doc,
+            &["tool", "uv", "sources", &git_dep.name],
+            Item::Value(Value::InlineTable(source_table)),
+        );
+    }
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Migrated {} git dependencies", git_deps.len());
+    Ok(())
+}
+
+/// Writes source pins into `[tool.uv.sources]` as `{ index = "<source>" }` entries,
+/// with a `marker` when the dependency had one.
+pub fn update_source_pins(project_dir: &Path, pins: &[SourcePin]) -> Result<(), String> {
+    if pins.is_empty() {
+        return Ok(());
+    }
+
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_and_parse_toml(&pyproject_path)?;
+
+    for pin in pins {
+        let mut source_table = toml_edit::InlineTable::new();
+        source_table.insert("index", Value::String(Formatted::new(pin.index.clone())));
+        insert_marker(&mut source_table, &pin.marker);
+        update_section(
+            &mut doc,
+            &["tool", "uv", "sources", &pin.name],
+            Item::Value(Value::InlineTable(source_table)),
+        );
+    }
+
+    write_toml(&pyproject_path, &mut doc)?;
+    info!("Migrated {} source-pinned dependencies", pins.len());
+    Ok(())
+}
+
+pub fn extract_poetry_sources(project_dir: &Path) -> Result<Vec<UvIndex>, String> {
     let old_pyproject_path = project_dir.join("old.pyproject.toml");
     if !old_pyproject_path.exists() {
         return Ok(Vec::new());
@@ -272,7 +1773,8 @@ pub fn extract_poetry_sources(project_dir: &Path) -> Result<Vec<(String, String)
                 table.get("name").and_then(|n| n.as_str()),
                 table.get("url").and_then(|u| u.as_str()),
             ) {
-                sources.push((name.to_string(), url.to_string()));
+                let priority = table.get("priority").and_then(|p| p.as_str());
+                sources.push(UvIndex::from_poetry_source(name, url, priority));
             }
         }
     }
@@ -290,7 +1792,8 @@ pub fn extract_poetry_sources(project_dir: &Path) -> Result<Vec<(String, String)
                         source.get("name").and_then(|n| n.as_str()),
                         source.get("url").and_then(|u| u.as_str()),
                     ) {
-                        sources.push((name.to_string(), url.to_string()));
+                        let priority = source.get("priority").and_then(|p| p.as_str());
+                        sources.push(UvIndex::from_poetry_source(name, url, priority));
                     }
                 }
             }
@@ -0,0 +1,106 @@
+use crate::models::SkippedItem;
+use log::info;
+use std::fs;
+use std::path::Path;
+
+/// Writes the skipped items of a migration to `path`.
+///
+/// Paths ending in `.csv` are written as CSV with a `kind,name,reason` header;
+/// anything else is written as plain text with one item per line.
+pub fn write_skipped_report(path: &Path, items: &[SkippedItem]) -> Result<(), String> {
+    let is_csv = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+
+    let content = if is_csv {
+        format_csv(items)
+    } else {
+        format_text(items)
+    };
+
+    fs::write(path, content)
+        .map_err(|e| format!("Failed to write skipped report {}: {}", path.display(), e))?;
+    info!(
+        "Wrote {} skipped item(s) to {}",
+        items.len(),
+        path.display()
+    );
+    Ok(())
+}
+
+fn format_text(items: &[SkippedItem]) -> String {
+    if items.is_empty() {
+        return "No items were skipped.\n".to_string();
+    }
+
+    items
+        .iter()
+        .map(|item| format!("[{}] {}: {}\n", item.kind, item.name, item.reason))
+        .collect()
+}
+
+fn format_csv(items: &[SkippedItem]) -> String {
+    let mut output = String::from("kind,name,reason\n");
+    for item in items {
+        output.push_str(&format!(
+            "{},{},{}\n",
+            item.kind,
+            csv_field(&item.name),
+            csv_field(&item.reason)
+        ));
+    }
+    output
+}
+
+/// Quotes a CSV field when it contains a separator, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SkippedKind;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_text_report() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("skipped.txt");
+        let items = vec![SkippedItem::new(
+            SkippedKind::Line,
+            "requirements.txt:3",
+            "Malformed requirement line",
+        )];
+
+        write_skipped_report(&path, &items).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "[line] requirements.txt:3: Malformed requirement line\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv_report_quotes_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("skipped.csv");
+        let items = vec![SkippedItem::new(
+            SkippedKind::Package,
+            "mkl",
+            "system library, not on PyPI",
+        )];
+
+        write_skipped_report(&path, &items).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "kind,name,reason\npackage,mkl,\"system library, not on PyPI\"\n"
+        );
+    }
+}
@@ -0,0 +1,133 @@
+/// Version comparison operators defined by PEP 440, longest first
+const SPECIFIER_OPERATORS: &[&str] = &["===", "==", "!=", "<=", ">=", "~=", "<", ">"];
+
+/// Checks that `specifiers` is a valid comma-separated PEP 440 version specifier
+/// set such as `>=0.111.0,<0.112.0`.
+///
+/// The check is deliberately shallow: it catches malformed input like `>=,<2` or
+/// `>=^0.1` before it reaches `uv add`, without implementing full PEP 440 parsing.
+pub fn validate_specifiers(specifiers: &str) -> Result<(), String> {
+    if specifiers.trim().is_empty() {
+        return Err("specifier is empty".to_string());
+    }
+    specifiers.split(',').try_for_each(validate_specifier)
+}
+
+fn validate_specifier(specifier: &str) -> Result<(), String> {
+    let specifier = specifier.trim();
+    let Some(operator) = SPECIFIER_OPERATORS
+        .iter()
+        .find(|op| specifier.starts_with(**op))
+    else {
+        return Err(format!("'{}' has no comparison operator", specifier));
+    };
+    let version = specifier[operator.len()..].trim();
+    if version.is_empty() {
+        return Err(format!("'{}' has no version", specifier));
+    }
+
+    // Arbitrary equality compares strings, so any version is acceptable
+    if *operator == "===" {
+        return Ok(());
+    }
+
+    let (version, wildcard) = match version.strip_suffix(".*") {
+        Some(prefix) => (prefix, true),
+        None => (version, false),
+    };
+    if wildcard && !matches!(*operator, "==" | "!=") {
+        return Err(format!("'{}' uses a wildcard with {}", specifier, operator));
+    }
+    validate_version(version).map_err(|e| format!("'{}': {}", specifier, e))
+}
+
+/// Checks the shape of a PEP 440 version: an optional epoch, a numeric release and
+/// optional pre, post, dev and local segments
+fn validate_version(version: &str) -> Result<(), String> {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let release = match version.split_once('!') {
+        Some((epoch, release))
+            if !epoch.is_empty() && epoch.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            release
+        }
+        Some(_) => return Err("invalid epoch".to_string()),
+        None => version,
+    };
+
+    if !release.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(format!("version '{}' must start with a number", version));
+    }
+
+    let (public, local) = match release.split_once('+') {
+        Some((public, local)) => (public, Some(local)),
+        None => (release, None),
+    };
+    let valid_public = public
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+        && !public.contains("..");
+    if !valid_public {
+        return Err(format!("version '{}' contains invalid characters", version));
+    }
+    if let Some(local) = local {
+        let valid_local = !local.is_empty()
+            && local
+                .split(['.', '-', '_'])
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid_local {
+            return Err(format!(
+                "version '{}' has an invalid local segment",
+                version
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_specifiers() {
+        for specifiers in [
+            ">=0.111.0,<0.112.0",
+            "==2.31.0",
+            "~=1.4",
+            "!=1.5.*",
+            "==1.0.0rc1",
+            ">=1.0.post2",
+            "==1!2.0",
+            "==1.2.3+cpu",
+            ">= 1.0, < 2.0",
+            "===foobar",
+        ] {
+            assert!(
+                validate_specifiers(specifiers).is_ok(),
+                "{} should be valid",
+                specifiers
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_specifiers() {
+        for specifiers in [
+            "",
+            ">=,<2",
+            ">=^0.1",
+            "1.0",
+            ">=1.0 <2.0",
+            ">=1.*",
+            "==1..2",
+            "==1.0+",
+        ] {
+            assert!(
+                validate_specifiers(specifiers).is_err(),
+                "{} should be invalid",
+                specifiers
+            );
+        }
+    }
+}
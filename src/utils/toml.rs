@@ -1,6 +1,6 @@
 use std::{fs, path::Path};
 
-use toml_edit::{DocumentMut, Item, Table};
+use toml_edit::{Decor, DocumentMut, Item, Table, Value};
 
 /// Reads a TOML file and returns its content as a DocumentMut.
 pub fn read_toml(path: &Path) -> Result<DocumentMut, String> {
@@ -29,6 +29,66 @@ pub fn update_section(doc: &mut DocumentMut, section_path: &[&str], content: Ite
     current.insert(last_section, content);
 }
 
+/// How [`deep_merge`] resolves a key whose value differs in both tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the value already in the target
+    PreferTarget,
+    /// Replace the target's value with the one from the source
+    PreferSource,
+}
+
+/// Merges `source` into `target` without dropping anything `target` already has.
+///
+/// Keys missing from `target` are copied over, and tables present on both sides
+/// (standard or inline) are merged recursively. Any other value found on both
+/// sides, including arrays, is a conflict resolved by `strategy`. A replaced value
+/// keeps the comments and whitespace around the one it replaces.
+///
+/// Returns whether `target` changed.
+pub fn deep_merge(target: &mut Item, source: &Item, strategy: MergeStrategy) -> bool {
+    if let (Some(target_table), Some(source_table)) =
+        (target.as_table_like_mut(), source.as_table_like())
+    {
+        let mut changed = false;
+        for (key, value) in source_table.iter() {
+            match target_table.get_mut(key) {
+                Some(existing) => changed |= deep_merge(existing, value, strategy),
+                None => {
+                    target_table.insert(key, value.clone());
+                    changed = true;
+                }
+            }
+        }
+        // Inline tables cannot hold comments, so normalizing their spacing loses nothing
+        if let Some(inline) = target.as_inline_table_mut().filter(|_| changed) {
+            inline.fmt();
+        }
+        return changed;
+    }
+
+    if target.is_none() {
+        *target = source.clone();
+        return !source.is_none();
+    }
+    if strategy == MergeStrategy::PreferTarget || source.is_none() {
+        return false;
+    }
+
+    match (target.as_value_mut(), source.as_value()) {
+        (Some(existing), Some(value)) => {
+            let mut value = value.clone();
+            *value.decor_mut() = existing.decor().clone();
+            if value.to_string() == existing.to_string() {
+                return false;
+            }
+            *existing = value;
+        }
+        _ => *target = source.clone(),
+    }
+    true
+}
+
 /// Writes a TOML document to a file, removing any empty sections first.
 pub fn write_toml(path: &Path, doc: &mut DocumentMut) -> Result<(), String> {
     cleanup_empty_sections(doc);
@@ -88,6 +148,80 @@ fn is_empty_section(item: &Item) -> bool {
     }
 }
 
+/// Removes all comments from a TOML document while keeping its layout.
+pub fn strip_comments(doc: &mut DocumentMut) {
+    strip_table_comments(doc.as_table_mut());
+    let trailing = strip_comment_lines(doc.trailing().as_str().unwrap_or_default());
+    doc.set_trailing(trailing);
+}
+
+/// Reads pyproject.toml, strips every comment from it and writes it back.
+pub fn strip_toml_comments(project_dir: &Path) -> Result<(), String> {
+    let pyproject_path = project_dir.join("pyproject.toml");
+    let mut doc = read_toml(&pyproject_path)?;
+    strip_comments(&mut doc);
+    write_toml(&pyproject_path, &mut doc)
+}
+
+/// Drops comment lines from raw decor text, leaving whitespace untouched
+fn strip_comment_lines(raw: &str) -> String {
+    raw.split_inclusive('\n')
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .collect()
+}
+
+fn strip_decor(decor: &mut Decor) {
+    if let Some(prefix) = decor.prefix().and_then(|p| p.as_str()) {
+        let stripped = strip_comment_lines(prefix);
+        decor.set_prefix(stripped);
+    }
+    if let Some(suffix) = decor.suffix().and_then(|s| s.as_str()) {
+        let stripped = strip_comment_lines(suffix);
+        decor.set_suffix(stripped);
+    }
+}
+
+fn strip_table_comments(table: &mut Table) {
+    strip_decor(table.decor_mut());
+    for (mut key, item) in table.iter_mut() {
+        strip_decor(key.leaf_decor_mut());
+        strip_item_comments(item);
+    }
+}
+
+fn strip_item_comments(item: &mut Item) {
+    match item {
+        Item::Value(value) => strip_value_comments(value),
+        Item::Table(table) => strip_table_comments(table),
+        Item::ArrayOfTables(array) => {
+            for table in array.iter_mut() {
+                strip_table_comments(table);
+            }
+        }
+        Item::None => {}
+    }
+}
+
+fn strip_value_comments(value: &mut Value) {
+    strip_decor(value.decor_mut());
+    match value {
+        Value::Array(array) => {
+            for element in array.iter_mut() {
+                strip_value_comments(element);
+            }
+            let trailing = strip_comment_lines(array.trailing().as_str().unwrap_or_default());
+            array.set_trailing(trailing);
+        }
+        Value::InlineTable(table) => {
+            for (mut key, element) in table.iter_mut() {
+                strip_decor(key.leaf_decor_mut());
+                strip_value_comments(element);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Defines the expected order of fields within the [project] section
 const PROJECT_FIELD_ORDER: &[&str] = &[
     "name",
@@ -100,6 +234,7 @@ const PROJECT_FIELD_ORDER: &[&str] = &[
     "classifiers",
     "optional-dependencies",
     "scripts",
+    "entry-points",
     "urls",
 ];
 
@@ -107,6 +242,8 @@ const PROJECT_FIELD_ORDER: &[&str] = &[
 fn order_table_fields(table: &mut Table, field_order: &[&str]) -> Table {
     let mut ordered = Table::new();
     ordered.set_implicit(table.is_implicit());
+    // Keep comments written above the table header
+    *ordered.decor_mut() = table.decor().clone();
 
     // First add fields in the specified order
     for &field in field_order {
@@ -235,4 +372,163 @@ description = "Test description"
             "authors should come before dependencies"
         );
     }
+
+    #[test]
+    fn test_strip_comments_removes_all_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let input_content = r#"# Header comment
+[project]
+name = "test-project" # inline name comment
+dependencies = [
+    # leading element comment
+    "requests>=2.0", # trailing element comment
+]
+
+# Black configuration
+[tool.black]
+line-length = 88 # max line length
+"#;
+        fs::write(temp_dir.path().join("pyproject.toml"), input_content).unwrap();
+
+        strip_toml_comments(temp_dir.path()).unwrap();
+
+        let result = fs::read_to_string(temp_dir.path().join("pyproject.toml")).unwrap();
+        assert!(
+            !result.contains('#'),
+            "comments should be stripped: {}",
+            result
+        );
+        assert!(result.contains(r#"name = "test-project""#));
+        assert!(result.contains(r#""requests>=2.0""#));
+        assert!(result.contains("[tool.black]"));
+        assert!(result.contains("line-length = 88"));
+    }
+
+    #[test]
+    fn test_comments_preserved_without_stripping() {
+        let temp_dir = TempDir::new().unwrap();
+        let pyproject_path = temp_dir.path().join("pyproject.toml");
+        let input_content = r#"[project]
+name = "test-project" # inline name comment
+
+# Black configuration
+[tool.black]
+line-length = 88
+"#;
+        fs::write(&pyproject_path, input_content).unwrap();
+
+        let mut doc = read_toml(&pyproject_path).unwrap();
+        write_toml(&pyproject_path, &mut doc).unwrap();
+
+        let result = fs::read_to_string(&pyproject_path).unwrap();
+        assert!(result.contains("# inline name comment"));
+        assert!(result.contains("# Black configuration"));
+    }
+
+    fn merge(target: &str, source: &str, strategy: MergeStrategy) -> (bool, DocumentMut) {
+        let mut target = target.parse::<DocumentMut>().unwrap();
+        let source = source.parse::<DocumentMut>().unwrap();
+        let changed = deep_merge(target.as_item_mut(), source.as_item(), strategy);
+        (changed, target)
+    }
+
+    #[test]
+    fn test_deep_merge_adds_missing_keys_in_nested_tables() {
+        let target = r#"[tool.ruff]
+line-length = 100
+
+[tool.ruff.lint]
+select = ["E"]
+"#;
+        let source = r#"[tool.ruff]
+target-version = "py311"
+
+[tool.ruff.lint]
+ignore = ["E501"]
+
+[tool.ruff.lint.isort]
+known-first-party = ["app"]
+
+[tool.black]
+line-length = 88
+"#;
+        let (changed, doc) = merge(target, source, MergeStrategy::PreferTarget);
+
+        assert!(changed);
+        assert_eq!(doc["tool"]["ruff"]["line-length"].as_integer(), Some(100));
+        assert_eq!(
+            doc["tool"]["ruff"]["target-version"].as_str(),
+            Some("py311")
+        );
+        assert!(doc["tool"]["ruff"]["lint"]["select"].is_array());
+        assert!(doc["tool"]["ruff"]["lint"]["ignore"].is_array());
+        assert!(doc["tool"]["ruff"]["lint"]["isort"]["known-first-party"].is_array());
+        assert_eq!(doc["tool"]["black"]["line-length"].as_integer(), Some(88));
+    }
+
+    #[test]
+    fn test_deep_merge_conflicts_follow_strategy() {
+        let target = "[tool.black]\nline-length = 100\n";
+        let source = "[tool.black]\nline-length = 88\n";
+
+        let (changed, doc) = merge(target, source, MergeStrategy::PreferTarget);
+        assert!(!changed);
+        assert_eq!(doc["tool"]["black"]["line-length"].as_integer(), Some(100));
+
+        let (changed, doc) = merge(target, source, MergeStrategy::PreferSource);
+        assert!(changed);
+        assert_eq!(doc["tool"]["black"]["line-length"].as_integer(), Some(88));
+    }
+
+    #[test]
+    fn test_deep_merge_treats_arrays_as_values() {
+        let target = "[tool.mypy]\nplugins = [\"pydantic.mypy\"]\n";
+        let source = "[tool.mypy]\nplugins = [\"sqlalchemy.ext.mypy.plugin\"]\n";
+
+        let (_, doc) = merge(target, source, MergeStrategy::PreferTarget);
+        assert_eq!(
+            doc.to_string(),
+            "[tool.mypy]\nplugins = [\"pydantic.mypy\"]\n"
+        );
+
+        let (_, doc) = merge(target, source, MergeStrategy::PreferSource);
+        assert_eq!(
+            doc.to_string(),
+            "[tool.mypy]\nplugins = [\"sqlalchemy.ext.mypy.plugin\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_deep_merge_inline_and_standard_tables() {
+        let target = "[project]\nurls = { homepage = \"https://example.com\" }\n";
+        let source = "[project.urls]\nrepository = \"https://github.com/example/project\"\n";
+
+        let (changed, doc) = merge(target, source, MergeStrategy::PreferSource);
+        assert!(changed);
+        assert_eq!(
+            doc.to_string(),
+            "[project]\nurls = { homepage = \"https://example.com\", repository = \"https://github.com/example/project\" }\n"
+        );
+    }
+
+    #[test]
+    fn test_deep_merge_keeps_comments_of_replaced_values() {
+        let target = "[tool.black]\n# Wider than the default\nline-length = 100 # team agreement\n";
+        let source = "[tool.black]\nline-length = 120\n";
+
+        let (_, doc) = merge(target, source, MergeStrategy::PreferSource);
+        assert_eq!(
+            doc.to_string(),
+            "[tool.black]\n# Wider than the default\nline-length = 120 # team agreement\n"
+        );
+    }
+
+    #[test]
+    fn test_deep_merge_identical_value_is_unchanged() {
+        let target = "[tool.black]\nline-length = 88\n";
+
+        let (changed, doc) = merge(target, target, MergeStrategy::PreferSource);
+        assert!(!changed);
+        assert_eq!(doc.to_string(), target);
+    }
 }
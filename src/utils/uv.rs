@@ -1,4 +1,21 @@
+use log::{debug, info, warn};
 use semver::Version;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::time::Duration;
+
+/// Lowercased stderr fragments of network failures that are worth retrying:
+/// timeouts, reset connections, HTTP 429 and HTTP 503
+const TRANSIENT_ERROR_PATTERNS: &[&str] = &[
+    "timed out",
+    "timeout",
+    "connection reset",
+    "429 too many requests",
+    "503 service unavailable",
+];
+
+/// Delay before the first retry; it doubles for every further attempt
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
 
 pub fn check_uv_requirements() -> Result<(), String> {
     // First check if uv is in PATH
@@ -13,7 +30,7 @@ pub fn check_uv_requirements() -> Result<(), String> {
     };
 
     // If uv is found, check its version
-    let output = std::process::Command::new(&uv_path)
+    let output = Command::new(&uv_path)
         .arg("--version")
         .output()
         .map_err(|e| format!("Failed to execute uv --version: {}", e))?;
@@ -43,3 +60,294 @@ pub fn check_uv_requirements() -> Result<(), String> {
 
     Ok(())
 }
+
+/// Builds and executes `uv` subprocess invocations.
+///
+/// Keeping the assembled arguments around lets the exact command line be logged
+/// (or rendered without running it) before execution.
+#[derive(Debug, Clone)]
+pub struct UvCommandBuilder {
+    uv_path: PathBuf,
+    args: Vec<String>,
+    working_dir: Option<PathBuf>,
+    verbose: bool,
+    retries: u32,
+    retry_backoff: Duration,
+}
+
+impl UvCommandBuilder {
+    /// Creates a builder for the `uv` executable found in PATH
+    pub fn new() -> Result<Self, String> {
+        let uv_path =
+            which::which("uv").map_err(|e| format!("Failed to find uv command: {}", e))?;
+        Ok(Self::with_path(uv_path))
+    }
+
+    /// Creates a builder for an explicit `uv` executable path
+    pub fn with_path(uv_path: impl Into<PathBuf>) -> Self {
+        UvCommandBuilder {
+            uv_path: uv_path.into(),
+            args: Vec::new(),
+            working_dir: None,
+            verbose: false,
+            retries: 0,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn working_dir(mut self, dir: &Path) -> Self {
+        self.working_dir = Some(dir.to_path_buf());
+        self
+    }
+
+    /// Logs the exact command line at info level instead of debug before running it
+    pub fn verbose(mut self, verbose: bool) -> Self {
+        self.verbose = verbose;
+        self
+    }
+
+    /// Retries [`Self::execute_success`] up to `retries` times when uv fails with a
+    /// transient network error
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Delay before the first retry, doubled for every further attempt
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    pub fn get_args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Renders the full command line as it could be pasted into a shell
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.uv_path.display().to_string())
+            .chain(self.args.iter().cloned())
+            .map(|part| shell_quote(&part))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn log_command(&self) {
+        let location = self
+            .working_dir
+            .as_ref()
+            .map(|dir| format!(" (in {})", dir.display()))
+            .unwrap_or_default();
+        if self.verbose {
+            info!("Running: {}{}", self.command_line(), location);
+        } else {
+            debug!("Running: {}{}", self.command_line(), location);
+        }
+    }
+
+    /// Runs the command and returns its output regardless of exit status
+    pub fn execute(&self) -> Result<Output, String> {
+        self.log_command();
+
+        let mut command = Command::new(&self.uv_path);
+        command.args(&self.args);
+        if let Some(dir) = &self.working_dir {
+            command.current_dir(dir);
+        }
+
+        command
+            .output()
+            .map_err(|e| format!("Failed to execute {}: {}", self.command_line(), e))
+    }
+
+    /// Runs the command and turns a non-zero exit status into an error.
+    ///
+    /// Failures whose stderr looks like a transient network error are retried with
+    /// exponential backoff, up to the configured number of retries.
+    pub fn execute_success(&self, description: &str) -> Result<Output, String> {
+        let mut attempt = 0;
+        loop {
+            let output = self.execute()?;
+            if output.status.success() {
+                return Ok(output);
+            }
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if attempt >= self.retries || !is_transient_failure(&stderr) {
+                return Err(format!("{} failed: {}", description, stderr));
+            }
+
+            let delay = self.retry_backoff.saturating_mul(1 << attempt.min(16));
+            attempt += 1;
+            warn!(
+                "{} failed with a transient error, retrying in {:?} ({}/{}): {}",
+                description,
+                delay,
+                attempt,
+                self.retries,
+                stderr.trim()
+            );
+            std::thread::sleep(delay);
+        }
+    }
+}
+
+/// Whether uv's stderr points at a network failure that may succeed when retried
+fn is_transient_failure(stderr: &str) -> bool {
+    let stderr = stderr.to_lowercase();
+    TRANSIENT_ERROR_PATTERNS
+        .iter()
+        .any(|pattern| stderr.contains(pattern))
+}
+
+/// Quotes a command line argument for display when it contains shell-special characters
+fn shell_quote(arg: &str) -> String {
+    let needs_quoting = arg.is_empty()
+        || arg
+            .chars()
+            .any(|c| c.is_whitespace() || r#"'"$`\;&|<>()*?![]{}#~"#.contains(c));
+    if needs_quoting {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    } else {
+        arg.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_line_contains_args() {
+        let builder = UvCommandBuilder::with_path("uv")
+            .arg("add")
+            .arg("--group")
+            .arg("docs")
+            .args(["sphinx>=7.0", "furo"]);
+
+        assert_eq!(
+            builder.command_line(),
+            "uv add --group docs 'sphinx>=7.0' furo"
+        );
+        assert_eq!(
+            builder.get_args(),
+            &["add", "--group", "docs", "sphinx>=7.0", "furo"]
+        );
+    }
+
+    #[test]
+    fn test_command_line_quotes_markers() {
+        let builder = UvCommandBuilder::with_path("uv")
+            .arg("add")
+            .arg("pywin32==306; sys_platform == 'win32'");
+
+        assert_eq!(
+            builder.command_line(),
+            r"uv add 'pywin32==306; sys_platform == '\''win32'\'''"
+        );
+    }
+
+    #[test]
+    fn test_transient_failures() {
+        assert!(is_transient_failure(
+            "error: Request failed after 3 retries\n  Caused by: operation timed out"
+        ));
+        assert!(is_transient_failure(
+            "Connection reset by peer (os error 104)"
+        ));
+        assert!(is_transient_failure(
+            "HTTP status server error (503 Service Unavailable)"
+        ));
+        assert!(!is_transient_failure(
+            "No solution found when resolving dependencies"
+        ));
+    }
+
+    /// Writes a fake uv that counts its runs in `runs` and fails with `stderr`
+    /// until it has run `failures` times
+    #[cfg(unix)]
+    fn fake_uv(dir: &Path, failures: usize, stderr: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let script = dir.join("uv");
+        std::fs::write(
+            &script,
+            format!(
+                "#!/bin/sh\n\
+                 echo run >> \"{runs}\"\n\
+                 if [ \"$(wc -l < \"{runs}\")\" -gt {failures} ]; then exit 0; fi\n\
+                 echo '{stderr}' >&2\n\
+                 exit 1\n",
+                runs = dir.join("runs").display(),
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        script
+    }
+
+    #[cfg(unix)]
+    fn runs(dir: &Path) -> usize {
+        std::fs::read_to_string(dir.join("runs"))
+            .map(|runs| runs.lines().count())
+            .unwrap_or_default()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_retries_transient_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let uv = fake_uv(dir.path(), 1, "error: connection reset by peer");
+
+        let builder = UvCommandBuilder::with_path(&uv)
+            .arg("add")
+            .retries(2)
+            .retry_backoff(Duration::ZERO);
+        assert!(builder.execute_success("uv add").is_ok());
+        assert_eq!(runs(dir.path()), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_does_not_retry_permanent_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let uv = fake_uv(
+            dir.path(),
+            1,
+            "No solution found when resolving dependencies",
+        );
+
+        let builder = UvCommandBuilder::with_path(&uv)
+            .arg("add")
+            .retries(2)
+            .retry_backoff(Duration::ZERO);
+        let err = builder.execute_success("uv add").unwrap_err();
+        assert!(err.contains("No solution found"));
+        assert_eq!(runs(dir.path()), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_no_retries_by_default() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let uv = fake_uv(dir.path(), 1, "error: operation timed out");
+
+        let builder = UvCommandBuilder::with_path(&uv).retry_backoff(Duration::ZERO);
+        assert!(builder.execute_success("uv add").is_err());
+        assert_eq!(runs(dir.path()), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::migrators::setup_cfg::SetupCfg;
 use log::debug;
 use std::fs;
 use std::path::Path;
@@ -41,7 +42,13 @@ fn clean_version(version: &str) -> Option<String> {
 ///
 /// * `Result<Option<String>, String>` - The version if found, None if not found, or an error
 pub fn extract_version(project_dir: &Path) -> Result<Option<String>, String> {
-    // First try to get version from setup.py
+    // setuptools reads metadata from setup.cfg before setup.py
+    if let Some(version) = SetupCfg::load(project_dir)?.and_then(|cfg| cfg.metadata("version")) {
+        debug!("Found version in setup.cfg: {}", version);
+        return Ok(Some(version));
+    }
+
+    // Then try to get version from setup.py
     if let Some(version) = extract_version_from_setup_py(project_dir)? {
         debug!("Found version in setup.py: {}", version);
         return Ok(Some(version));
@@ -73,12 +80,9 @@ fn extract_version_from_setup_py(project_dir: &Path) -> Result<Option<String>, S
         .map_err(|e| format!("Failed to read setup.py: {}", e))?;
 
     // Look for version in setup() call
-    if let Some(start_idx) = content.find("setup(") {
-        let bracket_content =
-            crate::migrators::setup_py::SetupPyMigrationSource::extract_setup_content(
-                &content[start_idx..],
-            )?;
-
+    if let Some(bracket_content) =
+        crate::migrators::setup_py::SetupPyMigrationSource::setup_call(&content)
+    {
         if let Some(version) = crate::migrators::setup_py::SetupPyMigrationSource::extract_parameter(
             &bracket_content,
             "version",
@@ -109,7 +113,7 @@ fn extract_version_from_init_py(project_dir: &Path) -> Result<Option<String>, St
         if path.is_dir()
             && !path
                 .file_name()
-                .map_or(true, |n| n.to_string_lossy().starts_with('.'))
+                .is_none_or(|n| n.to_string_lossy().starts_with('.'))
         {
             let init_path = path.join("__init__.py");
             if let Some(version) = extract_version_from_init_file(&init_path)? {
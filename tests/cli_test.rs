@@ -0,0 +1,95 @@
+use tempfile::TempDir;
+use uv_migrator::cli::{build_command, command_from_matches, Args, CliCommand};
+use uv_migrator::error::Error;
+use uv_migrator::migrators::detect::NO_PROJECT_ERROR;
+use uv_migrator::migrators::run_migration;
+
+fn parse(args: &[&str]) -> CliCommand {
+    let matches = build_command()
+        .try_get_matches_from(args)
+        .expect("arguments should parse");
+    command_from_matches(&matches)
+}
+
+/// Test mapping of `-v`/`--verbose` and `--quiet` to log levels.
+///
+/// This test verifies that:
+/// 1. No flag leaves the log level to RUST_LOG and the default
+/// 2. One `-v` selects debug and two or more select trace
+/// 3. `--quiet` selects warn and conflicts with `--verbose`
+/// 4. The flags are accepted after a subcommand
+#[test]
+fn test_verbose_and_quiet_log_levels() {
+    let level = |args: &[&str]| match parse(args) {
+        CliCommand::Migrate(args) | CliCommand::Plan { args, .. } => args.log_level(),
+        CliCommand::Apply { args, .. } => args.log_level(),
+    };
+
+    assert_eq!(level(&["uv-migrator", "."]), None);
+    assert_eq!(level(&["uv-migrator", ".", "-v"]), Some("debug"));
+    assert_eq!(level(&["uv-migrator", ".", "--verbose"]), Some("debug"));
+    assert_eq!(level(&["uv-migrator", ".", "-vv"]), Some("trace"));
+    assert_eq!(
+        level(&["uv-migrator", ".", "-v", "-v", "-v"]),
+        Some("trace")
+    );
+    assert_eq!(level(&["uv-migrator", ".", "--quiet"]), Some("warn"));
+    assert_eq!(level(&["uv-migrator", "plan", ".", "-v"]), Some("debug"));
+
+    assert!(build_command()
+        .try_get_matches_from(["uv-migrator", ".", "-v", "--quiet"])
+        .is_err());
+}
+
+/// Test that `--strip-comments` cannot erase comments other flags write.
+///
+/// This test verifies that:
+/// 1. `--strip-comments` conflicts with `--preserve-dep-comments` and `--index-auth-env`
+/// 2. Library callers setting both options get an error instead of a silent no-op
+#[test]
+fn test_strip_comments_conflicts_with_generated_comments() {
+    for flag in ["--preserve-dep-comments", "--index-auth-env"] {
+        assert!(build_command()
+            .try_get_matches_from(["uv-migrator", ".", "--strip-comments", flag])
+            .is_err());
+    }
+
+    let project = TempDir::new().unwrap();
+    std::fs::write(
+        project.path().join("requirements.txt"),
+        "requests==2.31.0\n",
+    )
+    .unwrap();
+    let args = Args {
+        strip_comments: true,
+        preserve_dep_comments: true,
+        ..Args::default()
+    };
+    let err = run_migration(project.path(), &args)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("--strip-comments"), "{}", err);
+}
+
+/// Test the error category and exit code when no project is detected.
+///
+/// This test verifies that:
+/// 1. Migrating an empty directory fails with `Error::NoProjectDetected`
+/// 2. That error maps to exit code 2
+/// 3. Other failures remain migration errors with exit code 1, even when their
+///    message mentions project detection
+#[test]
+fn test_no_project_detected_exit_code() {
+    let empty = TempDir::new().unwrap();
+
+    let err = run_migration(empty.path(), &Args::default()).unwrap_err();
+    assert_eq!(err, Error::NoProjectDetected);
+    assert_eq!(err.exit_code(), 2);
+
+    let err = Error::from("Failed to run uv add".to_string());
+    assert_eq!(err, Error::Migration("Failed to run uv add".to_string()));
+    assert_eq!(err.exit_code(), 1);
+
+    let message = format!("uv add failed: {}", NO_PROJECT_ERROR);
+    assert_eq!(Error::from(message.clone()), Error::Migration(message));
+}
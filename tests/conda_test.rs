@@ -0,0 +1,278 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use uv_migrator::cli::Args;
+use uv_migrator::migrators::conda::CondaMigrationSource;
+use uv_migrator::migrators::detect::{detect_project_type, ProjectType};
+use uv_migrator::migrators::{self, plan_migration, MigrationSource};
+use uv_migrator::models::SkippedKind;
+use uv_migrator::utils::report::write_skipped_report;
+
+/// Helper function to create a temporary test project with an environment.yml file.
+///
+/// # Arguments
+///
+/// * `content` - The content to write to environment.yml
+///
+/// # Returns
+///
+/// A tuple containing the temporary directory and its path
+fn create_test_project(content: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+    fs::write(project_dir.join("environment.yml"), content).unwrap();
+    (temp_dir, project_dir)
+}
+
+const ENVIRONMENT: &str = r#"
+name: analysis
+channels:
+  - conda-forge
+  - defaults
+dependencies:
+  - python=3.11
+  - numpy=1.26
+  - pandas>=2.0
+  - pytorch
+  - mkl
+  - openssl=3.0
+  - pip
+  - pip:
+      - requests==2.31.0
+"#;
+
+/// Test that Conda projects are detected from environment.yml.
+#[test]
+fn test_detect_conda_project() {
+    let (_temp_dir, project_dir) = create_test_project(ENVIRONMENT);
+
+    assert_eq!(
+        detect_project_type(&project_dir).unwrap(),
+        ProjectType::Conda
+    );
+}
+
+/// Test extraction of Conda and pip dependencies from environment.yml.
+///
+/// This test verifies that:
+/// 1. Conda match specs are converted to PEP 440 specifiers
+/// 2. Conda package names are mapped to their PyPI names
+/// 3. Entries of the nested pip list are included
+/// 4. python, pip and system packages are not added as dependencies
+#[test]
+fn test_extract_conda_dependencies() {
+    let (_temp_dir, project_dir) = create_test_project(ENVIRONMENT);
+
+    let source = CondaMigrationSource::default();
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+    let formatted: Vec<String> = dependencies
+        .iter()
+        .map(migrators::format_dependency)
+        .collect();
+
+    assert_eq!(
+        formatted,
+        vec!["numpy==1.26.*", "pandas>=2.0", "torch", "requests==2.31.0"]
+    );
+    assert_eq!(
+        source.extract_python_version(&project_dir).unwrap(),
+        Some("3.11".to_string())
+    );
+}
+
+/// Test the skipped-items report for a Conda migration.
+///
+/// This test verifies that:
+/// 1. Conda system packages are reported with the reason they were skipped
+/// 2. pip itself is reported as an excluded dependency
+/// 3. The CSV report lists every skipped item with its reason
+#[test]
+fn test_report_skipped_conda_system_packages() {
+    let (_temp_dir, project_dir) = create_test_project(ENVIRONMENT);
+
+    let source = CondaMigrationSource::default();
+    let skipped = source.skipped_items(&project_dir).unwrap();
+
+    let system_packages: Vec<&str> = skipped
+        .iter()
+        .filter(|item| item.kind == SkippedKind::Package)
+        .map(|item| item.name.as_str())
+        .collect();
+    assert_eq!(system_packages, vec!["mkl", "openssl"]);
+    assert!(skipped
+        .iter()
+        .any(|item| item.kind == SkippedKind::Dependency && item.name == "pip"));
+
+    let report_path = project_dir.join("skipped.csv");
+    write_skipped_report(&report_path, &skipped).unwrap();
+
+    let report = fs::read_to_string(&report_path).unwrap();
+    let lines: Vec<&str> = report.lines().collect();
+    assert_eq!(lines[0], "kind,name,reason");
+    assert!(lines.contains(&"package,mkl,Conda system package with no PyPI equivalent"));
+    assert!(lines.contains(&"package,openssl,Conda system package with no PyPI equivalent"));
+    assert!(lines.contains(&"dependency,pip,pip is not needed in a uv-managed environment"));
+}
+
+/// Test that --conda-include-system keeps packages that are normally skipped.
+///
+/// This test verifies that:
+/// 1. System packages are migrated like any other Conda package
+/// 2. They are no longer reported as skipped
+/// 3. python and pip are still left out
+#[test]
+fn test_conda_include_system_keeps_system_packages() {
+    let (_temp_dir, project_dir) = create_test_project(ENVIRONMENT);
+
+    let args = Args {
+        conda_include_system: true,
+        report_skipped: Some(project_dir.join("skipped.csv")),
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+
+    let added: Vec<&String> = plan.add_args.iter().flatten().collect();
+    assert!(added.iter().any(|arg| *arg == "mkl"));
+    assert!(added.iter().any(|arg| *arg == "openssl==3.0.*"));
+    assert!(!added.iter().any(|arg| *arg == "pip" || *arg == "python"));
+    assert!(plan
+        .skipped
+        .iter()
+        .all(|item| item.kind != SkippedKind::Package));
+}
+
+/// Test migrating a Conda project whose environment file has a custom name.
+///
+/// This test verifies that:
+/// 1. A conda.yml file is not discovered on its own
+/// 2. --environment-file selects the Conda source and reads the named file
+/// 3. The Python version is taken from the named file
+#[test]
+fn test_environment_file_override() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join("conda.yml"), ENVIRONMENT).unwrap();
+
+    assert!(plan_migration(project_dir, &Args::default()).is_err());
+
+    let args = Args {
+        environment_file: Some(PathBuf::from("conda.yml")),
+        ..Args::default()
+    };
+    let plan = plan_migration(project_dir, &args).unwrap();
+
+    assert_eq!(plan.project_type, ProjectType::Conda);
+    assert_eq!(
+        plan.init_args,
+        vec!["init", "--python", "3.11", "--vcs", "none"]
+    );
+    assert!(plan.add_args[0].contains(&"numpy==1.26.*".to_string()));
+}
+
+/// Test a pip section that references a requirements file.
+///
+/// This test verifies that:
+/// 1. `-r` entries are resolved relative to the environment file
+/// 2. Packages from the referenced file are added as main dependencies
+/// 3. An editable install of the project itself is skipped
+#[test]
+fn test_pip_requirements_file_reference() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::create_dir(project_dir.join("envs")).unwrap();
+    fs::write(
+        project_dir.join("envs/environment.yml"),
+        "dependencies:\n  - python=3.11\n  - numpy=1.26\n  - pip:\n      - -r requirements.txt\n      - -e .\n",
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("envs/requirements.txt"),
+        "requests==2.31.0\nrich>=13.0\n",
+    )
+    .unwrap();
+
+    let source = CondaMigrationSource::default().environment_file("envs/environment.yml");
+    let (dependencies, skipped) = source.parse_environment(project_dir).unwrap();
+    let formatted: Vec<String> = dependencies
+        .iter()
+        .map(migrators::format_dependency)
+        .collect();
+
+    assert_eq!(
+        formatted,
+        vec!["numpy==1.26.*", "requests==2.31.0", "rich>=13.0"]
+    );
+    assert!(dependencies
+        .iter()
+        .all(|dep| dep.dep_type == migrators::DependencyType::Main));
+    assert!(skipped.iter().any(|item| item.name == "-e ."));
+}
+
+/// Test Conda to PyPI name mappings supplied at runtime.
+///
+/// This test verifies that:
+/// 1. A supplied mapping overrides the built-in one for the same Conda name
+/// 2. A supplied mapping extends the table with new names
+/// 3. `--conda-map` values reach the planned `uv add` command
+/// 4. A malformed mapping is rejected
+#[test]
+fn test_custom_conda_name_mappings() {
+    let (_temp_dir, project_dir) =
+        create_test_project("dependencies:\n  - pytorch\n  - faiss-gpu=1.7.4\n  - pandas>=2.0\n");
+
+    let source = CondaMigrationSource::new(HashMap::from([
+        ("pytorch".to_string(), "torch-nightly".to_string()),
+        ("FAISS-GPU".to_string(), "faiss-gpu-cu12".to_string()),
+    ]));
+    let formatted: Vec<String> = source
+        .extract_dependencies(&project_dir)
+        .unwrap()
+        .iter()
+        .map(migrators::format_dependency)
+        .collect();
+    assert_eq!(
+        formatted,
+        vec!["torch-nightly", "faiss-gpu-cu12==1.7.4", "pandas>=2.0"]
+    );
+
+    let args = Args {
+        conda_map: vec!["pytorch=torch-nightly".to_string()],
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    assert!(plan.add_args[0].contains(&"torch-nightly".to_string()));
+
+    let args = Args {
+        conda_map: vec!["pytorch".to_string()],
+        ..Args::default()
+    };
+    let err = plan_migration(&project_dir, &args).unwrap_err().to_string();
+    assert!(
+        err.contains("expected NAME=PYPI_NAME"),
+        "unexpected: {}",
+        err
+    );
+}
+
+/// Test that Conda Python specs become a version uv init accepts.
+///
+/// This test verifies that:
+/// 1. A range such as `>=3.9,<3.12` is reduced to its lower bound
+/// 2. An exact pin is passed through with its patch version
+/// 3. A spec without a lower bound does not pin Python
+#[test]
+fn test_python_version_from_conda_spec() {
+    let python_version = |spec: &str| {
+        let environment = format!("dependencies:\n  - {}\n  - numpy\n", spec);
+        let (_temp_dir, project_dir) = create_test_project(&environment);
+        CondaMigrationSource::default()
+            .extract_python_version(&project_dir)
+            .unwrap()
+    };
+
+    assert_eq!(python_version("python>=3.9,<3.12"), Some("3.9".to_string()));
+    assert_eq!(python_version("python=3.10"), Some("3.10".to_string()));
+    assert_eq!(python_version("python==3.11.4"), Some("3.11.4".to_string()));
+    assert_eq!(python_version("python<3.12"), None);
+}
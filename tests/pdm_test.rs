@@ -0,0 +1,134 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use uv_migrator::migrators::detect::{detect_project_type, ProjectType};
+use uv_migrator::migrators::pdm::PdmMigrationSource;
+use uv_migrator::migrators::{self, DependencyType, MigrationSource};
+
+/// Helper function to create a temporary test project with a pyproject.toml file.
+///
+/// # Arguments
+///
+/// * `content` - The content to write to pyproject.toml
+///
+/// # Returns
+///
+/// A tuple containing the temporary directory and its path
+fn create_test_project(content: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+    fs::write(project_dir.join("pyproject.toml"), content).unwrap();
+    (temp_dir, project_dir)
+}
+
+const PDM_PYPROJECT: &str = r#"
+[project]
+name = "pdm-project"
+version = "0.1.0"
+requires-python = ">=3.10"
+dependencies = [
+    "requests>=2.31.0",
+    "rich==13.7.0",
+    "pywin32>=306; sys_platform == 'win32'",
+]
+
+[tool.pdm.dev-dependencies]
+dev = ["pytest>=7.0"]
+lint = ["ruff>=0.1.0", "-e file:///${PROJECT_ROOT}/../shared"]
+
+[tool.pdm.resolution]
+excludes = ["pywin32"]
+"#;
+
+/// Test that a pyproject.toml with a [tool.pdm] table is detected as PDM rather than Poetry.
+#[test]
+fn test_detect_pdm_project() {
+    let (_temp_dir, project_dir) = create_test_project(PDM_PYPROJECT);
+
+    assert_eq!(detect_project_type(&project_dir).unwrap(), ProjectType::Pdm);
+}
+
+/// Test extraction of PDM project and dev-dependency groups.
+///
+/// This test verifies that:
+/// 1. PEP 621 project dependencies become main dependencies
+/// 2. The "dev" list maps to dev dependencies and other lists to named groups
+/// 3. Editable local dependencies are skipped
+/// 4. Packages excluded by [tool.pdm.resolution] are skipped without erroring
+#[test]
+fn test_extract_pdm_dependencies() {
+    let (_temp_dir, project_dir) = create_test_project(PDM_PYPROJECT);
+
+    let source = PdmMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    assert_eq!(dependencies.len(), 4);
+
+    let requests = dependencies.iter().find(|d| d.name == "requests").unwrap();
+    assert_eq!(requests.dep_type, DependencyType::Main);
+    assert_eq!(migrators::format_dependency(requests), "requests>=2.31.0");
+
+    let rich = dependencies.iter().find(|d| d.name == "rich").unwrap();
+    assert_eq!(migrators::format_dependency(rich), "rich==13.7.0");
+
+    let pytest = dependencies.iter().find(|d| d.name == "pytest").unwrap();
+    assert_eq!(pytest.dep_type, DependencyType::Dev);
+
+    let ruff = dependencies.iter().find(|d| d.name == "ruff").unwrap();
+    assert_eq!(ruff.dep_type, DependencyType::Group("lint".to_string()));
+
+    assert!(!dependencies.iter().any(|d| d.name == "pywin32"));
+
+    let skipped = source.skipped_items(&project_dir).unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].name, "pywin32");
+}
+
+/// Test that requires-python is read from the backed up pyproject.toml.
+#[test]
+fn test_extract_pdm_python_version() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path();
+    fs::write(project_dir.join("old.pyproject.toml"), PDM_PYPROJECT).unwrap();
+
+    assert_eq!(
+        PdmMigrationSource::extract_python_version(project_dir).unwrap(),
+        Some("3.10".to_string())
+    );
+}
+
+/// Test that PDM extras are migrated as optional dependencies.
+///
+/// This test verifies that:
+/// 1. Each [project.optional-dependencies] table becomes an optional dependency of that extra
+/// 2. Extras are added with `uv add --optional <extra>`
+#[test]
+fn test_extract_pdm_optional_dependencies() {
+    let content = format!(
+        "{}\n[project.optional-dependencies]\nsocks = [\"pysocks>=1.7\"]\nyaml = [\"pyyaml>=6.0\"]\n",
+        PDM_PYPROJECT
+    );
+    let (_temp_dir, project_dir) = create_test_project(&content);
+
+    let dependencies = PdmMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let pysocks = dependencies.iter().find(|d| d.name == "pysocks").unwrap();
+    assert_eq!(
+        pysocks.dep_type,
+        DependencyType::Optional("socks".to_string())
+    );
+    let pyyaml = dependencies.iter().find(|d| d.name == "pyyaml").unwrap();
+    assert_eq!(
+        pyyaml.dep_type,
+        DependencyType::Optional("yaml".to_string())
+    );
+
+    let plan = migrators::plan_migration(&project_dir, &Default::default()).unwrap();
+    assert!(plan.add_args.iter().any(|args| args.starts_with(&[
+        "add".to_string(),
+        "--optional".to_string(),
+        "socks".to_string()
+    ]) && args.contains(&"pysocks>=1.7".to_string())));
+}
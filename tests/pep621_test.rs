@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use uv_migrator::migrators::detect::{detect_project_type, ProjectType};
+use uv_migrator::migrators::pep621::Pep621MigrationSource;
+use uv_migrator::migrators::{self, DependencyType, MigrationSource};
+
+/// Helper function to create a temporary test project with a pyproject.toml file.
+///
+/// # Arguments
+///
+/// * `content` - The content to write to pyproject.toml
+///
+/// # Returns
+///
+/// A tuple containing the temporary directory and its path
+fn create_test_project(content: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+    fs::write(project_dir.join("pyproject.toml"), content).unwrap();
+    (temp_dir, project_dir)
+}
+
+const PEP621_PYPROJECT: &str = r#"
+[build-system]
+requires = ["hatchling"]
+build-backend = "hatchling.build"
+
+[project]
+name = "standard-project"
+version = "0.1.0"
+requires-python = ">=3.11"
+dependencies = [
+    "httpx>=0.27",
+    "pydantic[email]==2.7.1",
+]
+
+[project.optional-dependencies]
+docs = ["mkdocs>=1.5"]
+
+[dependency-groups]
+test = ["pytest>=8.0"]
+dev = [
+    "ruff>=0.4",
+    { include-group = "test" },
+]
+"#;
+
+/// Test that a [project] table with a non-Poetry backend is detected as PEP 621.
+#[test]
+fn test_detect_pep621_project() {
+    let (_temp_dir, project_dir) = create_test_project(PEP621_PYPROJECT);
+
+    assert_eq!(
+        detect_project_type(&project_dir).unwrap(),
+        ProjectType::Pep621
+    );
+}
+
+/// Test extraction of dependencies from standard pyproject.toml tables.
+///
+/// This test verifies that:
+/// 1. [project].dependencies become main dependencies, keeping extras
+/// 2. [project.optional-dependencies] tables become optional dependencies of that extra
+/// 3. The "dev" dependency group maps to dev dependencies and other groups keep their name
+/// 4. include-group entries pull in the referenced group's requirements
+#[test]
+fn test_extract_pep621_dependencies() {
+    let (_temp_dir, project_dir) = create_test_project(PEP621_PYPROJECT);
+
+    let dependencies = Pep621MigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let httpx = dependencies.iter().find(|d| d.name == "httpx").unwrap();
+    assert_eq!(httpx.dep_type, DependencyType::Main);
+    assert_eq!(migrators::format_dependency(httpx), "httpx>=0.27");
+
+    let pydantic = dependencies.iter().find(|d| d.name == "pydantic").unwrap();
+    assert_eq!(pydantic.dep_type, DependencyType::Main);
+    assert_eq!(pydantic.extras, Some(vec!["email".to_string()]));
+
+    let mkdocs = dependencies.iter().find(|d| d.name == "mkdocs").unwrap();
+    assert_eq!(
+        mkdocs.dep_type,
+        DependencyType::Optional("docs".to_string())
+    );
+
+    let ruff = dependencies.iter().find(|d| d.name == "ruff").unwrap();
+    assert_eq!(ruff.dep_type, DependencyType::Dev);
+
+    let pytest_types: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.name == "pytest")
+        .map(|d| d.dep_type.clone())
+        .collect();
+    assert!(pytest_types.contains(&DependencyType::Dev));
+    assert!(pytest_types.contains(&DependencyType::Group("test".to_string())));
+}
+
+/// Test that PEP 621 extras stay installable as `pkg[extra]` after the migration.
+///
+/// This test verifies that:
+/// 1. Extras are added with `uv add --optional <extra>`, not as dependency groups
+/// 2. No `--group` is created for an extra
+#[test]
+fn test_pep621_extras_stay_optional() {
+    let (_temp_dir, project_dir) = create_test_project(PEP621_PYPROJECT);
+
+    let plan = migrators::plan_migration(&project_dir, &Default::default()).unwrap();
+    assert!(plan.add_args.iter().any(|args| args.starts_with(&[
+        "add".to_string(),
+        "--optional".to_string(),
+        "docs".to_string()
+    ]) && args.contains(&"mkdocs>=1.5".to_string())));
+    assert!(!plan
+        .add_args
+        .iter()
+        .any(|args| args.contains(&"--group".to_string()) && args.contains(&"docs".to_string())));
+}
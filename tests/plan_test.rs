@@ -0,0 +1,625 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use uv_migrator::cli::{
+    build_command, check_apply_options, command_from_matches, Args, CliCommand,
+};
+use uv_migrator::migrators::detect::{ProjectType, ALREADY_MIGRATED_ERROR};
+use uv_migrator::migrators::{apply_plan, plan_migration};
+use uv_migrator::models::{MigrationPlan, PythonSelection, PythonSource};
+use uv_migrator::{migrate, Error, MigrationConfig};
+
+/// Helper function to create a temporary test project with the given files.
+///
+/// # Arguments
+///
+/// * `files` - A vector of tuples containing filename and content for each file
+///
+/// # Returns
+///
+/// A tuple containing the temporary directory and its path
+fn create_test_project(files: Vec<(&str, &str)>) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+
+    for (filename, content) in files {
+        fs::write(project_dir.join(filename), content).unwrap();
+    }
+
+    (temp_dir, project_dir)
+}
+
+/// Test that a plan captures detection, extraction and the exact uv commands.
+///
+/// This test verifies that:
+/// 1. The detected project type and dependency counts are recorded
+/// 2. The `uv init` and `uv add` arguments are computed up front
+/// 3. Planning does not change any files
+#[test]
+fn test_plan_generation() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("requirements.txt", "requests==2.31.0\n"),
+        ("requirements-dev.txt", "pytest>=8.0\n"),
+    ]);
+
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+
+    assert_eq!(plan.project_type, ProjectType::Requirements);
+    assert!(!plan.is_package);
+    assert_eq!(
+        plan.init_args,
+        vec!["init", "--no-pin-python", "--vcs", "none"]
+    );
+    assert_eq!(plan.dependencies.main, 1);
+    assert_eq!(plan.dependencies.dev, 1);
+
+    let mut add_args = plan.add_args.clone();
+    add_args.sort();
+    assert_eq!(
+        add_args,
+        vec![
+            vec!["add", "--dev", "pytest>=8.0"],
+            vec!["add", "requests==2.31.0"],
+        ]
+    );
+
+    let mut files: Vec<_> = fs::read_dir(&project_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec!["requirements-dev.txt", "requirements.txt"]);
+}
+
+/// Test that a package declared in main and a group is only added once.
+///
+/// This test verifies that:
+/// 1. By default the main entry is kept and the dev group duplicate is dropped
+/// 2. With `--prefer-group` the dev entry is kept and the main one is dropped
+/// 3. Other dependencies are unaffected
+#[test]
+fn test_main_and_group_duplicates() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "2.31.0"
+pytest = "8.0.0"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "8.0.0"
+ruff = "0.4.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(vec![("pyproject.toml", content)]);
+
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    let mut add_args = plan.add_args.clone();
+    add_args.sort();
+    assert_eq!(
+        add_args,
+        vec![
+            vec!["add", "--dev", "ruff==0.4.0"],
+            vec!["add", "requests==2.31.0", "pytest==8.0.0"],
+        ]
+    );
+
+    let args = Args {
+        prefer_group: true,
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    let mut add_args = plan.add_args.clone();
+    add_args.sort();
+    assert_eq!(
+        add_args,
+        vec![
+            vec!["add", "--dev", "pytest==8.0.0", "ruff==0.4.0"],
+            vec!["add", "requests==2.31.0"],
+        ]
+    );
+}
+
+/// Test that the plan and the migration report record the Python version chosen for uv init.
+///
+/// This test verifies that:
+/// 1. A Poetry python constraint is used and recorded as its source
+/// 2. `--python` overrides it and is recorded as an override
+/// 3. `--no-pin-python` leaves the version unpinned and says why
+/// 4. The choice is carried through to the report of a (dry) migration
+#[test]
+fn test_python_selection_recorded() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(vec![("pyproject.toml", content)]);
+
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    assert_eq!(
+        plan.python,
+        PythonSelection {
+            version: Some("3.11".to_string()),
+            source: PythonSource::PoetryConstraint,
+        }
+    );
+
+    let args = Args {
+        python: Some("3.12".to_string()),
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    assert_eq!(plan.python.version.as_deref(), Some("3.12"));
+    assert_eq!(plan.python.source, PythonSource::Override);
+    assert!(plan.init_args.contains(&"3.12".to_string()));
+
+    let args = Args {
+        no_pin_python: true,
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    assert_eq!(plan.python.version, None);
+    assert_eq!(plan.python.source, PythonSource::NotPinned);
+
+    let report = migrate(&MigrationConfig::new(&project_dir).dry_run(true)).unwrap();
+    assert_eq!(report.python.version.as_deref(), Some("3.11"));
+    assert_eq!(report.python.source, PythonSource::PoetryConstraint);
+}
+
+/// Test that a saved plan round-trips through its file and can be applied.
+///
+/// This test verifies that:
+/// 1. A plan written to disk loads back with identical commands and options
+/// 2. Applying the loaded plan (as a dry run) reports the planned dependencies
+#[test]
+fn test_plan_round_trip_apply() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+
+[tool.poetry.group.docs.dependencies]
+mkdocs = "^1.5.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(vec![("pyproject.toml", content)]);
+    let args = Args {
+        dry_run: true,
+        merge_groups: true,
+        ..Args::default()
+    };
+
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    let plan_path = project_dir.join("plan.json");
+    plan.save(&plan_path).unwrap();
+
+    let loaded = MigrationPlan::load(&plan_path).unwrap();
+    assert_eq!(loaded.project_type, plan.project_type);
+    assert_eq!(
+        loaded.init_args,
+        vec!["init", "--python", "3.11", "--vcs", "none"]
+    );
+    assert_eq!(loaded.add_args, plan.add_args);
+    assert!(loaded.options.merge_groups);
+    assert!(loaded.options.dry_run);
+
+    let summary = apply_plan(&loaded).unwrap();
+    assert!(summary.dry_run);
+    assert_eq!(summary.dependencies.main, 1);
+    assert_eq!(summary.dependencies.dev, 1);
+    assert_eq!(
+        fs::read_to_string(project_dir.join("pyproject.toml")).unwrap(),
+        content
+    );
+}
+
+/// Test that planning the same project twice produces identical uv commands.
+///
+/// This test verifies that:
+/// 1. `uv add` invocations follow the order in which dependency types first appear
+/// 2. Dependencies keep their order from the source file within each invocation
+/// 3. Repeated runs produce the same arguments
+#[test]
+fn test_add_args_are_deterministic() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        (
+            "requirements.txt",
+            "zope.interface\nrequests==2.31.0\nattrs\n",
+        ),
+        ("requirements-dev.txt", "pytest>=8.0\nblack\n"),
+        ("requirements-docs.txt", "sphinx\n"),
+        ("requirements-lint.txt", "ruff\n"),
+    ]);
+
+    let first = plan_migration(&project_dir, &Args::default()).unwrap();
+    for _ in 0..5 {
+        let again = plan_migration(&project_dir, &Args::default()).unwrap();
+        assert_eq!(again.add_args, first.add_args);
+    }
+
+    assert_eq!(
+        first.add_args,
+        vec![
+            vec!["add", "zope.interface", "requests==2.31.0", "attrs"],
+            vec!["add", "--dev", "pytest>=8.0", "black"],
+            vec!["add", "--group", "docs", "sphinx"],
+            vec!["add", "--group", "lint", "ruff"],
+        ]
+    );
+}
+
+/// Test that `--strict-detection` refuses to guess between project types.
+///
+/// This test verifies that:
+/// 1. A Poetry project with a Pipfile is migrated as Poetry by default
+/// 2. With `--strict-detection` planning fails and lists both project types
+/// 3. `--from` picks one of the detected project types
+#[test]
+fn test_strict_detection_with_poetry_and_pipfile() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        (
+            "pyproject.toml",
+            "[tool.poetry]\nname = \"test-project\"\nversion = \"0.1.0\"\n\n\
+            [tool.poetry.dependencies]\npython = \"^3.11\"\nrequests = \"^2.31.0\"\n",
+        ),
+        (
+            "Pipfile",
+            "[packages]\nflask = \"*\"\n\n[requires]\npython_version = \"3.11\"\n",
+        ),
+        (
+            "Pipfile.lock",
+            r#"{"_meta": {}, "default": {"flask": {"version": "==3.0.0"}}, "develop": {}}"#,
+        ),
+    ]);
+
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    assert!(matches!(plan.project_type, ProjectType::Poetry(_)));
+
+    let strict = Args {
+        strict_detection: true,
+        ..Args::default()
+    };
+    let err = plan_migration(&project_dir, &strict)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.contains("Ambiguous project type"),
+        "unexpected: {}",
+        err
+    );
+    assert!(err.contains("poetry, pipenv"), "unexpected: {}", err);
+    assert!(err.contains("--from"), "unexpected: {}", err);
+
+    let from_pipenv = Args {
+        from: Some("pipenv".to_string()),
+        ..strict
+    };
+    let plan = plan_migration(&project_dir, &from_pipenv).unwrap();
+    assert_eq!(plan.project_type, ProjectType::Pipenv);
+}
+
+/// Test that `--python` overrides the detected Python version.
+///
+/// This test verifies that:
+/// 1. Without the flag the Poetry python constraint is pinned
+/// 2. The `--python` value replaces it in the `uv init` arguments
+/// 3. A value that is not X.Y or X.Y.Z is rejected before anything runs
+#[test]
+fn test_python_override() {
+    let (_temp_dir, project_dir) = create_test_project(vec![(
+        "pyproject.toml",
+        "[tool.poetry]\nname = \"test-project\"\nversion = \"0.1.0\"\n\n\
+        [tool.poetry.dependencies]\npython = \"^3.11\"\n",
+    )]);
+
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    assert_eq!(
+        plan.init_args,
+        vec!["init", "--python", "3.11", "--vcs", "none"]
+    );
+
+    let args = Args {
+        python: Some("3.12.4".to_string()),
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    assert_eq!(
+        plan.init_args,
+        vec!["init", "--python", "3.12.4", "--vcs", "none"]
+    );
+
+    let args = Args {
+        python: Some("python3".to_string()),
+        ..Args::default()
+    };
+    let err = plan_migration(&project_dir, &args).unwrap_err().to_string();
+    assert!(err.contains("expected X.Y or X.Y.Z"), "unexpected: {}", err);
+}
+
+/// Test that malformed version constraints are caught before the project is changed.
+///
+/// This test verifies that:
+/// 1. Planning fails and lists every dependency with an invalid constraint at once
+/// 2. Nothing is written to the project directory
+/// 3. With `--skip-invalid` the offending dependencies are left out and reported as skipped
+#[test]
+fn test_invalid_version_specifiers() {
+    let pyproject = "[tool.poetry]\nname = \"test-project\"\nversion = \"0.1.0\"\n\n\
+        [tool.poetry.dependencies]\npython = \"^3.11\"\nfastapi = \"^^0.1\"\n\
+        requests = \">=,<2\"\nhttpx = \"^0.27.0\"\n";
+    let (_temp_dir, project_dir) = create_test_project(vec![("pyproject.toml", pyproject)]);
+
+    let err = plan_migration(&project_dir, &Args::default())
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("fastapi ('^^0.1')"), "unexpected: {}", err);
+    assert!(err.contains("requests ('>=,<2')"), "unexpected: {}", err);
+    assert!(!err.contains("httpx"), "unexpected: {}", err);
+    assert!(err.contains("--skip-invalid"));
+    assert_eq!(
+        fs::read_to_string(project_dir.join("pyproject.toml")).unwrap(),
+        pyproject
+    );
+
+    let args = Args {
+        skip_invalid: true,
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    let added: Vec<&String> = plan.add_args.iter().flatten().collect();
+    assert!(added.iter().any(|arg| arg.starts_with("httpx")));
+    assert!(!added
+        .iter()
+        .any(|arg| arg.starts_with("fastapi") || arg.starts_with("requests")));
+
+    let skipped: Vec<&str> = plan.skipped.iter().map(|s| s.name.as_str()).collect();
+    assert!(skipped.contains(&"fastapi"));
+    assert!(skipped.contains(&"requests"));
+}
+
+/// Test that `--skip-invalid` only drops the entry with the invalid constraint.
+///
+/// This test verifies that:
+/// 1. A valid entry of the same package in another group is still added
+/// 2. Only the invalid entry is reported as skipped
+#[test]
+fn test_skip_invalid_keeps_valid_entries_of_same_package() {
+    let pyproject = "[tool.poetry]\nname = \"test-project\"\nversion = \"0.1.0\"\n\n\
+        [tool.poetry.dependencies]\npython = \"^3.11\"\n\n\
+        [tool.poetry.group.dev.dependencies]\nrequests = \">=,<2\"\n\n\
+        [tool.poetry.group.test.dependencies]\nrequests = \"^2.31.0\"\n";
+    let (_temp_dir, project_dir) = create_test_project(vec![("pyproject.toml", pyproject)]);
+
+    let args = Args {
+        skip_invalid: true,
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+
+    let test_group = plan
+        .add_args
+        .iter()
+        .find(|args| args.contains(&"test".to_string()))
+        .unwrap();
+    assert!(test_group.contains(&"requests>=2.31.0,<3.0.0".to_string()));
+    assert_eq!(
+        plan.skipped.iter().filter(|s| s.name == "requests").count(),
+        1
+    );
+}
+
+/// Test that the plan and apply steps refuse to migrate an already migrated project.
+///
+/// This test verifies that:
+/// 1. Planning a project with uv.lock and a plain `[project]` table fails without `--force`
+/// 2. `--force` allows planning it again
+/// 3. Applying a plan fails when the project was migrated after the plan was made,
+///    before anything is backed up
+#[test]
+fn test_plan_and_apply_refuse_migrated_project() {
+    let migrated = "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n\
+        dependencies = [\"requests>=2.31.0\"]\n";
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("pyproject.toml", migrated),
+        ("uv.lock", "version = 1\n"),
+    ]);
+
+    let err = plan_migration(&project_dir, &Args::default()).unwrap_err();
+    assert!(
+        matches!(err, Error::AlreadyMigrated(_)),
+        "unexpected: {}",
+        err
+    );
+    assert!(err.to_string().contains(ALREADY_MIGRATED_ERROR));
+
+    let args = Args {
+        force: true,
+        ..Args::default()
+    };
+    assert!(plan_migration(&project_dir, &args).is_ok());
+
+    let (_temp_dir, project_dir) =
+        create_test_project(vec![("requirements.txt", "requests==2.31.0\n")]);
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+
+    fs::write(project_dir.join("pyproject.toml"), migrated).unwrap();
+    fs::write(project_dir.join("uv.lock"), "version = 1\n").unwrap();
+
+    let err = apply_plan(&plan).unwrap_err();
+    assert!(
+        matches!(err, Error::AlreadyMigrated(_)),
+        "unexpected: {}",
+        err
+    );
+    assert!(!project_dir.join("old.pyproject.toml").exists());
+    assert_eq!(
+        fs::read_to_string(project_dir.join("pyproject.toml")).unwrap(),
+        migrated
+    );
+}
+
+/// Test that a plan is not applied once the project's source files have changed.
+///
+/// This test verifies that:
+/// 1. Editing a requirements file after planning makes applying the plan fail
+/// 2. Adding a new requirements file after planning makes applying the plan fail
+/// 3. Editing a file included with `-r` or `-c` makes applying the plan fail
+/// 4. Nothing is backed up or changed when the plan is refused
+#[test]
+fn test_apply_refuses_changed_sources() {
+    let edits = [
+        ("requirements.txt", "requests==2.31.0\n-r extra/base.txt\n"),
+        ("extra/base.txt", "-c constraints.txt\nclick\n"),
+        ("extra/constraints.txt", "click==8.1.7\n"),
+    ];
+    let (_temp_dir, project_dir) = create_test_project(Vec::new());
+    fs::create_dir(project_dir.join("extra")).unwrap();
+    for (file, content) in edits {
+        fs::write(project_dir.join(file), content).unwrap();
+    }
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    let assert_refused = || {
+        let err = apply_plan(&plan).unwrap_err().to_string();
+        assert!(err.contains("changed since the plan was made"), "{}", err);
+    };
+
+    for (file, original) in edits {
+        fs::write(project_dir.join(file), format!("{}flask\n", original)).unwrap();
+        assert_refused();
+        fs::write(project_dir.join(file), original).unwrap();
+    }
+
+    fs::write(project_dir.join("requirements-dev.txt"), "pytest==8.0.0\n").unwrap();
+    assert_refused();
+
+    assert!(!project_dir.join("pyproject.toml").exists());
+    assert!(!project_dir.join("old.pyproject.toml").exists());
+}
+
+/// Test that a Pipenv plan covers Pipfile.lock, which its dependencies come from.
+///
+/// This test verifies that:
+/// 1. Editing Pipfile.lock after planning makes applying the plan fail
+#[test]
+fn test_apply_refuses_changed_pipfile_lock() {
+    let lock = |version: &str| {
+        format!(
+            r#"{{"_meta": {{}}, "default": {{"requests": {{"version": "=={}"}}}}, "develop": {{}}}}"#,
+            version
+        )
+    };
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("Pipfile", "[packages]\nrequests = \"*\"\n"),
+        ("Pipfile.lock", &lock("2.31.0")),
+    ]);
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+
+    fs::write(project_dir.join("Pipfile.lock"), lock("2.32.0")).unwrap();
+    let err = apply_plan(&plan).unwrap_err().to_string();
+    assert!(err.contains("changed since the plan was made"), "{}", err);
+}
+
+/// Test that a plan file cannot skip the source check.
+///
+/// This test verifies that:
+/// 1. A plan without `source_hash` fails to load
+/// 2. A plan from another format version fails to load
+#[test]
+fn test_load_requires_source_hash_and_format_version() {
+    let (_temp_dir, project_dir) =
+        create_test_project(vec![("requirements.txt", "requests==2.31.0\n")]);
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    let plan_path = project_dir.join("plan.json");
+    let json: serde_json::Value = serde_json::from_str(&plan.to_json().unwrap()).unwrap();
+
+    let mut without_hash = json.clone();
+    without_hash.as_object_mut().unwrap().remove("source_hash");
+    fs::write(&plan_path, without_hash.to_string()).unwrap();
+    let err = MigrationPlan::load(&plan_path).unwrap_err();
+    assert!(err.contains("source_hash"), "{}", err);
+
+    let mut old_format = json;
+    old_format.as_object_mut().unwrap().remove("format_version");
+    fs::write(&plan_path, old_format.to_string()).unwrap();
+    let err = MigrationPlan::load(&plan_path).unwrap_err();
+    assert!(err.contains("create a new plan"), "{}", err);
+}
+
+/// Test that the library entry point reports typed error categories.
+///
+/// This test verifies that:
+/// 1. `migrate` on an empty directory returns `Error::NoProjectDetected`
+/// 2. `migrate` on an already migrated project returns `Error::AlreadyMigrated`
+/// 3. `force(true)` lets the migrated project be planned again
+#[test]
+fn test_migrate_returns_typed_errors() {
+    let empty = TempDir::new().unwrap();
+    let err = migrate(&MigrationConfig::new(empty.path()).dry_run(true)).unwrap_err();
+    assert_eq!(err, Error::NoProjectDetected);
+
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        (
+            "pyproject.toml",
+            "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\ndependencies = []\n",
+        ),
+        ("uv.lock", "version = 1\n"),
+    ]);
+    let err = migrate(&MigrationConfig::new(&project_dir).dry_run(true)).unwrap_err();
+    assert!(
+        matches!(err, Error::AlreadyMigrated(_)),
+        "unexpected: {}",
+        err
+    );
+
+    assert!(migrate(&MigrationConfig::new(&project_dir).dry_run(true).force(true)).is_ok());
+}
+
+/// Test that `apply --dry-run` never runs a plan made without `--dry-run`.
+///
+/// This test verifies that:
+/// 1. `--dry-run` on the apply command line turns the loaded plan into a dry run
+/// 2. Applying it changes no files and reports a dry run
+/// 3. Migration options other than the output and safety flags are rejected on apply
+#[test]
+fn test_apply_dry_run_overrides_plan() {
+    let (_temp_dir, project_dir) =
+        create_test_project(vec![("requirements.txt", "requests==2.31.0\n")]);
+    let plan = plan_migration(&project_dir, &Args::default()).unwrap();
+    assert!(!plan.options.dry_run);
+    let plan_path = project_dir.join("plan.json");
+    plan.save(&plan_path).unwrap();
+
+    let plan_arg = plan_path.to_str().unwrap();
+    let matches = build_command()
+        .try_get_matches_from(["uv-migrator", "apply", plan_arg, "--dry-run"])
+        .unwrap();
+    let (_, apply_matches) = matches.subcommand().unwrap();
+    assert!(check_apply_options(apply_matches).is_ok());
+    let CliCommand::Apply { args, plan } = command_from_matches(&matches) else {
+        panic!("expected the apply command");
+    };
+
+    let mut loaded = MigrationPlan::load(&plan).unwrap();
+    loaded.apply_cli_overrides(&args);
+    assert!(loaded.options.dry_run);
+    let summary = apply_plan(&loaded).unwrap();
+    assert!(summary.dry_run);
+    assert!(!project_dir.join("pyproject.toml").exists());
+
+    let matches = build_command()
+        .try_get_matches_from(["uv-migrator", "apply", plan_arg, "--force"])
+        .unwrap();
+    let (_, apply_matches) = matches.subcommand().unwrap();
+    let err = check_apply_options(apply_matches).unwrap_err();
+    assert!(err.contains("--force"), "{}", err);
+}
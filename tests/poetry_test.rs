@@ -1,10 +1,15 @@
 use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
+use uv_migrator::cli::Args;
 use uv_migrator::migrators::poetry::PoetryMigrationSource;
 use uv_migrator::migrators::{self};
-use uv_migrator::migrators::{DependencyType, MigrationSource};
+use uv_migrator::migrators::{Dependency, DependencyType, MigrationSource};
 use uv_migrator::utils::author::extract_authors_from_poetry;
+use uv_migrator::utils::pyproject::{
+    extract_poetry_git_dependencies, extract_poetry_path_dependencies, extract_poetry_source_pins,
+    update_git_dependencies, update_path_dependencies, update_source_pins, SourcePin,
+};
 use uv_migrator::utils::update_pyproject_toml;
 
 /// Helper function to create a temporary test project with a pyproject.toml file.
@@ -61,6 +66,726 @@ uvicorn = { extras = ["standard"], version = "^0.30.1" }
     assert_eq!(aiofiles_dep.dep_type, DependencyType::Main);
 }
 
+/// Test that Poetry `markers` are passed through verbatim.
+///
+/// This test verifies that:
+/// 1. `platform_machine` and `platform_system` markers are extracted unchanged
+/// 2. Both inline-table and full-table dependency forms carry markers
+/// 3. The rendered `uv add` argument includes the marker as-is
+#[test]
+fn test_extract_platform_markers() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+tensorflow-macos = { version = "2.15.0", markers = "platform_machine == 'arm64'" }
+
+[tool.poetry.dependencies.pywin32]
+version = "306"
+markers = "platform_system == 'Windows'"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let source = PoetryMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    let tf_dep = dependencies
+        .iter()
+        .find(|d| d.name == "tensorflow-macos")
+        .unwrap();
+    assert_eq!(
+        tf_dep.environment_markers,
+        Some("platform_machine == 'arm64'".to_string())
+    );
+    assert_eq!(
+        migrators::format_dependency(tf_dep),
+        "tensorflow-macos==2.15.0; platform_machine == 'arm64'"
+    );
+
+    let pywin_dep = dependencies.iter().find(|d| d.name == "pywin32").unwrap();
+    assert_eq!(
+        pywin_dep.environment_markers,
+        Some("platform_system == 'Windows'".to_string())
+    );
+}
+
+/// Test that a dependency's `python` key becomes a `python_version` marker.
+///
+/// This test verifies that:
+/// 1. `python = "^3.8"` on an inline-table dependency becomes `python_version >= '3.8'`
+/// 2. The full-table form is translated the same way
+/// 3. The rendered `uv add` argument carries the marker
+#[test]
+fn test_dependency_python_key_marker() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+tomli = { version = "^2.0", python = "^3.8" }
+
+[tool.poetry.dependencies.backports-zoneinfo]
+version = "^0.2"
+python = "<3.9"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let tomli = dependencies.iter().find(|d| d.name == "tomli").unwrap();
+    assert_eq!(
+        tomli.environment_markers.as_deref(),
+        Some("python_version >= '3.8'")
+    );
+    assert_eq!(
+        migrators::format_dependency(tomli),
+        "tomli>=2.0,<3.0.0; python_version >= '3.8'"
+    );
+
+    let zoneinfo = dependencies
+        .iter()
+        .find(|d| d.name == "backports-zoneinfo")
+        .unwrap();
+    assert_eq!(
+        zoneinfo.environment_markers.as_deref(),
+        Some("python_version < '3.9'")
+    );
+}
+
+/// Test that markers on dependencies inside a custom group survive extraction.
+///
+/// This test verifies that:
+/// 1. Group dependencies read the `markers` key like main dependencies do
+/// 2. The marker is emitted on the `uv add --group` argument list
+#[test]
+fn test_group_dependency_markers() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+
+[tool.poetry.group.platform.dependencies]
+pywin32 = { version = "306", markers = "sys_platform == 'win32'" }
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let source = PoetryMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    let group_type = DependencyType::Group("platform".to_string());
+    let group_deps: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.dep_type == group_type)
+        .collect();
+    assert_eq!(group_deps.len(), 1);
+
+    let args = migrators::build_add_args(&group_type, &group_deps);
+    assert_eq!(
+        args,
+        vec![
+            "add",
+            "--group",
+            "platform",
+            "pywin32==306; sys_platform == 'win32'"
+        ]
+    );
+}
+
+/// Test that an `extras` value written as a bare string is treated as one extra.
+///
+/// This test verifies that:
+/// 1. `extras = "cli"` becomes a one-element extras list
+/// 2. The dependency is rendered as `pkg[cli]`
+#[test]
+fn test_string_extras() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+pkg = { version = "*", extras = "cli" }
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let pkg = dependencies.iter().find(|d| d.name == "pkg").unwrap();
+
+    assert_eq!(pkg.extras, Some(vec!["cli".to_string()]));
+    assert_eq!(migrators::format_dependency(pkg), "pkg[cli]");
+}
+
+/// Test a fully specified Poetry dependency with version, extras, source and markers.
+///
+/// This test verifies that:
+/// 1. Version, extras and markers combine into a single `uv add` requirement
+/// 2. The source pin is written to `[tool.uv.sources]` as an index reference
+/// 3. The dependency's marker is written with the source so it applies conditionally
+#[test]
+fn test_dependency_with_version_extras_source_and_markers() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+torch = { version = "^2.0", extras = ["cuda"], source = "pytorch", markers = "sys_platform == 'linux'" }
+
+[[tool.poetry.source]]
+name = "pytorch"
+url = "https://download.pytorch.org/whl/cu121"
+priority = "explicit"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let source = PoetryMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+    let torch = dependencies.iter().find(|d| d.name == "torch").unwrap();
+
+    assert_eq!(torch.extras, Some(vec!["cuda".to_string()]));
+    assert_eq!(
+        migrators::format_dependency(torch),
+        "torch[cuda]>=2.0,<3.0.0; sys_platform == 'linux'"
+    );
+
+    fs::rename(
+        project_dir.join("pyproject.toml"),
+        project_dir.join("old.pyproject.toml"),
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pyproject.toml"),
+        "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let pins = extract_poetry_source_pins(&project_dir).unwrap();
+    assert_eq!(
+        pins,
+        vec![SourcePin {
+            name: "torch".to_string(),
+            index: "pytorch".to_string(),
+            marker: Some("sys_platform == 'linux'".to_string()),
+        }]
+    );
+    update_source_pins(&project_dir, &pins).unwrap();
+
+    let result = fs::read_to_string(project_dir.join("pyproject.toml")).unwrap();
+    let doc: toml::Value = toml::from_str(&result).unwrap();
+    assert_eq!(
+        doc["tool"]["uv"]["sources"]["torch"]["index"].as_str(),
+        Some("pytorch")
+    );
+    assert_eq!(
+        doc["tool"]["uv"]["sources"]["torch"]["marker"].as_str(),
+        Some("sys_platform == 'linux'")
+    );
+}
+
+/// Test that git dependencies keep their reference and subdirectory.
+///
+/// This test verifies that:
+/// 1. The `uv add` argument is a direct git reference including the subdirectory
+/// 2. The `[tool.uv.sources]` entry carries the git URL, tag and subdirectory
+#[test]
+fn test_git_dependency_with_subdirectory() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+foo = { git = "https://github.com/acme/monorepo.git", tag = "v1.2.0", subdirectory = "packages/foo" }
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(
+        migrators::format_dependency(&dependencies[0]),
+        "foo @ git+https://github.com/acme/monorepo.git@v1.2.0#subdirectory=packages/foo"
+    );
+
+    fs::rename(
+        project_dir.join("pyproject.toml"),
+        project_dir.join("old.pyproject.toml"),
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pyproject.toml"),
+        "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+
+    let git_deps = extract_poetry_git_dependencies(&project_dir).unwrap();
+    update_git_dependencies(&project_dir, &git_deps).unwrap();
+
+    let result = fs::read_to_string(project_dir.join("pyproject.toml")).unwrap();
+    let doc: toml::Value = toml::from_str(&result).unwrap();
+    let source = &doc["tool"]["uv"]["sources"]["foo"];
+    assert_eq!(
+        source["git"].as_str(),
+        Some("https://github.com/acme/monorepo.git")
+    );
+    assert_eq!(source["tag"].as_str(), Some("v1.2.0"));
+    assert_eq!(source["subdirectory"].as_str(), Some("packages/foo"));
+    assert!(source.get("branch").is_none());
+}
+
+/// Test that path dependencies pointing outside the project stay relative.
+///
+/// This test verifies that:
+/// 1. Path dependencies are not passed to `uv add`
+/// 2. The `[tool.uv.sources]` entries keep the original relative paths and `develop` flag
+/// 3. Only the dependency whose directory is missing is reported
+/// 4. Group path dependencies land in their dependency group
+#[test]
+fn test_path_dependencies_outside_project() {
+    let workspace = TempDir::new().unwrap();
+    let project_dir = workspace.path().join("app");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::create_dir_all(workspace.path().join("shared")).unwrap();
+
+    let content = r#"
+[tool.poetry]
+name = "app"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+shared = { path = "../shared", develop = true }
+
+[tool.poetry.group.dev.dependencies]
+tooling = { path = "../tooling" }
+"#;
+    fs::write(project_dir.join("pyproject.toml"), content).unwrap();
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let names: Vec<_> = dependencies.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["requests"]);
+
+    fs::rename(
+        project_dir.join("pyproject.toml"),
+        project_dir.join("old.pyproject.toml"),
+    )
+    .unwrap();
+    fs::write(
+        project_dir.join("pyproject.toml"),
+        "[project]\nname = \"app\"\nversion = \"0.1.0\"\ndependencies = [\"requests>=2.31.0\"]\n",
+    )
+    .unwrap();
+
+    let path_deps = extract_poetry_path_dependencies(&project_dir).unwrap();
+    let missing = update_path_dependencies(&project_dir, &path_deps).unwrap();
+    assert_eq!(missing, vec!["tooling".to_string()]);
+
+    let result = fs::read_to_string(project_dir.join("pyproject.toml")).unwrap();
+    let doc: toml::Value = toml::from_str(&result).unwrap();
+    let sources = &doc["tool"]["uv"]["sources"];
+    assert_eq!(sources["shared"]["path"].as_str(), Some("../shared"));
+    assert_eq!(sources["shared"]["editable"].as_bool(), Some(true));
+    assert_eq!(sources["tooling"]["path"].as_str(), Some("../tooling"));
+    assert!(sources["tooling"].get("editable").is_none());
+
+    let main: Vec<_> = doc["project"]["dependencies"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d.as_str().unwrap())
+        .collect();
+    assert_eq!(main, vec!["requests>=2.31.0", "shared"]);
+    assert_eq!(
+        doc["dependency-groups"]["dev"].as_array().unwrap()[0].as_str(),
+        Some("tooling")
+    );
+}
+
+/// Test that optional dependencies are migrated through their Poetry extras.
+///
+/// This test verifies that:
+/// 1. A dependency listed under two extras is added to both extras
+/// 2. Optional dependencies are not added as main dependencies
+/// 3. Each extra is passed to `uv add` with `--optional`
+#[test]
+fn test_optional_dependencies_in_extras() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+psycopg2 = { version = "^2.9", optional = true }
+mysqlclient = { version = "^2.2", optional = true }
+
+[tool.poetry.extras]
+postgres = ["psycopg2"]
+databases = ["psycopg2", "mysqlclient"]
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let source = PoetryMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    let main: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.dep_type == DependencyType::Main)
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(main, vec!["requests"]);
+
+    let psycopg2_extras: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.name == "psycopg2")
+        .map(|d| d.dep_type.clone())
+        .collect();
+    assert_eq!(
+        psycopg2_extras,
+        vec![
+            DependencyType::Optional("postgres".to_string()),
+            DependencyType::Optional("databases".to_string())
+        ]
+    );
+
+    let databases_type = DependencyType::Optional("databases".to_string());
+    let databases: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.dep_type == databases_type)
+        .collect();
+    assert_eq!(
+        migrators::build_add_args(&databases_type, &databases),
+        vec![
+            "add",
+            "--optional",
+            "databases",
+            "psycopg2>=2.9,<3.0.0",
+            "mysqlclient>=2.2,<3.0.0"
+        ]
+    );
+}
+
+/// Test that `--no-pin-python` overrides the detected Python version.
+///
+/// This test verifies that:
+/// 1. A detected version is passed to `uv init` as `--python` by default
+/// 2. With the flag set no `--python` argument is passed even though a version was detected
+#[test]
+fn test_no_pin_python_overrides_detected_version() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+    let version = PoetryMigrationSource::extract_python_version(&project_dir).unwrap();
+    assert_eq!(version.as_deref(), Some("3.11"));
+
+    let default_args = migrators::build_init_args(false, version.as_deref(), &Args::default());
+    assert_eq!(
+        default_args,
+        vec!["init", "--python", "3.11", "--vcs", "none"]
+    );
+
+    let args = Args {
+        no_pin_python: true,
+        ..Args::default()
+    };
+    let init_args = migrators::build_init_args(false, version.as_deref(), &args);
+    assert_eq!(init_args, vec!["init", "--no-pin-python", "--vcs", "none"]);
+    assert!(!init_args.iter().any(|arg| arg == "--python"));
+}
+
+/// Test a Poetry multiple-constraints dependency end to end.
+///
+/// This test verifies that:
+/// 1. Each python-gated constraint becomes its own dependency
+/// 2. Each python constraint is translated into a `python_version` marker
+/// 3. Both constraints reach `uv add` as distinct arguments for the same package
+#[test]
+fn test_multiple_constraints_reach_uv_add() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+numpy = [
+    { version = "^1.24", python = ">=3.9" },
+    { version = "^1.21", python = "<3.9" },
+]
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let source = PoetryMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+    let main_deps: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.dep_type == DependencyType::Main)
+        .collect();
+    assert_eq!(main_deps.len(), 2);
+
+    let args = migrators::build_add_args(&DependencyType::Main, &main_deps);
+    assert_eq!(
+        args,
+        vec![
+            "add",
+            "numpy>=1.24,<2.0.0; python_version >= '3.9'",
+            "numpy>=1.21,<2.0.0; python_version < '3.9'"
+        ]
+    );
+}
+
+/// Test the array of tables spelling of a Poetry multiple-constraints dependency.
+///
+/// This test verifies that:
+/// 1. Each `[[tool.poetry.dependencies.<name>]]` table becomes its own dependency
+/// 2. Each python constraint is translated into a `python_version` marker
+#[test]
+fn test_multiple_constraints_array_of_tables() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+
+[[tool.poetry.dependencies.numpy]]
+version = "^1.24"
+python = ">=3.9"
+
+[[tool.poetry.dependencies.numpy]]
+version = "^1.21"
+python = "<3.9"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let numpy: Vec<_> = dependencies.iter().filter(|d| d.name == "numpy").collect();
+    assert_eq!(numpy.len(), 2);
+    assert_eq!(numpy[0].version.as_deref(), Some("^1.24"));
+    assert_eq!(
+        numpy[0].environment_markers.as_deref(),
+        Some("python_version >= '3.9'")
+    );
+    assert_eq!(numpy[1].version.as_deref(), Some("^1.21"));
+    assert_eq!(
+        numpy[1].environment_markers.as_deref(),
+        Some("python_version < '3.9'")
+    );
+}
+
+/// Test that OR'd python constraints on a dependency become an `or` marker.
+///
+/// This test verifies that:
+/// 1. Each `||` alternative is translated into its own `python_version` marker
+/// 2. Alternatives with several clauses are parenthesized
+/// 3. An explicit `markers` value is combined with the whole python marker
+#[test]
+fn test_or_python_constraint_marker() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+tomli = { version = "^2.0", python = "^3.9 || >=3.6,<3.8" }
+pywin32 = { version = "^306", python = "~3.9 || ^3.11", markers = "sys_platform == 'win32'" }
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let tomli = dependencies.iter().find(|d| d.name == "tomli").unwrap();
+    assert_eq!(
+        tomli.environment_markers.as_deref(),
+        Some("python_version >= '3.9' or (python_version >= '3.6' and python_version < '3.8')")
+    );
+
+    let pywin32 = dependencies.iter().find(|d| d.name == "pywin32").unwrap();
+    assert_eq!(
+        pywin32.environment_markers.as_deref(),
+        Some("(python_version >= '3.9' or python_version >= '3.11') and sys_platform == 'win32'")
+    );
+}
+
+/// Test that python constraints with a patch version compare the full Python version.
+///
+/// This test verifies that:
+/// 1. `>=3.8.1` becomes a `python_full_version` marker
+/// 2. Clauses without a patch version keep using `python_version`
+#[test]
+fn test_patch_python_constraint_marker() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+tomli = { version = "^2.0", python = ">=3.8.1" }
+exceptiongroup = { version = "^1.2", python = ">=3.8.1,<3.11" }
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let tomli = dependencies.iter().find(|d| d.name == "tomli").unwrap();
+    assert_eq!(
+        tomli.environment_markers.as_deref(),
+        Some("python_full_version >= '3.8.1'")
+    );
+
+    let exceptiongroup = dependencies
+        .iter()
+        .find(|d| d.name == "exceptiongroup")
+        .unwrap();
+    assert_eq!(
+        exceptiongroup.environment_markers.as_deref(),
+        Some("python_full_version >= '3.8.1' and python_version < '3.11'")
+    );
+}
+
+/// Test a dependency with a version, a python constraint and explicit markers.
+///
+/// This test verifies that:
+/// 1. The version goes to the requirement spec
+/// 2. The python marker and the explicit markers are joined with `and`
+/// 3. Explicit markers containing `or` are parenthesized
+#[test]
+fn test_version_python_and_markers_combined() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.8"
+uvloop = { version = "^0.19", python = ">=3.8", markers = "sys_platform == 'linux' or sys_platform == 'darwin'" }
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let uvloop = dependencies.iter().find(|d| d.name == "uvloop").unwrap();
+
+    assert_eq!(
+        migrators::format_dependency(uvloop),
+        "uvloop>=0.19,<0.20.0; python_version >= '3.8' and \
+        (sys_platform == 'linux' or sys_platform == 'darwin')"
+    );
+}
+
+/// Test that Poetry caret requirements are expanded to the range Poetry allows.
+///
+/// This test verifies that:
+/// 1. `^X.Y.Z` with X > 0 is bounded below the next major version
+/// 2. `^0.Y.Z` is bounded below the next minor version
+/// 3. `^0.0.Z` is bounded below the next patch version
+/// 4. A caret inside a comma-separated list is expanded too
+#[test]
+fn test_caret_expansion() {
+    let format = |version: &str| {
+        let dep = Dependency::new("pkg")
+            .version(version.to_string())
+            .build()
+            .unwrap();
+        migrators::format_dependency(&dep)
+    };
+
+    assert_eq!(format("^1.2.3"), "pkg>=1.2.3,<2.0.0");
+    assert_eq!(format("^0.5.2"), "pkg>=0.5.2,<0.6.0");
+    assert_eq!(format("^0.0.3"), "pkg>=0.0.3,<0.0.4");
+    assert_eq!(format("^1.2,!=1.2.5"), "pkg>=1.2,<2.0.0,!=1.2.5");
+}
+
+/// Test that a rendered dependency keeps the constraint it was rendered from.
+///
+/// This test verifies that:
+/// 1. The original caret constraint is retained alongside the expanded one
+/// 2. The requirement string matches `format_dependency` and carries the markers
+/// 3. A dependency without a version has neither constraint
+#[test]
+fn test_rendered_dependency_constraint_mapping() {
+    let dep = Dependency::new("fastapi")
+        .version("^0.111.0".to_string())
+        .markers("python_version >= '3.9'".to_string())
+        .build()
+        .unwrap();
+
+    let rendered = migrators::render_dependency(&dep);
+    assert_eq!(rendered.original.as_deref(), Some("^0.111.0"));
+    assert_eq!(rendered.constraint.as_deref(), Some(">=0.111.0,<0.112.0"));
+    assert_eq!(
+        rendered.requirement,
+        "fastapi>=0.111.0,<0.112.0; python_version >= '3.9'"
+    );
+    assert_eq!(rendered.requirement, migrators::format_dependency(&dep));
+
+    let unversioned = Dependency::new("httpx").build().unwrap();
+    let rendered = migrators::render_dependency(&unversioned);
+    assert_eq!(rendered.original, None);
+    assert_eq!(rendered.constraint, None);
+    assert_eq!(rendered.requirement, "httpx");
+}
+
+/// Test that Poetry tilde requirements are expanded to the range Poetry allows.
+///
+/// This test verifies that:
+/// 1. `~1` is bounded below the next major version
+/// 2. `~1.2` and `~1.2.3` are bounded below the next minor version
+/// 3. pip's `~=` operator is passed through unchanged
+/// 4. A tilde inside a comma-separated list is expanded too
+#[test]
+fn test_tilde_expansion() {
+    let format = |version: &str| {
+        let dep = Dependency::new("pkg")
+            .version(version.to_string())
+            .build()
+            .unwrap();
+        migrators::format_dependency(&dep)
+    };
+
+    assert_eq!(format("~1"), "pkg>=1,<2.0.0");
+    assert_eq!(format("~1.2"), "pkg>=1.2,<1.3.0");
+    assert_eq!(format("~1.2.3"), "pkg>=1.2.3,<1.3.0");
+    assert_eq!(format("~=1.2.3"), "pkg~=1.2.3");
+    assert_eq!(format("~1.2,!=1.2.3"), "pkg>=1.2,<1.3.0,!=1.2.3");
+}
+
 /// Test that development dependencies are correctly extracted from a Poetry project.
 ///
 /// This test verifies that:
@@ -302,6 +1027,95 @@ pytest-cov = "^4.1.0"
     assert!(matches!(pytest_cov_dep.dep_type, DependencyType::Group(ref name) if name == "test"));
 }
 
+/// Test that a package listed in both `[project]` and `[tool.poetry.dependencies]` with
+/// different versions is flagged.
+///
+/// This test verifies that:
+/// 1. The `[project]` version is the one that gets migrated
+/// 2. The package is only migrated once
+/// 3. The conflicting `[tool.poetry]` entry is reported as skipped with both versions
+#[test]
+fn test_conflicting_project_and_poetry_versions() {
+    let content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+dependencies = [
+    "requests (>=2.31.0)",
+    "rich (>=13.0.0)",
+]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.28.0"
+rich = ">= 13.0.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let source = PoetryMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    let requests: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.name == "requests")
+        .collect();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].version, Some(">=2.31.0".to_string()));
+
+    let skipped = source.skipped_items(&project_dir).unwrap();
+    assert_eq!(skipped.len(), 1, "Only requests should conflict");
+    assert_eq!(skipped[0].name, "requests");
+    assert_eq!(
+        skipped[0].reason,
+        "[tool.poetry.dependencies] version '^2.28.0' conflicts with [project] version '>=2.31.0'"
+    );
+}
+
+/// Test that `--prefer-poetry-table` lets the `[tool.poetry]` version win a conflict.
+///
+/// This test verifies that:
+/// 1. The `[tool.poetry.dependencies]` version replaces the `[project]` one
+/// 2. Packages only declared in `[project]` are still migrated
+/// 3. The flag reaches the planned `uv add` arguments
+#[test]
+fn test_prefer_poetry_table() {
+    let content = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+dependencies = [
+    "requests (>=2.31.0)",
+    "rich (>=13.0.0)",
+]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "2.28.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+
+    let dependencies = PoetryMigrationSource
+        .extract_dependencies_with_precedence(&project_dir, true)
+        .unwrap();
+    let requests: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.name == "requests")
+        .collect();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].version, Some("2.28.0".to_string()));
+    assert!(dependencies.iter().any(|d| d.name == "rich"));
+
+    let args = Args {
+        prefer_poetry_table: true,
+        ..Args::default()
+    };
+    let plan = migrators::plan_migration(&project_dir, &args).unwrap();
+    assert!(plan
+        .add_args
+        .iter()
+        .any(|add| add.contains(&"requests==2.28.0".to_string())));
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -460,6 +1274,177 @@ description = "Add your description here"
 
         Ok(())
     }
+
+    /// Writes old.pyproject.toml and a freshly initialized pyproject.toml, then runs the
+    /// metadata migration and returns the resulting `[project].readme`.
+    fn migrated_readme(old_content: &str) -> Option<toml::Value> {
+        let test_dir = setup_test_dir();
+        fs::write(test_dir.path().join("old.pyproject.toml"), old_content).unwrap();
+        fs::write(
+            test_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        update_pyproject_toml(test_dir.path(), &[]).unwrap();
+
+        let result = fs::read_to_string(test_dir.path().join("pyproject.toml")).unwrap();
+        let doc: toml::Value = toml::from_str(&result).unwrap();
+        doc["project"].get("readme").cloned()
+    }
+
+    /// Test migration of a single Poetry readme.
+    ///
+    /// This test verifies that:
+    /// 1. `tool.poetry.readme` is copied to `[project].readme`
+    /// 2. A Poetry 2.0 `project.readme` table is copied as is
+    #[test]
+    fn test_readme_string_migration() {
+        let readme = migrated_readme(
+            r#"[tool.poetry]
+name = "test-project"
+version = "1.3.0"
+readme = "README.rst"
+"#,
+        );
+        assert_eq!(readme.as_ref().and_then(|r| r.as_str()), Some("README.rst"));
+
+        let readme = migrated_readme(
+            r#"[project]
+name = "test-project"
+version = "1.3.0"
+readme = { file = "README.txt", content-type = "text/plain" }
+
+[tool.poetry]
+readme = "IGNORED.md"
+"#,
+        )
+        .unwrap();
+        assert_eq!(readme["file"].as_str(), Some("README.txt"));
+        assert_eq!(readme["content-type"].as_str(), Some("text/plain"));
+    }
+
+    /// Test migration of a Poetry SPDX license.
+    ///
+    /// This test verifies that:
+    /// 1. `tool.poetry.license` is copied to `[project].license`
+    /// 2. An SPDX identifier is written in the string form
+    #[test]
+    fn test_license_spdx_migration() {
+        let test_dir = setup_test_dir();
+        fs::write(
+            test_dir.path().join("old.pyproject.toml"),
+            "[tool.poetry]\nname = \"test-project\"\nlicense = \"Apache-2.0\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        update_pyproject_toml(test_dir.path(), &[]).unwrap();
+
+        let result = fs::read_to_string(test_dir.path().join("pyproject.toml")).unwrap();
+        assert!(result.contains(r#"license = "Apache-2.0""#), "{}", result);
+    }
+
+    /// Test migration of a Poetry license that names a file.
+    ///
+    /// This test verifies that:
+    /// 1. A license file path is written as `{ file = "..." }`
+    #[test]
+    fn test_license_file_migration() {
+        let test_dir = setup_test_dir();
+        fs::write(
+            test_dir.path().join("old.pyproject.toml"),
+            "[tool.poetry]\nname = \"test-project\"\nlicense = \"LICENSE\"\n",
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        update_pyproject_toml(test_dir.path(), &[]).unwrap();
+
+        let result = fs::read_to_string(test_dir.path().join("pyproject.toml")).unwrap();
+        assert!(
+            result.contains(r#"license = { file = "LICENSE" }"#),
+            "{}",
+            result
+        );
+    }
+
+    /// Test migration of Poetry keywords and classifiers.
+    ///
+    /// This test verifies that:
+    /// 1. `tool.poetry.keywords` is copied to `[project].keywords`
+    /// 2. `tool.poetry.classifiers` is copied to `[project].classifiers`
+    /// 3. Both keep their values and order
+    #[test]
+    fn test_keywords_and_classifiers_migration() {
+        let test_dir = setup_test_dir();
+        fs::write(
+            test_dir.path().join("old.pyproject.toml"),
+            r#"[tool.poetry]
+name = "test-project"
+version = "1.3.0"
+keywords = ["parsing", "rosters"]
+classifiers = [
+    "Programming Language :: Python :: 3",
+    "License :: OSI Approved :: MIT License",
+    "Operating System :: OS Independent",
+]
+"#,
+        )
+        .unwrap();
+        fs::write(
+            test_dir.path().join("pyproject.toml"),
+            "[project]\nname = \"test-project\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        update_pyproject_toml(test_dir.path(), &[]).unwrap();
+
+        let result = fs::read_to_string(test_dir.path().join("pyproject.toml")).unwrap();
+        let doc: toml::Value = toml::from_str(&result).unwrap();
+        let strings = |key: &str| -> Vec<String> {
+            doc["project"][key]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|v| v.as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(strings("keywords"), vec!["parsing", "rosters"]);
+        assert_eq!(
+            strings("classifiers"),
+            vec![
+                "Programming Language :: Python :: 3",
+                "License :: OSI Approved :: MIT License",
+                "Operating System :: OS Independent",
+            ]
+        );
+    }
+
+    /// Test migration of a Poetry readme list.
+    ///
+    /// This test verifies that:
+    /// 1. The first readme of the list becomes `[project].readme`
+    /// 2. The result is a single string rather than a list
+    #[test]
+    fn test_readme_list_migration() {
+        let readme = migrated_readme(
+            r#"[tool.poetry]
+name = "test-project"
+version = "1.3.0"
+readme = ["README.md", "CHANGELOG.md"]
+"#,
+        );
+        assert_eq!(readme.as_ref().and_then(|r| r.as_str()), Some("README.md"));
+    }
 }
 
 #[cfg(test)]
@@ -543,7 +1528,7 @@ pytest-mock = "^3.10.0"
         );
 
         // Apply group merging
-        let merged_deps = migrators::merge_dependency_groups(dependencies);
+        let merged_deps = migrators::merge_dependency_groups(dependencies, false);
 
         // Verify merged state
         assert_eq!(
@@ -624,7 +1609,7 @@ pytest-django = ">=4.5.0"
         assert_eq!(django_dep.version, Some(">=4.0.0,<5.0.0".to_string()));
 
         // Apply merge and verify complex dependencies are preserved
-        let merged_deps = migrators::merge_dependency_groups(dependencies);
+        let merged_deps = migrators::merge_dependency_groups(dependencies, false);
 
         // Verify versions are maintained after merge
         let pytest_dep = merged_deps
@@ -641,6 +1626,60 @@ pytest-django = ">=4.5.0"
         assert_eq!(pytest_django_dep.version, Some(">=4.5.0".to_string()));
         assert!(matches!(pytest_django_dep.dep_type, DependencyType::Dev));
     }
+
+    /// Test that optional Poetry groups survive --merge-groups.
+    ///
+    /// This test verifies that:
+    /// 1. Dependencies of `optional = true` groups are marked as such
+    /// 2. Merging leaves optional groups as their own group
+    /// 3. Non-optional groups are still merged into dev
+    /// 4. Optional groups are merged too when merge_optional is set
+    #[test]
+    fn test_merge_groups_keeps_optional_groups() {
+        let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+
+[tool.poetry.group.test.dependencies]
+pytest = "^8.0.0"
+
+[tool.poetry.group.docs]
+optional = true
+
+[tool.poetry.group.docs.dependencies]
+mkdocs = "^1.5.0"
+"#;
+
+        let (_temp_dir, project_dir) = create_test_project(content);
+        let dependencies = PoetryMigrationSource
+            .extract_dependencies(&project_dir)
+            .unwrap();
+
+        let mkdocs = dependencies.iter().find(|d| d.name == "mkdocs").unwrap();
+        assert!(mkdocs.optional_group);
+        let pytest = dependencies.iter().find(|d| d.name == "pytest").unwrap();
+        assert!(!pytest.optional_group);
+
+        let merged = migrators::merge_dependency_groups(dependencies.clone(), false);
+        let dep_type = |deps: &[Dependency], name: &str| {
+            deps.iter()
+                .find(|d| d.name == name)
+                .map(|d| d.dep_type.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            dep_type(&merged, "mkdocs"),
+            DependencyType::Group("docs".to_string())
+        );
+        assert_eq!(dep_type(&merged, "pytest"), DependencyType::Dev);
+
+        let merged = migrators::merge_dependency_groups(dependencies, true);
+        assert_eq!(dep_type(&merged, "mkdocs"), DependencyType::Dev);
+    }
 }
 
 #[test]
@@ -868,3 +1907,86 @@ description = "Add your description here"
 
     Ok(())
 }
+
+/// Test that a dry run leaves the project untouched.
+///
+/// This test verifies that:
+/// 1. `run_migration` succeeds under `--dry-run` without uv being invoked
+/// 2. pyproject.toml is not renamed or rewritten
+/// 3. No new files are created in the project directory
+#[test]
+fn test_dry_run_leaves_project_untouched() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+    let args = Args {
+        dry_run: true,
+        generate_gitignore: true,
+        report_skipped: Some(project_dir.join("skipped.txt")),
+        ..Args::default()
+    };
+
+    migrators::run_migration(&project_dir, &args).unwrap();
+
+    let files: Vec<_> = fs::read_dir(&project_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    assert_eq!(files, vec!["pyproject.toml"]);
+    assert_eq!(
+        fs::read_to_string(project_dir.join("pyproject.toml")).unwrap(),
+        content
+    );
+}
+
+/// Test the machine-readable migration summary.
+///
+/// This test verifies that:
+/// 1. The summary records the detected project type and dependency counts
+/// 2. The summary serializes to JSON with the expected keys
+#[test]
+fn test_migration_summary_json() {
+    let content = r#"
+[tool.poetry]
+name = "test-project"
+version = "0.1.0"
+
+[tool.poetry.dependencies]
+python = "^3.11"
+requests = "^2.31.0"
+fastapi = "^0.110.0"
+
+[tool.poetry.group.dev.dependencies]
+pytest = "^8.0.0"
+
+[tool.poetry.group.docs.dependencies]
+mkdocs = "^1.5.0"
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+    let args = Args {
+        dry_run: true,
+        ..Args::default()
+    };
+
+    let summary = migrators::run_migration(&project_dir, &args).unwrap();
+    assert_eq!(summary.project_type, "Poetry(Application)");
+    assert_eq!(summary.dependencies.main, 2);
+    assert_eq!(summary.dependencies.dev, 1);
+    assert_eq!(summary.dependencies.group, 1);
+
+    let json = serde_json::to_value(&summary).unwrap();
+    assert_eq!(json["project_type"], "Poetry(Application)");
+    assert_eq!(json["dependencies"]["main"], 2);
+    assert_eq!(json["dry_run"], true);
+    assert!(json["tool_sections_copied"].as_array().unwrap().is_empty());
+}
@@ -1,6 +1,7 @@
 use std::fs;
 use std::path::PathBuf;
 use tempfile::TempDir;
+use uv_migrator::migrators::detect::{detect_project_type, ProjectType};
 use uv_migrator::migrators::requirements::RequirementsMigrationSource;
 use uv_migrator::migrators::{DependencyType, MigrationSource};
 
@@ -21,6 +22,7 @@ fn create_test_project(files: Vec<(&str, &str)>) -> (TempDir, PathBuf) {
 
     for (filename, content) in files {
         let file_path = project_dir.join(filename);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
         fs::write(&file_path, content).unwrap();
     }
 
@@ -295,6 +297,60 @@ git+https://github.com/user/other-project.git@v1.0.0#egg=other-project
     assert_eq!(dependencies.len(), 3);
 }
 
+/// Test handling of requirements that reference the project itself.
+///
+/// This test verifies that:
+/// 1. `.[test]` is not added as a dependency
+/// 2. The named extra is resolved from the project's own pyproject.toml
+/// 3. A bare `-e .` line is ignored
+#[test]
+fn test_self_reference_with_extras() {
+    let requirements = r#"
+-e .
+.[test]
+requests==2.31.0
+    "#;
+    let pyproject = r#"
+[project]
+name = "test-project"
+version = "0.1.0"
+
+[project.optional-dependencies]
+test = ["pytest>=8.0"]
+docs = ["sphinx>=7.0"]
+"#;
+
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("requirements.txt", requirements),
+        ("pyproject.toml", pyproject),
+    ]);
+
+    let source = RequirementsMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    assert!(!dependencies.iter().any(|d| d.name.starts_with('.')));
+    assert_eq!(dependencies.len(), 2);
+
+    let pytest = dependencies.iter().find(|d| d.name == "pytest").unwrap();
+    assert_eq!(pytest.version, Some(">=8.0".to_string()));
+    assert_eq!(pytest.dep_type, DependencyType::Main);
+    assert!(dependencies.iter().any(|d| d.name == "requests"));
+}
+
+/// Test that a self-reference without a pyproject.toml is skipped gracefully.
+#[test]
+fn test_self_reference_without_pyproject() {
+    let requirements = ".[dev]\nflask>=2.0\n";
+
+    let (_temp_dir, project_dir) = create_test_project(vec![("requirements.txt", requirements)]);
+
+    let source = RequirementsMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(dependencies[0].name, "flask");
+}
+
 /// Test handling of malformed requirements files.
 ///
 /// This test verifies that:
@@ -472,7 +528,7 @@ mod merge_groups_tests {
         );
 
         // Apply group merging
-        let merged_deps = migrators::merge_dependency_groups(dependencies);
+        let merged_deps = migrators::merge_dependency_groups(dependencies, false);
 
         // Verify merged state
         assert_eq!(
@@ -501,3 +557,237 @@ mod merge_groups_tests {
         );
     }
 }
+
+/// Test that unparseable requirement lines are reported as skipped.
+///
+/// This test verifies that:
+/// 1. Malformed lines are listed with their file and line number
+/// 2. Comments, blank lines and valid requirements are not reported
+#[test]
+fn test_skipped_items_lists_unparseable_lines() {
+    let content = "requests==2.31.0\n# a comment\n\nbroken===1.0\n";
+    let (_temp_dir, project_dir) = create_test_project(vec![("requirements.txt", content)]);
+
+    let source = RequirementsMigrationSource;
+    let skipped = source.skipped_items(&project_dir).unwrap();
+
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].name, "requirements.txt:4");
+    assert!(skipped[0].reason.contains("broken===1.0"));
+}
+
+/// Test that `-r` includes are followed through a two-level chain.
+///
+/// This test verifies that:
+/// 1. `-r` and `--requirement` lines are resolved relative to the including file
+/// 2. Dependencies from nested includes are merged in
+/// 3. Included dependencies inherit the type of the including file
+/// 4. Include lines are not treated as packages
+#[test]
+fn test_recursive_requirement_includes() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("requirements.txt", "-r base.txt\nflask>=2.0.0\n"),
+        (
+            "base.txt",
+            "requests==2.31.0\n--requirement constraints/prod.txt\n",
+        ),
+        ("requirements-dev.txt", "-r base.txt\npytest>=8.0\n"),
+    ]);
+    fs::create_dir(project_dir.join("constraints")).unwrap();
+    fs::write(
+        project_dir.join("constraints/prod.txt"),
+        "gunicorn==21.2.0\n",
+    )
+    .unwrap();
+
+    let source = RequirementsMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    assert!(!dependencies.iter().any(|d| d.name.starts_with('-')));
+
+    for name in ["flask", "requests", "gunicorn"] {
+        let dep = dependencies.iter().find(|d| d.name == name).unwrap();
+        assert_eq!(
+            dep.dep_type,
+            DependencyType::Main,
+            "{} should be main",
+            name
+        );
+    }
+    assert_eq!(
+        dependencies.iter().filter(|d| d.name == "requests").count(),
+        1,
+        "base.txt should only be processed once"
+    );
+
+    let pytest = dependencies.iter().find(|d| d.name == "pytest").unwrap();
+    assert_eq!(pytest.dep_type, DependencyType::Dev);
+    assert_eq!(dependencies.len(), 4);
+}
+
+/// Test that an include cycle does not loop forever.
+///
+/// This test verifies that:
+/// 1. A file that includes itself through another file is only read once
+/// 2. Dependencies from every file in the cycle are still extracted
+#[test]
+fn test_requirement_include_cycle() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("requirements.txt", "-r a.txt\n"),
+        ("a.txt", "requests==2.31.0\n-r b.txt\n"),
+        ("b.txt", "flask>=2.0.0\n-r a.txt\n-r requirements.txt\n"),
+    ]);
+
+    let source = RequirementsMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    let mut names: Vec<_> = dependencies.iter().map(|d| d.name.as_str()).collect();
+    names.sort();
+    assert_eq!(names, vec!["flask", "requests"]);
+}
+
+/// Test that `-c` constraint files pin unversioned dependencies.
+///
+/// This test verifies that:
+/// 1. A dependency without a version takes the version from the constraints file
+/// 2. Dependencies that already declare a version keep it
+/// 3. Constraints for packages that are not declared are ignored
+/// 4. The constraint line itself is not treated as a package
+#[test]
+fn test_constraints_file_pins_versions() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        (
+            "requirements.txt",
+            "-c constraints.txt\nflask\nrequests>=2.0.0\n",
+        ),
+        (
+            "constraints.txt",
+            "flask==2.0.1\nrequests==2.31.0\nwerkzeug==2.0.3\n",
+        ),
+    ]);
+
+    let source = RequirementsMigrationSource;
+    let dependencies = source.extract_dependencies(&project_dir).unwrap();
+
+    assert_eq!(dependencies.len(), 2);
+
+    let flask = dependencies.iter().find(|d| d.name == "flask").unwrap();
+    assert_eq!(migrators::format_dependency(flask), "flask==2.0.1");
+
+    let requests = dependencies.iter().find(|d| d.name == "requests").unwrap();
+    assert_eq!(requests.version, Some(">=2.0.0".to_string()));
+
+    assert!(!dependencies.iter().any(|d| d.name == "werkzeug"));
+}
+
+/// Test that `--validate-markers` flags malformed environment markers.
+///
+/// This test verifies that:
+/// 1. A marker with an unquoted version is reported with the dependency name
+/// 2. Valid markers are not reported
+/// 3. With `--strict` the migration plan fails instead of only warning
+#[test]
+fn test_validate_markers_flags_malformed_marker() {
+    let content =
+        "requests==2.31.0; python_version >= '3.8'\nflask>=2.0.0; python_version >= 3.8\n";
+    let (_temp_dir, project_dir) = create_test_project(vec![("requirements.txt", content)]);
+
+    let dependencies = RequirementsMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let problems = migrators::invalid_markers(&dependencies);
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("flask ('python_version >= 3.8')"));
+
+    let mut args = uv_migrator::cli::Args {
+        validate_markers: true,
+        ..Default::default()
+    };
+    assert!(migrators::plan_migration(&project_dir, &args).is_ok());
+
+    args.strict = true;
+    let err = migrators::plan_migration(&project_dir, &args)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("flask"));
+}
+
+/// Test that `--dev-group-name` keeps requirements-dev.txt in a named group.
+///
+/// This test verifies that:
+/// 1. requirements-dev.txt is added with `--dev` by default
+/// 2. With a dev group name it is added to that group instead
+/// 3. Main dependencies are unaffected
+#[test]
+fn test_dev_group_name_for_requirements_dev() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("requirements.txt", "requests==2.31.0\n"),
+        ("requirements-dev.txt", "pytest==8.0.0\n"),
+    ]);
+
+    let plan = migrators::plan_migration(&project_dir, &Default::default()).unwrap();
+    assert_eq!(
+        plan.add_args,
+        vec![
+            vec!["add", "requests==2.31.0"],
+            vec!["add", "--dev", "pytest==8.0.0"],
+        ]
+    );
+
+    let matches = uv_migrator::cli::build_command()
+        .try_get_matches_from(["uv-migrator", "--dev-group-name", "local"])
+        .unwrap();
+    let args = uv_migrator::cli::args_from_matches(&matches);
+    let plan = migrators::plan_migration(&project_dir, &args).unwrap();
+    assert_eq!(
+        plan.add_args,
+        vec![
+            vec!["add", "requests==2.31.0"],
+            vec!["add", "--group", "local", "pytest==8.0.0"],
+        ]
+    );
+}
+
+/// Test migrating requirements kept in a `requirements/` directory.
+///
+/// This test verifies that:
+/// 1. The project is detected as a requirements project
+/// 2. base.txt maps to main, dev.txt to dev and test.txt to the "test" group
+/// 3. `-r base.txt` includes inside the directory do not add base requirements twice
+/// 4. A package listed for the same group in both layouts is only extracted once
+#[test]
+fn test_requirements_directory_layout() {
+    let (_temp_dir, project_dir) = create_test_project(vec![
+        ("requirements/base.txt", "requests==2.31.0\nclick>=8.0\n"),
+        ("requirements/dev.txt", "-r base.txt\nruff>=0.4\n"),
+        ("requirements/test.txt", "-r base.txt\npytest>=8.0\n"),
+        ("requirements-dev.txt", "ruff>=0.4\n"),
+    ]);
+
+    assert_eq!(
+        detect_project_type(&project_dir).unwrap(),
+        ProjectType::Requirements
+    );
+
+    let dependencies = RequirementsMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let mut found: Vec<(String, DependencyType)> = dependencies
+        .iter()
+        .map(|d| (d.name.clone(), d.dep_type.clone()))
+        .collect();
+    found.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        found,
+        vec![
+            ("click".to_string(), DependencyType::Main),
+            (
+                "pytest".to_string(),
+                DependencyType::Group("test".to_string())
+            ),
+            ("requests".to_string(), DependencyType::Main),
+            ("ruff".to_string(), DependencyType::Dev),
+        ]
+    );
+}
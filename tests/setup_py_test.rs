@@ -483,3 +483,210 @@ requires-python = ">=3.8"
         updated_content.contains(r#"urls = { repository = "https://gitlab.com/updated/project" }"#)
     );
 }
+
+/// Test migrating a legacy package that keeps everything in setup.cfg.
+///
+/// This test verifies that:
+/// 1. The project is detected as setuptools with an empty setup.py
+/// 2. install_requires, tests_require and extras_require become dependencies
+/// 3. Name, version, description and authors are read from [metadata]
+#[test]
+fn test_setup_cfg_metadata_with_empty_setup_py() {
+    let setup_cfg = r#"
+[metadata]
+name = legacy-package
+version = 1.4.2
+author = Jane Doe
+author_email = jane@example.com
+description = A package configured in setup.cfg
+
+[options]
+packages = find:
+install_requires =
+    requests>=2.31.0
+    click==8.1.7
+tests_require =
+    pytest>=8.0
+
+[options.extras_require]
+yaml =
+    pyyaml>=6.0
+"#;
+    let (_temp_dir, project_dir) = create_test_project("", None);
+    fs::write(project_dir.join("setup.cfg"), setup_cfg).unwrap();
+
+    assert_eq!(
+        uv_migrator::migrators::detect::detect_project_type(&project_dir).unwrap(),
+        uv_migrator::migrators::detect::ProjectType::SetupPy
+    );
+
+    let dependencies = SetupPyMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    assert_eq!(dependencies.len(), 4);
+
+    let requests = dependencies.iter().find(|d| d.name == "requests").unwrap();
+    assert_eq!(requests.version, Some(">=2.31.0".to_string()));
+    assert_eq!(requests.dep_type, DependencyType::Main);
+
+    let pytest = dependencies.iter().find(|d| d.name == "pytest").unwrap();
+    assert_eq!(pytest.dep_type, DependencyType::Dev);
+
+    let pyyaml = dependencies.iter().find(|d| d.name == "pyyaml").unwrap();
+    assert_eq!(
+        pyyaml.dep_type,
+        DependencyType::Optional("yaml".to_string())
+    );
+
+    assert_eq!(
+        SetupPyMigrationSource::extract_name(&project_dir).unwrap(),
+        Some("legacy-package".to_string())
+    );
+    assert_eq!(
+        SetupPyMigrationSource::extract_description(&project_dir).unwrap(),
+        Some("A package configured in setup.cfg".to_string())
+    );
+    assert_eq!(
+        uv_migrator::utils::version::extract_version(&project_dir).unwrap(),
+        Some("1.4.2".to_string())
+    );
+
+    let authors = extract_authors_from_setup_py(&project_dir).unwrap();
+    assert_eq!(authors.len(), 1);
+    assert_eq!(authors[0].name, "Jane Doe");
+    assert_eq!(authors[0].email, Some("jane@example.com".to_string()));
+}
+
+/// Test that extras_require entries become dependency groups.
+///
+/// This test verifies that:
+/// 1. Each named extra is migrated as a dependency group of the same name
+/// 2. The dev extra is migrated as dev dependencies
+/// 3. An `all` extra built from other extras by item assignment is flattened
+#[test]
+fn test_setup_py_extras_require_groups() {
+    let setup_content = r#"
+from setuptools import setup
+
+extras_require = {
+    "dev": ["pytest>=7.0.0", "black"],
+    # documentation tooling
+    "docs": [
+        "sphinx>=5.0",
+        "furo",
+    ],
+}
+extras_require["all"] = extras_require["dev"] + extras_require["docs"]
+
+setup(
+    name="demo",
+    install_requires=["requests>=2.31.0"],
+    extras_require=extras_require,
+)
+"#;
+
+    let (_temp_dir, project_dir) = create_test_project(setup_content, None);
+    let dependencies = SetupPyMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let names_of = |dep_type: DependencyType| -> Vec<&str> {
+        dependencies
+            .iter()
+            .filter(|d| d.dep_type == dep_type)
+            .map(|d| d.name.as_str())
+            .collect()
+    };
+
+    assert_eq!(names_of(DependencyType::Main), vec!["requests"]);
+    assert_eq!(names_of(DependencyType::Dev), vec!["pytest", "black"]);
+    assert_eq!(
+        names_of(DependencyType::Group("docs".to_string())),
+        vec!["sphinx", "furo"]
+    );
+    assert_eq!(
+        names_of(DependencyType::Group("all".to_string())),
+        vec!["pytest", "black", "sphinx", "furo"]
+    );
+
+    let sphinx = dependencies.iter().find(|d| d.name == "sphinx").unwrap();
+    assert_eq!(sphinx.version, Some(">=5.0".to_string()));
+}
+
+/// Test a setup.py that calls `setuptools.setup` through the module.
+///
+/// This test verifies that:
+/// 1. The qualified `setuptools.setup(...)` call is found
+/// 2. Dependencies and metadata are read from its arguments
+#[test]
+fn test_setup_py_qualified_setup_call() {
+    let setup_content = r#"
+import setuptools
+
+setuptools.setup(
+    name="qualified",
+    version="2.1.0",
+    description="Qualified setup call",
+    install_requires=[
+        'requests>=2.31.0',
+    ],
+)
+"#;
+
+    let (_temp_dir, project_dir) = create_test_project(setup_content, None);
+    let dependencies = SetupPyMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(dependencies[0].name, "requests");
+    assert_eq!(
+        SetupPyMigrationSource::extract_description(&project_dir).unwrap(),
+        Some("Qualified setup call".to_string())
+    );
+    assert_eq!(
+        uv_migrator::utils::version::extract_version(&project_dir).unwrap(),
+        Some("2.1.0".to_string())
+    );
+}
+
+/// Test a setup.py that calls an aliased `setup` with unpacked keyword arguments.
+///
+/// This test verifies that:
+/// 1. `from setuptools import setup as _s` makes `_s(...)` the setup call
+/// 2. A helper named `def setup(...)` is not mistaken for the call
+/// 3. `**kwargs` is expanded from the dict literal it refers to
+#[test]
+fn test_setup_py_aliased_setup_with_kwargs() {
+    let setup_content = r#"
+from setuptools import find_packages, setup as _s
+
+
+def setup(app):
+    pass
+
+
+kwargs = {
+    "name": "aliased",
+    "description": "Aliased setup call",
+    "install_requires": [
+        "click>=8.0",
+        "rich",
+    ],
+}
+
+_s(**kwargs)
+"#;
+
+    let (_temp_dir, project_dir) = create_test_project(setup_content, None);
+    let dependencies = SetupPyMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let names: Vec<&str> = dependencies.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["click", "rich"]);
+    assert_eq!(
+        SetupPyMigrationSource::extract_description(&project_dir).unwrap(),
+        Some("Aliased setup call".to_string())
+    );
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+use uv_migrator::cli::Args;
+use uv_migrator::migrators::tox::ToxMigrationSource;
+use uv_migrator::migrators::{plan_migration, DependencyType, MigrationSource};
+
+/// Helper function to create a temporary test project with a tox.ini file.
+///
+/// # Arguments
+///
+/// * `content` - The content to write to tox.ini
+///
+/// # Returns
+///
+/// A tuple containing the temporary directory and its path
+fn create_test_project(content: &str) -> (TempDir, PathBuf) {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().to_path_buf();
+    fs::write(project_dir.join("tox.ini"), content).unwrap();
+    (temp_dir, project_dir)
+}
+
+/// Test extraction of the base `[testenv]` dependencies.
+///
+/// This test verifies that:
+/// 1. `[testenv]` deps become dev dependencies, keeping their versions
+/// 2. `-r` lines are read through the requirements parser, `{toxinidir}` included
+/// 3. Factor-conditional entries and other sections are ignored
+#[test]
+fn test_base_testenv_dependencies() {
+    let content = r#"
+[tox]
+envlist = py311, py312
+
+[testenv]
+deps =
+    pytest>=8.0
+    -r{toxinidir}/requirements-test.txt
+    py311: tomli
+commands = pytest
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+    fs::write(
+        project_dir.join("requirements-test.txt"),
+        "coverage==7.5.0\n",
+    )
+    .unwrap();
+
+    let dependencies = ToxMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+
+    let names: Vec<&str> = dependencies.iter().map(|d| d.name.as_str()).collect();
+    assert_eq!(names, vec!["pytest", "coverage"]);
+    assert!(dependencies
+        .iter()
+        .all(|d| d.dep_type == DependencyType::Dev));
+    assert_eq!(dependencies[0].version.as_deref(), Some(">=8.0"));
+    assert_eq!(dependencies[1].version.as_deref(), Some("7.5.0"));
+}
+
+/// Test that named test environments become dependency groups.
+///
+/// This test verifies that:
+/// 1. `[testenv:lint]` deps are migrated into a `lint` group
+/// 2. tox.ini dependencies are merged into the plan of the primary source
+/// 3. A tox dependency already declared by the primary source is not added twice
+#[test]
+fn test_named_testenv_merged_into_plan() {
+    let content = r#"
+[testenv:lint]
+deps = ruff>=0.4
+
+[testenv]
+deps =
+    pytest
+    requests
+"#;
+    let (_temp_dir, project_dir) = create_test_project(content);
+    fs::write(project_dir.join("requirements.txt"), "requests==2.31.0\n").unwrap();
+    fs::write(project_dir.join("requirements-dev.txt"), "pytest\n").unwrap();
+
+    let dependencies = ToxMigrationSource
+        .extract_dependencies(&project_dir)
+        .unwrap();
+    let ruff = dependencies.iter().find(|d| d.name == "ruff").unwrap();
+    assert_eq!(ruff.dep_type, DependencyType::Group("lint".to_string()));
+
+    let args = Args {
+        dry_run: true,
+        ..Args::default()
+    };
+    let plan = plan_migration(&project_dir, &args).unwrap();
+    assert!(plan
+        .add_args
+        .contains(&vec!["add".to_string(), "requests==2.31.0".to_string()]));
+    assert!(plan.add_args.contains(&vec![
+        "add".to_string(),
+        "--dev".to_string(),
+        "pytest".to_string()
+    ]));
+    assert!(plan.add_args.contains(&vec![
+        "add".to_string(),
+        "--group".to_string(),
+        "lint".to_string(),
+        "ruff>=0.4".to_string()
+    ]));
+}